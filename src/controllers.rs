@@ -1,7 +1,18 @@
+pub(crate) mod annotation_controller;
 pub(crate) mod api_controller;
+pub(crate) mod bookmark_controller;
+pub(crate) mod collection_controller;
 pub(crate) mod collectionner_controller;
 pub(crate) mod common_controller;
 pub(crate) mod database_controller;
+pub(crate) mod library_controller;
+pub(crate) mod metadata_controller;
+pub(crate) mod on_deck_controller;
 pub(crate) mod profile_controller;
+pub(crate) mod reading_history_controller;
+pub(crate) mod reading_order_controller;
+pub(crate) mod resource_controller;
+pub(crate) mod search_controller;
 pub(crate) mod settings_controller;
+pub(crate) mod statistics_controller;
 pub(crate) mod viewer_controller;
@@ -0,0 +1,160 @@
+use std::{path::Path, sync::Arc};
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use tokio::sync::Mutex;
+use tracing::{error, info};
+
+use crate::{
+    controllers::common_controller::{get_profile_db, get_profile_path},
+    routes_manager::AppState,
+    services::annotation_service::{
+        AnnotationChanges, AnnotationPayload, ExportFormat, create_annotation, delete_annotation,
+        export_annotations, get_page_manifest, list_book_annotations, update_annotation,
+    },
+};
+
+#[derive(Deserialize)]
+pub struct AnnotationQuery {
+    page: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct ExportQuery {
+    format: Option<ExportFormat>,
+}
+
+fn annotation_error(e: Box<dyn std::error::Error + Send + Sync>) -> Response {
+    match e.downcast_ref::<sqlx::Error>() {
+        Some(e) => {
+            error!("Failed to write annotation: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+        }
+        None => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn list_annotations_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, book_id)): axum::extract::Path<(String, String)>,
+    Query(query): Query<AnnotationQuery>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match list_book_annotations(&pool, &book_id, query.page).await {
+        Ok(annotations) => (StatusCode::OK, Json(annotations)).into_response(),
+        Err(e) => annotation_error(e.into()),
+    }
+}
+
+pub async fn create_annotation_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, book_id)): axum::extract::Path<(String, String)>,
+    Json(payload): Json<AnnotationPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match create_annotation(&pool, &book_id, &payload).await {
+        Ok(annotation) => {
+            info!("Created annotation {} on {}", annotation.id, book_id);
+            (StatusCode::CREATED, Json(annotation)).into_response()
+        }
+        Err(e) => annotation_error(e),
+    }
+}
+
+pub async fn update_annotation_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, book_id, id)): axum::extract::Path<(String, String, i64)>,
+    Json(payload): Json<AnnotationChanges>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match update_annotation(&pool, &book_id, id, &payload).await {
+        Ok(Some(annotation)) => (StatusCode::OK, Json(annotation)).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "Annotation not found").into_response(),
+        Err(e) => annotation_error(e),
+    }
+}
+
+pub async fn delete_annotation_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, book_id, id)): axum::extract::Path<(String, String, i64)>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match delete_annotation(&pool, &book_id, id).await {
+        Ok(true) => {
+            info!("Deleted annotation {}", id);
+            (StatusCode::OK, "Delete successful").into_response()
+        }
+        Ok(false) => (StatusCode::NOT_FOUND, "Annotation not found").into_response(),
+        Err(e) => annotation_error(e.into()),
+    }
+}
+
+pub async fn export_annotations_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, book_id)): axum::extract::Path<(String, String)>,
+    Query(query): Query<ExportQuery>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    let format = query.format.unwrap_or(ExportFormat::Markdown);
+    match export_annotations(&pool, &book_id, format).await {
+        Ok(Some(export)) => {
+            let (content_type, extension) = match format {
+                ExportFormat::Markdown => ("text/markdown; charset=utf-8", "md"),
+                ExportFormat::Json => ("application/json", "json"),
+            };
+            let mut headers = HeaderMap::new();
+            headers.insert("Content-Type", content_type.parse().unwrap());
+            headers.insert(
+                "Content-Disposition",
+                format!("attachment; filename=\"annotations.{}\"", extension)
+                    .parse()
+                    .unwrap(),
+            );
+            (StatusCode::OK, headers, export).into_response()
+        }
+        Ok(None) => (StatusCode::NOT_FOUND, "Book not found").into_response(),
+        Err(e) => annotation_error(e.into()),
+    }
+}
+
+pub async fn get_page_manifest_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+) -> impl IntoResponse {
+    let state = state.lock().await;
+    let pool = match get_profile_db(&state, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    let Some(profile_path) = get_profile_path(&state, &token).await else {
+        return (StatusCode::UNAUTHORIZED, "Invalid token").into_response();
+    };
+    drop(state);
+    let current_book = format!("{}/current_book", profile_path);
+    match get_page_manifest(&pool, Path::new(&current_book)).await {
+        Ok(Some(manifest)) => (StatusCode::OK, Json(manifest)).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "No book is currently open").into_response(),
+        Err(e) => annotation_error(e.into()),
+    }
+}
@@ -1,13 +1,18 @@
 use crate::repositories::database_repo::insert_into_db;
 use crate::routes_manager::AppState;
 use crate::services::anilist_service::{api_anilist_get, api_anilist_get_search};
+use crate::services::collection_service::ItemType;
+use crate::services::credit_service::{Credit, CreditKind, set_item_credits};
 use crate::services::googlebooks_service::search_gbapi_comics_by_name;
+use crate::services::library_service::adopt_manual_series;
 use crate::services::marvel_service::{
     api_marvel_get, get_marvel_api_characters, get_marvel_api_comics, get_marvel_api_creators,
     get_marvel_api_relations, get_marvel_api_search,
 };
 use crate::services::openlibrary_service::get_olapi_search;
+use crate::services::parser_service::get_series_title;
 use crate::services::profile_service::resolve_token;
+use crate::utils::strip_outer_quotes;
 use axum::Json;
 use axum::extract::State;
 use axum::http::StatusCode;
@@ -227,9 +232,13 @@ pub async fn marvel_add(
                     .split(",")
                     .map(|s| s.trim().to_string())
                     .collect::<Vec<String>>();
+                let series_id = strip_outer_quotes(&values_vec[0]).to_string();
                 insert_into_db(&pool, "Series", Some(columns_to_vec), values_vec)
                     .await
                     .expect("Failed to insert into Series");
+                if let Err(e) = adopt_manual_series(&pool, &path, &series_id).await {
+                    error!("Failed to link books to series {}: {}", series_id, e);
+                }
             } else {
                 let temp_data = data
                     .get("data")
@@ -310,9 +319,13 @@ pub async fn marvel_add(
                     .split(",")
                     .map(|s| s.trim().to_string())
                     .collect::<Vec<String>>();
+                let series_id = strip_outer_quotes(&values_vec[0]).to_string();
                 insert_into_db(&pool, "Series", Some(columns_to_vec), values_vec)
                     .await
                     .expect("Failed to insert into Series");
+                if let Err(e) = adopt_manual_series(&pool, &path, &series_id).await {
+                    error!("Failed to link books to series {}: {}", series_id, e);
+                }
 
                 match get_marvel_api_creators(
                     temp_data.get("id").unwrap().as_str().unwrap(),
@@ -326,14 +339,17 @@ pub async fn marvel_add(
                         let creators_data =
                             creators_data.get("data").unwrap().get("results").unwrap();
                         let creators = creators_data.as_array().unwrap();
+                        let mut credits = Vec::new();
                         for creator in creators {
+                            let credit_id = creator.get("id").unwrap().to_string() + "_1";
+                            let credit_name = creator
+                                .get("fullName")
+                                .and_then(|name| name.as_str())
+                                .unwrap_or_default();
+                            credits.push(Credit::new(&credit_id, credit_name, None));
                             let values_vec = vec![
-                                (creator.get("id").unwrap().to_string() + "_1"),
-                                creator
-                                    .get("fullName")
-                                    .unwrap()
-                                    .to_string()
-                                    .replace("'", "''"),
+                                credit_id,
+                                credit_name.to_string(),
                                 creator
                                     .get("thumbnail")
                                     .unwrap()
@@ -349,6 +365,17 @@ pub async fn marvel_add(
                                 .await
                                 .expect("Failed to insert into Creators");
                         }
+                        if let Err(e) = set_item_credits(
+                            &pool,
+                            CreditKind::Creator,
+                            ItemType::Series,
+                            &series_id,
+                            &credits,
+                        )
+                        .await
+                        {
+                            error!("Failed to link creators to series {}: {}", series_id, e);
+                        }
                     }
                     Err(e) => {
                         error!("Error fetching data from Marvel API: {}", e);
@@ -372,14 +399,17 @@ pub async fn marvel_add(
                         let characters_data =
                             characters_data.get("data").unwrap().get("results").unwrap();
                         let characters = characters_data.as_array().unwrap();
+                        let mut credits = Vec::new();
                         for character in characters {
+                            let credit_id = character.get("id").unwrap().to_string() + "_1";
+                            let credit_name = character
+                                .get("name")
+                                .and_then(|name| name.as_str())
+                                .unwrap_or_default();
+                            credits.push(Credit::new(&credit_id, credit_name, None));
                             let values_vec = vec![
-                                (character.get("id").unwrap().to_string() + "_1"),
-                                character
-                                    .get("name")
-                                    .unwrap()
-                                    .to_string()
-                                    .replace("'", "''"),
+                                credit_id,
+                                credit_name.to_string(),
                                 character
                                     .get("thumbnail")
                                     .unwrap()
@@ -399,6 +429,17 @@ pub async fn marvel_add(
                                 .await
                                 .expect("Failed to insert into Characters");
                         }
+                        if let Err(e) = set_item_credits(
+                            &pool,
+                            CreditKind::Character,
+                            ItemType::Series,
+                            &series_id,
+                            &credits,
+                        )
+                        .await
+                        {
+                            error!("Failed to link characters to series {}: {}", series_id, e);
+                        }
                     }
                     Err(e) => {
                         error!("Error fetching data from Marvel API: {}", e);
@@ -521,7 +562,7 @@ pub async fn anilist_add(
         }
     };
 
-    match api_anilist_get(name.as_str()).await {
+    match api_anilist_get(&get_series_title(&name)).await {
         Ok(data) => {
             let columns = "ID_Series,title,note,statut,start_date,end_date,description,Score,genres,cover,BG,CHARACTERS,TRENDING,STAFF,SOURCE,volumes,chapters,favorite,PATH,lock";
             if data.is_none() {
@@ -552,9 +593,13 @@ pub async fn anilist_add(
                     .split(",")
                     .map(|s| s.trim().to_string())
                     .collect::<Vec<String>>();
+                let series_id = strip_outer_quotes(&values_vec[0]).to_string();
                 insert_into_db(&pool, "Series", Some(columns_to_vec), values_vec)
                     .await
                     .expect("Failed to insert into Series");
+                if let Err(e) = adopt_manual_series(&pool, &path, &series_id).await {
+                    error!("Failed to link books to series {}: {}", series_id, e);
+                }
             }
             let base_data = data.clone().unwrap().get("base").unwrap().clone();
             let relations_data = data.clone().unwrap().get("relations").unwrap().clone();
@@ -630,20 +675,26 @@ pub async fn anilist_add(
                 .split(",")
                 .map(|s| s.trim().to_string())
                 .collect::<Vec<String>>();
+            let series_id = strip_outer_quotes(&values_vec[0]).to_string();
             insert_into_db(&pool, "Series", Some(columns_to_vec), values_vec)
                 .await
                 .expect("Failed to insert into Series");
+            if let Err(e) = adopt_manual_series(&pool, &path, &series_id).await {
+                error!("Failed to link books to series {}: {}", series_id, e);
+            }
             let staff = staff_data.as_array().unwrap();
+            let mut credits = Vec::new();
             for staff in staff {
+                let credit_id = staff.get("id").unwrap().to_string() + "_2";
+                let credit_name = staff
+                    .get("name")
+                    .and_then(|name| name.get("full"))
+                    .and_then(|name| name.as_str())
+                    .unwrap_or_default();
+                credits.push(Credit::new(&credit_id, credit_name, None));
                 let values_vec = vec![
-                    (staff.get("id").unwrap().to_string() + "_2"),
-                    staff
-                        .get("name")
-                        .unwrap()
-                        .get("full")
-                        .unwrap()
-                        .to_string()
-                        .replace("'", "''"),
+                    credit_id,
+                    credit_name.to_string(),
                     staff
                         .get("image")
                         .unwrap()
@@ -664,17 +715,30 @@ pub async fn anilist_add(
                     .await
                     .expect("Failed to insert into Staff");
             }
+            if let Err(e) = set_item_credits(
+                &pool,
+                CreditKind::Creator,
+                ItemType::Series,
+                &series_id,
+                &credits,
+            )
+            .await
+            {
+                error!("Failed to link creators to series {}: {}", series_id, e);
+            }
             let characters = characters_data.as_array().unwrap();
+            let mut credits = Vec::new();
             for character in characters {
+                let credit_id = character.get("id").unwrap().to_string() + "_2";
+                let credit_name = character
+                    .get("name")
+                    .and_then(|name| name.get("full"))
+                    .and_then(|name| name.as_str())
+                    .unwrap_or_default();
+                credits.push(Credit::new(&credit_id, credit_name, None));
                 let values_vec = vec![
-                    (character.get("id").unwrap().to_string() + "_2"),
-                    character
-                        .get("name")
-                        .unwrap()
-                        .get("full")
-                        .unwrap()
-                        .to_string()
-                        .replace("'", "''"),
+                    credit_id,
+                    credit_name.to_string(),
                     character
                         .get("image")
                         .unwrap()
@@ -695,6 +759,17 @@ pub async fn anilist_add(
                     .await
                     .expect("Failed to insert into Characters");
             }
+            if let Err(e) = set_item_credits(
+                &pool,
+                CreditKind::Character,
+                ItemType::Series,
+                &series_id,
+                &credits,
+            )
+            .await
+            {
+                error!("Failed to link characters to series {}: {}", series_id, e);
+            }
             let relations = relations_data.as_array().unwrap();
             for relation in relations {
                 let values_vec = vec![
@@ -0,0 +1,171 @@
+use std::{fs, path::Path, sync::Arc};
+
+use axum::{
+    Json,
+    extract::State,
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+};
+use reqwest::StatusCode;
+use tokio::sync::Mutex;
+use tracing::{error, info};
+
+use crate::{
+    controllers::common_controller::{get_profile_db, get_profile_path},
+    routes_manager::AppState,
+    services::bookmark_service::{
+        BookmarkChanges, NewBookmark, create_bookmark, delete_bookmark, jump_to_bookmark,
+        list_book_bookmarks, refresh_bookmark_thumbnail, update_bookmark,
+    },
+};
+
+fn bookmark_error(e: Box<dyn std::error::Error + Send + Sync>) -> Response {
+    match e.downcast_ref::<sqlx::Error>() {
+        Some(e) => {
+            error!("Failed to write bookmark: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+        }
+        None => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+fn get_bookmark_dirs(profile_path: &str) -> (String, String) {
+    (
+        format!("{}/current_book", profile_path),
+        format!("{}/bookmarks", profile_path),
+    )
+}
+
+pub async fn list_book_bookmarks_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, book_id)): axum::extract::Path<(String, String)>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match list_book_bookmarks(&pool, &book_id).await {
+        Ok(bookmarks) => (StatusCode::OK, Json(bookmarks)).into_response(),
+        Err(e) => bookmark_error(e.into()),
+    }
+}
+
+pub async fn create_bookmark_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Json(payload): Json<NewBookmark>,
+) -> impl IntoResponse {
+    let state = state.lock().await;
+    let pool = match get_profile_db(&state, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    let Some(profile_path) = get_profile_path(&state, &token).await else {
+        return (StatusCode::UNAUTHORIZED, "Invalid token").into_response();
+    };
+    drop(state);
+    let mut bookmark = match create_bookmark(&pool, &payload).await {
+        Ok(bookmark) => bookmark,
+        Err(e) => return bookmark_error(e),
+    };
+    let (current_book, output_dir) = get_bookmark_dirs(&profile_path);
+    match refresh_bookmark_thumbnail(
+        &pool,
+        &bookmark,
+        Path::new(&current_book),
+        Path::new(&output_dir),
+    )
+    .await
+    {
+        Ok(thumbnail) => bookmark.thumbnail = thumbnail,
+        Err(e) => error!(
+            "Failed to build thumbnail of bookmark {}: {}",
+            bookmark.id, e
+        ),
+    }
+    info!("Created bookmark {} on {}", bookmark.id, bookmark.book_id);
+    (StatusCode::CREATED, Json(bookmark)).into_response()
+}
+
+pub async fn update_bookmark_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+    Json(payload): Json<BookmarkChanges>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match update_bookmark(&pool, id, &payload).await {
+        Ok(Some(bookmark)) => (StatusCode::OK, Json(bookmark)).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "Bookmark not found").into_response(),
+        Err(e) => bookmark_error(e),
+    }
+}
+
+pub async fn delete_bookmark_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match delete_bookmark(&pool, id).await {
+        Ok(Some(_)) => {
+            info!("Deleted bookmark {}", id);
+            (StatusCode::OK, "Delete successful").into_response()
+        }
+        Ok(None) => (StatusCode::NOT_FOUND, "Bookmark not found").into_response(),
+        Err(e) => bookmark_error(e.into()),
+    }
+}
+
+pub async fn get_bookmark_thumbnail_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+) -> impl IntoResponse {
+    let Some(profile_path) = get_profile_path(&*state.lock().await, &token).await else {
+        return (StatusCode::UNAUTHORIZED, "Invalid token").into_response();
+    };
+    let (_, output_dir) = get_bookmark_dirs(&profile_path);
+    match fs::read(Path::new(&output_dir).join(format!("{}.webp", id))) {
+        Ok(image_bytes) => {
+            let mut headers = HeaderMap::new();
+            headers.insert("Content-Type", "image/webp".parse().unwrap());
+            (StatusCode::OK, headers, image_bytes).into_response()
+        }
+        Err(_) => (StatusCode::NOT_FOUND, "Thumbnail not found").into_response(),
+    }
+}
+
+pub async fn jump_to_bookmark_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+) -> impl IntoResponse {
+    let state = state.lock().await;
+    let pool = match get_profile_db(&state, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    let Some(profile_path) = get_profile_path(&state, &token).await else {
+        return (StatusCode::UNAUTHORIZED, "Invalid token").into_response();
+    };
+    let global_vars = state.global_vars.clone();
+    drop(state);
+    let (current_book, output_dir) = get_bookmark_dirs(&profile_path);
+    match jump_to_bookmark(
+        &pool,
+        id,
+        Path::new(&current_book),
+        Path::new(&output_dir),
+        token,
+        &global_vars,
+    )
+    .await
+    {
+        Ok(Some(jump)) => (StatusCode::OK, Json(jump)).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "Bookmark not found").into_response(),
+        Err(e) => bookmark_error(e),
+    }
+}
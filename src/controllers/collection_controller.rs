@@ -0,0 +1,434 @@
+use std::{fs, path::Path, sync::Arc};
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    http::HeaderMap,
+    response::{IntoResponse, Response},
+};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+use sqlx::SqlitePool;
+use tokio::sync::Mutex;
+use tracing::{error, info};
+
+use crate::{
+    controllers::common_controller::{get_profile_db, get_profile_path},
+    routes_manager::AppState,
+    services::collection_service::{
+        ItemType, add_items_to_collection, collection_exists, create_collection, delete_collection,
+        get_collection, list_collections, refresh_collection_cover, remove_items_from_collection,
+        reorder_collection_items, reorder_collections, update_collection,
+    },
+    services::smart_collection_service::{
+        SmartQuery, SmartRules, create_smart_collection, evaluate_smart_rules, get_smart_rules,
+        update_smart_rules, validate_rules,
+    },
+};
+
+#[derive(Deserialize)]
+pub struct CollectionPayload {
+    name: String,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct SmartCollectionPayload {
+    name: String,
+    description: Option<String>,
+    rules: SmartRules,
+}
+
+#[derive(Deserialize)]
+pub struct SmartRulesPayload {
+    rules: SmartRules,
+}
+
+#[derive(Deserialize)]
+pub struct CollectionItemsPayload {
+    #[serde(default)]
+    books: Vec<String>,
+    #[serde(default)]
+    series: Vec<String>,
+}
+
+impl CollectionItemsPayload {
+    fn into_items(self) -> Vec<(ItemType, String)> {
+        self.books
+            .into_iter()
+            .map(|id| (ItemType::Book, id))
+            .chain(self.series.into_iter().map(|id| (ItemType::Series, id)))
+            .collect()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ReorderCollectionsPayload {
+    collections: Vec<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct ReorderItemsPayload {
+    items: Vec<i64>,
+}
+
+fn internal_error(action: &str, e: sqlx::Error) -> Response {
+    error!("Failed to {}: {}", action, e);
+    (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+}
+
+async fn evaluate_rules_response(
+    pool: &SqlitePool,
+    rules: &SmartRules,
+    query: &SmartQuery,
+) -> Response {
+    match evaluate_smart_rules(pool, rules, query).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) if e.downcast_ref::<sqlx::Error>().is_some() => {
+            error!("Failed to evaluate smart collection: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+async fn get_cover_dirs(state: &AppState, token: &str) -> Option<(String, String)> {
+    let base_path = state.config.lock().await.base_path.clone();
+    let profile_path = get_profile_path(state, token).await?;
+    Some((
+        format!("{}/public/FirstImagesOfAll", base_path),
+        format!("{}/collections", profile_path),
+    ))
+}
+
+async fn refresh_cover(state: &AppState, token: &str, pool: &SqlitePool, collection_id: i64) {
+    let Some((covers_dir, output_dir)) = get_cover_dirs(state, token).await else {
+        return;
+    };
+    if let Err(e) = refresh_collection_cover(pool, collection_id, &covers_dir, &output_dir).await {
+        error!(
+            "Failed to refresh cover of collection {}: {}",
+            collection_id, e
+        );
+    }
+}
+
+pub async fn list_collections_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match list_collections(&pool).await {
+        Ok(collections) => (StatusCode::OK, Json(collections)).into_response(),
+        Err(e) => internal_error("list collections", e),
+    }
+}
+
+pub async fn create_collection_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Json(payload): Json<CollectionPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    if payload.name.trim().is_empty() {
+        return (StatusCode::BAD_REQUEST, "Name cannot be empty").into_response();
+    }
+    match create_collection(&pool, payload.name.trim(), payload.description.as_deref()).await {
+        Ok(id) => {
+            info!("Created collection {}", id);
+            (StatusCode::CREATED, Json(json!({ "id": id }))).into_response()
+        }
+        Err(e) => internal_error("create collection", e),
+    }
+}
+
+pub async fn reorder_collections_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Json(payload): Json<ReorderCollectionsPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match reorder_collections(&pool, &payload.collections).await {
+        Ok(true) => (StatusCode::OK, "Reorder successful").into_response(),
+        Ok(false) => (
+            StatusCode::BAD_REQUEST,
+            "Collections must list every collection exactly once",
+        )
+            .into_response(),
+        Err(e) => internal_error("reorder collections", e),
+    }
+}
+
+pub async fn get_collection_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match get_collection(&pool, id).await {
+        Ok(Some(collection)) => (StatusCode::OK, Json(collection)).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "Collection not found").into_response(),
+        Err(e) => internal_error("get collection", e),
+    }
+}
+
+pub async fn update_collection_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+    Json(payload): Json<CollectionPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    if payload.name.trim().is_empty() {
+        return (StatusCode::BAD_REQUEST, "Name cannot be empty").into_response();
+    }
+    match update_collection(
+        &pool,
+        id,
+        payload.name.trim(),
+        payload.description.as_deref(),
+    )
+    .await
+    {
+        Ok(true) => (StatusCode::OK, "Update successful").into_response(),
+        Ok(false) => (StatusCode::NOT_FOUND, "Collection not found").into_response(),
+        Err(e) => internal_error("update collection", e),
+    }
+}
+
+pub async fn delete_collection_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+) -> impl IntoResponse {
+    let state = state.lock().await;
+    let pool = match get_profile_db(&state, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match delete_collection(&pool, id).await {
+        Ok(true) => {
+            if let Some((_, output_dir)) = get_cover_dirs(&state, &token).await {
+                let _ = fs::remove_file(Path::new(&output_dir).join(format!("{}.webp", id)));
+            }
+            (StatusCode::OK, "Delete successful").into_response()
+        }
+        Ok(false) => (StatusCode::NOT_FOUND, "Collection not found").into_response(),
+        Err(e) => internal_error("delete collection", e),
+    }
+}
+
+pub async fn add_collection_items_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+    Json(payload): Json<CollectionItemsPayload>,
+) -> impl IntoResponse {
+    let state = state.lock().await;
+    let pool = match get_profile_db(&state, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match collection_exists(&pool, id).await {
+        Ok(true) => {}
+        Ok(false) => return (StatusCode::NOT_FOUND, "Collection not found").into_response(),
+        Err(e) => return internal_error("get collection", e),
+    }
+    match get_smart_rules(&pool, id).await {
+        Ok(None) => {}
+        Ok(Some(_)) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                "Smart collections cannot hold items",
+            )
+                .into_response();
+        }
+        Err(e) => return internal_error("get collection rules", e),
+    }
+    match add_items_to_collection(&pool, id, &payload.into_items()).await {
+        Ok(added) => {
+            refresh_cover(&state, &token, &pool, id).await;
+            (StatusCode::OK, Json(json!({ "added": added }))).into_response()
+        }
+        Err(e) => internal_error("add items to collection", e),
+    }
+}
+
+pub async fn remove_collection_items_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+    Json(payload): Json<CollectionItemsPayload>,
+) -> impl IntoResponse {
+    let state = state.lock().await;
+    let pool = match get_profile_db(&state, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match remove_items_from_collection(&pool, id, &payload.into_items()).await {
+        Ok(removed) => {
+            refresh_cover(&state, &token, &pool, id).await;
+            (StatusCode::OK, Json(json!({ "removed": removed }))).into_response()
+        }
+        Err(e) => internal_error("remove items from collection", e),
+    }
+}
+
+pub async fn reorder_collection_items_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+    Json(payload): Json<ReorderItemsPayload>,
+) -> impl IntoResponse {
+    let state = state.lock().await;
+    let pool = match get_profile_db(&state, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match reorder_collection_items(&pool, id, &payload.items).await {
+        Ok(true) => {
+            refresh_cover(&state, &token, &pool, id).await;
+            (StatusCode::OK, "Reorder successful").into_response()
+        }
+        Ok(false) => (
+            StatusCode::BAD_REQUEST,
+            "Items must list every item of the collection exactly once",
+        )
+            .into_response(),
+        Err(e) => internal_error("reorder collection items", e),
+    }
+}
+
+pub async fn refresh_collection_cover_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+) -> impl IntoResponse {
+    let state = state.lock().await;
+    let pool = match get_profile_db(&state, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    let Some((covers_dir, output_dir)) = get_cover_dirs(&state, &token).await else {
+        return (StatusCode::UNAUTHORIZED, "Invalid token").into_response();
+    };
+    match refresh_collection_cover(&pool, id, &covers_dir, &output_dir).await {
+        Ok(cover) => (StatusCode::OK, Json(json!({ "cover": cover }))).into_response(),
+        Err(e) => {
+            error!("Failed to refresh cover of collection {}: {}", id, e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to build cover").into_response()
+        }
+    }
+}
+
+pub async fn get_collection_cover_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+) -> impl IntoResponse {
+    let state = state.lock().await;
+    let Some((_, output_dir)) = get_cover_dirs(&state, &token).await else {
+        return (StatusCode::UNAUTHORIZED, "Invalid token").into_response();
+    };
+    match fs::read(Path::new(&output_dir).join(format!("{}.webp", id))) {
+        Ok(image_bytes) => {
+            let mut headers = HeaderMap::new();
+            headers.insert("Content-Type", "image/webp".parse().unwrap());
+            (StatusCode::OK, headers, image_bytes).into_response()
+        }
+        Err(_) => (StatusCode::NOT_FOUND, "Cover not found").into_response(),
+    }
+}
+
+pub async fn create_smart_collection_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Json(payload): Json<SmartCollectionPayload>,
+) -> impl IntoResponse {
+    let state = state.lock().await;
+    let pool = match get_profile_db(&state, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    if payload.name.trim().is_empty() {
+        return (StatusCode::BAD_REQUEST, "Name cannot be empty").into_response();
+    }
+    if let Err(e) = validate_rules(&payload.rules) {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+    match create_smart_collection(
+        &pool,
+        payload.name.trim(),
+        payload.description.as_deref(),
+        &payload.rules,
+    )
+    .await
+    {
+        Ok(id) => {
+            info!("Created smart collection {}", id);
+            refresh_cover(&state, &token, &pool, id).await;
+            (StatusCode::CREATED, Json(json!({ "id": id }))).into_response()
+        }
+        Err(e) => internal_error("create smart collection", e),
+    }
+}
+
+pub async fn preview_smart_collection_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Query(query): Query<SmartQuery>,
+    Json(payload): Json<SmartRulesPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    evaluate_rules_response(&pool, &payload.rules, &query).await
+}
+
+pub async fn update_smart_rules_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+    Json(payload): Json<SmartRulesPayload>,
+) -> impl IntoResponse {
+    let state = state.lock().await;
+    let pool = match get_profile_db(&state, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    if let Err(e) = validate_rules(&payload.rules) {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+    match update_smart_rules(&pool, id, &payload.rules).await {
+        Ok(true) => {
+            refresh_cover(&state, &token, &pool, id).await;
+            (StatusCode::OK, "Update successful").into_response()
+        }
+        Ok(false) => (StatusCode::NOT_FOUND, "Smart collection not found").into_response(),
+        Err(e) => internal_error("update smart collection", e),
+    }
+}
+
+pub async fn get_collection_members_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+    Query(query): Query<SmartQuery>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match get_smart_rules(&pool, id).await {
+        Ok(Some(rules)) => evaluate_rules_response(&pool, &rules, &query).await,
+        Ok(None) => (StatusCode::NOT_FOUND, "Smart collection not found").into_response(),
+        Err(e) => internal_error("get collection rules", e),
+    }
+}
@@ -1,17 +1,20 @@
 use crate::repositories::database_repo::get_db;
 use crate::routes_manager::AppState;
 use crate::services::collectionner_service::{
-    get_list_of_files_and_folders, get_list_of_folders, handle_anilist_series, handle_google_book,
-    handle_marvel_book, handle_marvel_series, handle_openlibrary_book,
+    get_list_of_files_and_folders, get_list_of_folders, refresh_item_metadata,
 };
+use crate::services::converter_service::get_cover_path;
+use crate::services::duplicate_service::{compute_missing_hashes, find_duplicates};
 use crate::services::googlebooks_service::search_gbapi_comics_by_name;
 use crate::services::marvel_service::{
     get_marvel_api_characters, get_marvel_api_comics, get_marvel_api_creators,
 };
 use crate::services::openlibrary_service::{get_olapi_book, get_olapi_search};
+use crate::services::pagination_service::{ListQuery, paginate_items};
+use crate::services::parser_service::{get_search_title, series_matches};
 use crate::services::profile_service::resolve_token;
 use axum::Json;
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::http::{HeaderMap, StatusCode};
 use axum::response::IntoResponse;
 use serde::Deserialize;
@@ -22,6 +25,10 @@ use std::path::Path;
 use std::sync::Arc;
 use tracing::{error, info};
 
+const BOOK_INSERT_COLUMNS: &str = "ID_book, API_ID, NOM, note, read, reading, unread, favorite, \
+    last_page, folder, PATH, URLCover, issueNumber, description, format, pageCount, URLs, series, \
+    creators, characters, prices, dates, collectedIssues, collections, variants, lock";
+
 #[derive(Deserialize)]
 pub struct FillBlankImagePayload {
     token: String,
@@ -82,15 +89,102 @@ pub async fn fill_blank_images_controller(
     }
 }
 
+#[derive(Deserialize)]
+pub struct CoverSizeQuery {
+    size: Option<String>,
+}
+
+pub async fn scan_duplicates_controller(
+    State(state): State<Arc<tokio::sync::Mutex<AppState>>>,
+    Json(payload): Json<FillBlankImagePayload>,
+) -> impl IntoResponse {
+    let state = &state.lock().await;
+    let config = state.config.lock().await;
+    let global = state.global_vars.lock().await;
+
+    let base_path = config.base_path.clone();
+    let token = payload.token.clone();
+
+    let resolved_token = match resolve_token(&token, &base_path) {
+        Some(t) => t,
+        None => return StatusCode::UNAUTHORIZED,
+    };
+
+    let pool = match crate::repositories::database_repo::get_db(
+        &resolved_token,
+        &base_path,
+        global.opened_db.clone(),
+    )
+    .await
+    {
+        Ok(pool) => pool,
+        Err(_) => {
+            error!("Error getting database pool");
+            return StatusCode::INTERNAL_SERVER_ERROR;
+        }
+    };
+
+    let progress_status = state.global_vars.clone();
+    tokio::spawn(async move {
+        if let Err(e) = compute_missing_hashes(pool, token, progress_status).await {
+            error!("Error computing book hashes: {}", e);
+        }
+    });
+
+    StatusCode::ACCEPTED
+}
+
+pub async fn get_duplicates_controller(
+    State(state): State<Arc<tokio::sync::Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+) -> impl IntoResponse {
+    let state = &state.lock().await;
+    let config = state.config.lock().await;
+    let global = state.global_vars.lock().await;
+
+    let base_path = config.base_path.clone();
+    let resolved_token = match resolve_token(&token, &base_path) {
+        Some(t) => t,
+        None => return StatusCode::UNAUTHORIZED.into_response(),
+    };
+
+    let pool = match crate::repositories::database_repo::get_db(
+        &resolved_token,
+        &base_path,
+        global.opened_db.clone(),
+    )
+    .await
+    {
+        Ok(pool) => pool,
+        Err(_) => {
+            error!("Error getting database pool");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    match find_duplicates(&pool).await {
+        Ok(duplicates) => (StatusCode::OK, Json(duplicates)).into_response(),
+        Err(e) => {
+            error!("Error finding duplicates: {}", e);
+            StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
+
 pub async fn first_images_of_all_image_getter(
     State(state): State<Arc<tokio::sync::Mutex<AppState>>>,
     axum::extract::Path(image_name): axum::extract::Path<String>,
+    Query(query): Query<CoverSizeQuery>,
 ) -> impl IntoResponse {
     let state = &state.lock().await;
     let config = state.config.lock().await;
 
     let base_path = config.base_path.clone();
-    let file_path = format!("{}/public/FirstImagesOfAll/{}", base_path, image_name);
+    let file_path = get_cover_path(
+        &format!("{}/public/FirstImagesOfAll", base_path),
+        &image_name,
+        query.size.as_deref(),
+    );
     if Path::new(&file_path).exists() {
         match fs::read(&file_path) {
             Ok(image_bytes) => {
@@ -171,9 +265,9 @@ pub async fn insert_anilist_book(
         };
 
         for ele in path.split('/') {
-            if ele == el["english"].as_str().unwrap_or_default()
-                || ele == el["romaji"].as_str().unwrap_or_default()
-                || ele == el["native"].as_str().unwrap_or_default()
+            if series_matches(ele, el["english"].as_str().unwrap_or_default())
+                || series_matches(ele, el["romaji"].as_str().unwrap_or_default())
+                || series_matches(ele, el["native"].as_str().unwrap_or_default())
             {
                 series_name = el["english"]
                     .as_str()
@@ -192,7 +286,7 @@ pub async fn insert_anilist_book(
 
     let random_id = format!("{}_2", rand::random::<u32>());
     let insert_query = format!(
-        "INSERT INTO Books VALUES ('{}', '{}', '{}', NULL, 0, 0, 1, 0, 0, 0, '{}', NULL, NULL, NULL, NULL, NULL, NULL, '{}', NULL, NULL, NULL, NULL, NULL, NULL, NULL, false);",
+        "INSERT INTO Books ({columns}) VALUES ('{}', '{}', '{}', NULL, 0, 0, 1, 0, 0, 0, '{}', NULL, NULL, NULL, NULL, NULL, NULL, '{}', NULL, NULL, NULL, NULL, NULL, NULL, NULL, false);",
         random_id,
         2,
         realname,
@@ -201,7 +295,8 @@ pub async fn insert_anilist_book(
             "Anilist_{}_{}",
             realname.replace(" ", "$"),
             series_name.replace(" ", "$")
-        )
+        ),
+        columns = BOOK_INSERT_COLUMNS,
     );
 
     match sqlx::query(&insert_query).execute(&pool).await {
@@ -280,7 +375,7 @@ pub async fn insert_marvel_book(
                 let page_count = comic["pageCount"].as_u64().unwrap_or(0);
 
                 let insert_query = format!(
-                    "INSERT INTO Books VALUES ('{}_1', '1', '{}', NULL, 0, 0, 1, 0, 0, 0, '{}', '{}', '{}', '{}', '{}', {}, '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', false);",
+                    "INSERT INTO Books ({columns}) VALUES ('{}_1', '1', '{}', NULL, 0, 0, 1, 0, 0, 0, '{}', '{}', '{}', '{}', '{}', {}, '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', '{}', false);",
                     comic["id"].as_u64().unwrap_or(0),
                     realname,
                     path,
@@ -297,7 +392,8 @@ pub async fn insert_marvel_book(
                     comic["dates"].to_string(),
                     comic["collectedIssues"].to_string(),
                     comic["collections"].to_string(),
-                    comic["variants"].to_string()
+                    comic["variants"].to_string(),
+                    columns = BOOK_INSERT_COLUMNS,
                 );
 
                 if let Err(err) = sqlx::query(&insert_query).execute(&pool).await {
@@ -360,8 +456,11 @@ pub async fn insert_marvel_book(
             } else {
                 let random_id = format!("{}_1", rand::random::<u32>());
                 let default_query = format!(
-                    "INSERT INTO Books VALUES ('{}', '1', '{}', NULL, 0, 0, 1, 0, 0, 0, '{}', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, false);",
-                    random_id, realname, path
+                    "INSERT INTO Books ({columns}) VALUES ('{}', '1', '{}', NULL, 0, 0, 1, 0, 0, 0, '{}', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, false);",
+                    random_id,
+                    realname,
+                    path,
+                    columns = BOOK_INSERT_COLUMNS,
                 );
 
                 if let Err(err) = sqlx::query(&default_query).execute(&pool).await {
@@ -419,7 +518,7 @@ pub async fn insert_googlebooks_book(
 
     let google_books_api_key = state.creds.lock().await.google_books_api_key.clone();
 
-    match search_gbapi_comics_by_name(&realname, google_books_api_key).await {
+    match search_gbapi_comics_by_name(&get_search_title(&realname), google_books_api_key).await {
         Ok(cdata) => {
             let total_items = cdata["totalItems"].as_u64().unwrap_or(0);
             if total_items > 0 {
@@ -450,7 +549,7 @@ pub async fn insert_googlebooks_book(
                 let published_date = book["volumeInfo"]["publishedDate"].to_string();
 
                 let insert_query = format!(
-                    "INSERT INTO Books VALUES ('{}_4', '4', '{}', NULL, 0, 0, 1, 0, 0, 0, '{}','{}', NULL, '{}', '{}', {}, '{}', NULL, '{}', NULL, {}, '{}', NULL, NULL, NULL, false);",
+                    "INSERT INTO Books ({columns}) VALUES ('{}_4', '4', '{}', NULL, 0, 0, 1, 0, 0, 0, '{}','{}', NULL, '{}', '{}', {}, '{}', NULL, '{}', NULL, {}, '{}', NULL, NULL, NULL, false);",
                     book["id"].as_str().unwrap_or_default(),
                     realname,
                     path,
@@ -461,7 +560,8 @@ pub async fn insert_googlebooks_book(
                     info_link,
                     authors.join(", ").replace("'", "''"),
                     retail_price,
-                    published_date
+                    published_date,
+                    columns = BOOK_INSERT_COLUMNS,
                 );
 
                 if let Err(err) = sqlx::query(&insert_query).execute(&pool).await {
@@ -483,8 +583,11 @@ pub async fn insert_googlebooks_book(
             } else {
                 let random_id = format!("{}_4", rand::random::<u32>());
                 let default_query = format!(
-                    "INSERT INTO Books VALUES ('{}', '4', '{}', NULL, 0, 0, 1, 0, 0, 0, '{}', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, false);",
-                    random_id, realname, path
+                    "INSERT INTO Books ({columns}) VALUES ('{}', '4', '{}', NULL, 0, 0, 1, 0, 0, 0, '{}', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, false);",
+                    random_id,
+                    realname,
+                    path,
+                    columns = BOOK_INSERT_COLUMNS,
                 );
 
                 if let Err(err) = sqlx::query(&default_query).execute(&pool).await {
@@ -540,7 +643,7 @@ pub async fn insert_olib_book(
         }
     };
 
-    match get_olapi_search(&realname).await {
+    match get_olapi_search(&get_search_title(&realname)).await {
         Ok(cdata) => {
             let num_found = cdata.num_found;
             if num_found > 0 {
@@ -579,7 +682,7 @@ pub async fn insert_olib_book(
                             .collect::<Vec<String>>();
 
                         let insert_query = format!(
-                            "INSERT INTO Books VALUES ('{}_3', '3', '{}', NULL, 0, 0, 1, 0, 0, 0, '{}', '{}', NULL, '{}', '{}', {}, '{}', NULL, '{}', NULL, NULL, '{}', NULL, NULL, NULL, false);",
+                            "INSERT INTO Books ({columns}) VALUES ('{}_3', '3', '{}', NULL, 0, 0, 1, 0, 0, 0, '{}', '{}', NULL, '{}', '{}', {}, '{}', NULL, '{}', NULL, NULL, '{}', NULL, NULL, NULL, false);",
                             book[first_child]["bib_key"].as_str().unwrap_or_default(),
                             realname,
                             path,
@@ -589,7 +692,8 @@ pub async fn insert_olib_book(
                             number_of_pages,
                             info_url,
                             authors.join(", ").replace("'", "''"),
-                            publish_date
+                            publish_date,
+                            columns = BOOK_INSERT_COLUMNS,
                         );
 
                         if let Err(err) = sqlx::query(&insert_query).execute(&pool).await {
@@ -619,8 +723,11 @@ pub async fn insert_olib_book(
             } else {
                 let random_id = format!("{}_3", rand::random::<u32>());
                 let default_query = format!(
-                    "INSERT INTO Books VALUES ('{}', '3', '{}', NULL, 0, 0, 1, 0, 0, 0, '{}', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, false);",
-                    random_id, realname, path
+                    "INSERT INTO Books ({columns}) VALUES ('{}', '3', '{}', NULL, 0, 0, 1, 0, 0, 0, '{}', NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, NULL, false);",
+                    random_id,
+                    realname,
+                    path,
+                    columns = BOOK_INSERT_COLUMNS,
                 );
 
                 if let Err(err) = sqlx::query(&default_query).execute(&pool).await {
@@ -674,66 +781,69 @@ pub async fn refresh_meta_controller(
         sanitized_id, payload.item_type, payload.provider
     );
 
-    match payload.provider {
-        1 => {
-            let marvel_pub_api_key = state.creds.lock().await.marvel_public_key.clone();
-            let marvel_priv_api_key = state.creds.lock().await.marvel_private_key.clone();
-            if payload.item_type == "book" {
-                if let Err(e) = handle_marvel_book(
-                    &pool,
-                    &sanitized_id,
-                    payload.provider,
-                    marvel_priv_api_key.clone(),
-                    marvel_pub_api_key.clone(),
-                )
-                .await
-                {
-                    error!("Error handling Marvel book: {}", e);
-                }
-            } else {
-                if let Err(e) = handle_marvel_series(
-                    &pool,
-                    &sanitized_id,
-                    payload.provider,
-                    marvel_priv_api_key.clone(),
-                    marvel_pub_api_key.clone(),
-                )
-                .await
-                {
-                    error!("Error handling Marvel series: {}", e);
-                }
-            }
-        }
-        2 => {
-            if payload.item_type != "book" {
-                if let Err(e) = handle_anilist_series(&pool, &sanitized_id, payload.provider).await
-                {
-                    error!("Error handling Anilist series: {}", e);
-                }
-            }
-        }
-        3 => {
-            if let Err(e) = handle_openlibrary_book(&pool, &sanitized_id, payload.provider).await {
-                error!("Error handling OpenLibrary book: {}", e);
-            }
-        }
-        4 => {
-            if let Err(e) = handle_google_book(&pool, &sanitized_id, payload.provider).await {
-                error!("Error handling Google Book: {}", e);
-            }
-        }
-        _ => return StatusCode::BAD_REQUEST.into_response(),
+    if !(1..=4).contains(&payload.provider) {
+        return StatusCode::BAD_REQUEST.into_response();
+    }
+    let marvel_pub_api_key = state.creds.lock().await.marvel_public_key.clone();
+    let marvel_priv_api_key = state.creds.lock().await.marvel_private_key.clone();
+    if let Err(e) = refresh_item_metadata(
+        &pool,
+        &sanitized_id,
+        &payload.item_type,
+        payload.provider,
+        marvel_priv_api_key,
+        marvel_pub_api_key,
+    )
+    .await
+    {
+        error!("Error refreshing metadata: {}", e);
     }
 
     StatusCode::OK.into_response()
 }
 
+fn paginate_paths(response: &Value, query: &ListQuery) -> axum::response::Response {
+    let items: Vec<serde_json::Map<String, Value>> = response
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|path| path.as_str())
+        .map(|path| {
+            let entry = Path::new(path);
+            let mut item = serde_json::Map::new();
+            item.insert("path".to_string(), Value::from(path));
+            item.insert(
+                "name".to_string(),
+                Value::from(
+                    entry
+                        .file_name()
+                        .map(|name| name.to_string_lossy().to_string())
+                        .unwrap_or_default(),
+                ),
+            );
+            item.insert("folder".to_string(), Value::from(entry.is_dir()));
+            item
+        })
+        .collect();
+    match paginate_items(
+        items,
+        query,
+        &["name", "path", "folder"],
+        &["name", "path", "folder"],
+    ) {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+    }
+}
+
 pub async fn get_list_of_files_and_folders_controller(
     State(_): State<Arc<tokio::sync::Mutex<AppState>>>,
     axum::extract::Path(path): axum::extract::Path<String>,
+    Query(query): Query<ListQuery>,
 ) -> impl IntoResponse {
     let dir = crate::utils::replace_html_address_path(&path);
     match get_list_of_files_and_folders(dir).await {
+        Ok(response) if query.is_requested() => paginate_paths(&response, &query),
         Ok(response) => {
             info!("List of files and folders fetched successfully");
             (StatusCode::OK, response).into_response()
@@ -750,9 +860,11 @@ pub async fn get_list_of_files_and_folders_controller(
 pub async fn get_list_of_folders_controller(
     State(_): State<Arc<tokio::sync::Mutex<AppState>>>,
     axum::extract::Path(path): axum::extract::Path<String>,
+    Query(query): Query<ListQuery>,
 ) -> impl IntoResponse {
     let dir = crate::utils::replace_html_address_path(&path);
     match get_list_of_folders(dir).await {
+        Ok(response) if query.is_requested() => paginate_paths(&response, &query),
         Ok(response) => (StatusCode::OK, response).into_response(),
         Err(e) => {
             error!("Error fetching list of folders: {}", e);
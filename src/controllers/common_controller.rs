@@ -1,8 +1,12 @@
 use crate::routes_manager::AppState;
+use crate::services::pagination_service::{ListQuery, paginate_items, to_items};
 use crate::services::profile_service::resolve_token;
 use crate::utils::replace_html_address_path;
 use axum::http::HeaderMap;
-use axum::{extract::State, response::IntoResponse};
+use axum::{
+    extract::{Query, State},
+    response::IntoResponse,
+};
 use reqwest::StatusCode;
 use rgb::RGB;
 use serde::{Deserialize, Serialize};
@@ -14,6 +18,29 @@ use tokio::sync::Mutex;
 use tracing::{error, info};
 use zip::write::FileOptions;
 
+pub async fn get_profile_db(
+    state: &AppState,
+    token: &str,
+) -> Result<sqlx::SqlitePool, axum::response::Response> {
+    let base_path = state.config.lock().await.base_path.clone();
+    let resolved_token = match resolve_token(token, &base_path) {
+        Some(t) => t,
+        None => return Err((StatusCode::UNAUTHORIZED, "Invalid token").into_response()),
+    };
+    let opened_db = state.global_vars.lock().await.opened_db.clone();
+    crate::repositories::database_repo::get_db(&resolved_token, &base_path, opened_db)
+        .await
+        .map_err(|_| {
+            error!("Error getting database pool");
+            (StatusCode::INTERNAL_SERVER_ERROR, "Failed to get DB").into_response()
+        })
+}
+
+pub async fn get_profile_path(state: &AppState, token: &str) -> Option<String> {
+    let base_path = state.config.lock().await.base_path.clone();
+    resolve_token(token, &base_path).map(|profile| format!("{}/profiles/{}", base_path, profile))
+}
+
 #[derive(Serialize)]
 struct SerializableRgb {
     r: u8,
@@ -201,6 +228,7 @@ pub struct Bookmark {
 pub async fn get_bookmarks(
     State(state): State<Arc<Mutex<AppState>>>,
     headers: HeaderMap,
+    Query(list_query): Query<ListQuery>,
 ) -> impl IntoResponse {
     let state = state.lock().await;
     let config = state.config.lock().await;
@@ -256,5 +284,16 @@ pub async fn get_bookmarks(
 
     info!("Bookmarks retrieved successfully");
 
+    if list_query.is_requested() {
+        return match paginate_items(
+            to_items(&bookmarks),
+            &list_query,
+            &["ID_BOOKMARK", "BOOK_ID", "page"],
+            &["ID_BOOKMARK", "BOOK_ID", "PATH", "page"],
+        ) {
+            Ok(page) => (StatusCode::OK, axum::Json(page)).into_response(),
+            Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+        };
+    }
     (StatusCode::OK, axum::Json(bookmarks)).into_response()
 }
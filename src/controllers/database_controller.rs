@@ -1,16 +1,42 @@
 use std::sync::Arc;
 
-use axum::{extract::State, response::IntoResponse};
+use axum::{
+    Json,
+    extract::{Query, Request, State},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
 use reqwest::StatusCode;
 use serde_json::{Value, json};
 use tokio::sync::Mutex;
 use tracing::{debug, error, info};
 
 use crate::{
-    repositories::database_repo::insert_into_db, routes_manager::AppState,
+    repositories::database_repo::{
+        get_select_columns, insert_into_db, select_page_from_db_with_options,
+    },
+    routes_manager::AppState,
+    services::pagination_service::{ListQuery, Page, get_fields, get_sort_key, sort_order},
+    services::parser_service::series_matches,
     services::profile_service::resolve_token,
+    services::scheduler_service::{get_library_schedules, is_valid_schedule},
 };
 
+pub async fn legacy_db_routes_guard(
+    State(state): State<Arc<Mutex<AppState>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !state.lock().await.config.lock().await.legacy_db_routes {
+        return (
+            StatusCode::GONE,
+            "Raw database routes are disabled, use the REST API instead",
+        )
+            .into_response();
+    }
+    next.run(request).await
+}
+
 pub async fn insert_db(
     State(state): State<Arc<Mutex<AppState>>>,
     axum::extract::Path((token, db_name)): axum::extract::Path<(String, String)>,
@@ -305,13 +331,12 @@ pub async fn update_db_one_for_all(
     for book in book_list {
         let path = book.get("PATH").unwrap().to_string();
         let title_json = serde_json::from_str::<Value>(&title).unwrap();
-        let en_title = title_json["english"]
-            .as_str()
-            .unwrap_or_default()
-            .to_string()
-            .to_lowercase()
-            .replace('"', "");
-        if path.to_lowercase().contains(&en_title) {
+        let matches_title = path.split('/').any(|segment| {
+            ["english", "romaji", "native"]
+                .iter()
+                .any(|key| series_matches(segment, title_json[key].as_str().unwrap_or_default()))
+        });
+        if matches_title {
             let asso = json!({
                 payload_a: 1,
                 payload_w1: 0,
@@ -381,15 +406,39 @@ pub async fn update_lib(
         Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to get DB").into_response(),
     };
 
-    let name = payload["name"].as_str().unwrap_or_default();
-    let path = payload["path"].as_str().unwrap_or_default();
-    let api = payload["api"].as_str().unwrap_or_default();
-
-    let col_vec = vec!["NAME".to_string(), "PATH".to_string(), "API_ID".to_string()];
-    let val_vec = vec![name.to_string(), path.to_string(), api.to_string()];
+    let mut col_vec = Vec::new();
+    let mut val_vec = Vec::new();
+    for (key, column) in [("name", "NAME"), ("path", "PATH"), ("api", "API_ID")] {
+        match payload.get(key) {
+            None => {}
+            Some(Value::String(value)) if !value.trim().is_empty() => {
+                col_vec.push(column.to_string());
+                val_vec.push(value.to_string());
+            }
+            Some(_) => {
+                return (StatusCode::BAD_REQUEST, format!("Invalid {}", key)).into_response();
+            }
+        }
+    }
+    for column in ["rescanSchedule", "refreshSchedule"] {
+        if let Some(schedule) = payload[column].as_str() {
+            if !schedule.trim().is_empty() && !is_valid_schedule(schedule) {
+                return (StatusCode::BAD_REQUEST, "Invalid schedule").into_response();
+            }
+            col_vec.push(column.to_string());
+            val_vec.push(schedule.to_string());
+        }
+    }
+    if let Some(enabled) = payload["scheduleEnabled"].as_bool() {
+        col_vec.push("scheduleEnabled".to_string());
+        val_vec.push(if enabled { "1" } else { "0" }.to_string());
+    }
+    if col_vec.is_empty() {
+        return (StatusCode::BAD_REQUEST, "No fields to update").into_response();
+    }
     if let Err(_) = crate::repositories::database_repo::update_db(
         &pool,
-        "no_edit",
+        "edit",
         col_vec,
         val_vec,
         "Libraries",
@@ -402,6 +451,23 @@ pub async fn update_lib(
         return (StatusCode::INTERNAL_SERVER_ERROR, "Update failed").into_response();
     }
 
+    let library_id = id.parse::<i64>().unwrap_or_default();
+    match get_library_schedules(&pool).await {
+        Ok(schedules) => {
+            if let Some(schedule) = schedules.iter().find(|s| s.library_id == library_id)
+                && let Err(e) = state
+                    .scheduler
+                    .lock()
+                    .await
+                    .schedule_library(&resolved_token, schedule)
+                    .await
+            {
+                error!("Failed to reschedule library {}: {}", id, e);
+            }
+        }
+        Err(e) => error!("Failed to read library schedules: {}", e),
+    }
+
     info!("Updated library: {}", id);
     (StatusCode::OK, "Update successful").into_response()
 }
@@ -538,14 +604,75 @@ pub async fn delete_lib(
         error!("Failed to delete from library");
         return (StatusCode::INTERNAL_SERVER_ERROR, "Delete failed").into_response();
     }
+    if let Ok(library_id) = id.parse::<i64>()
+        && let Err(e) = state
+            .scheduler
+            .lock()
+            .await
+            .unschedule_library(&resolved_token, library_id)
+            .await
+    {
+        error!("Failed to unschedule library {}: {}", id, e);
+    }
 
     info!("Deleted from library: {}", id);
     (StatusCode::OK, "Delete successful").into_response()
 }
 
+async fn get_db_page(pool: &sqlx::SqlitePool, request: &str, list_query: &ListQuery) -> Response {
+    let columns = match get_select_columns(pool, request).await {
+        Ok(columns) => columns,
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    let columns: Vec<&str> = columns.iter().map(String::as_str).collect();
+    let (sort, order, fields) = match (
+        get_sort_key(list_query, &columns),
+        sort_order(list_query.order.as_deref()),
+        get_fields(list_query, &columns),
+    ) {
+        (Ok(sort), Ok(order), Ok(fields)) => (sort, order, fields),
+        (Err(e), _, _) | (_, Err(e), _) | (_, _, Err(e)) => {
+            return (StatusCode::BAD_REQUEST, e).into_response();
+        }
+    };
+    let (page, limit) = list_query.page_and_limit();
+    match select_page_from_db_with_options(
+        pool,
+        request,
+        sort.map(|sort| (sort, order)),
+        limit as i64,
+        list_query.offset(),
+    )
+    .await
+    {
+        Ok((mut items, total)) => {
+            if let Some(fields) = &fields {
+                for item in items.iter_mut() {
+                    item.retain(|column, _| fields.contains(column));
+                }
+            }
+            (
+                StatusCode::OK,
+                Json(Page {
+                    total,
+                    page,
+                    limit,
+                    items,
+                }),
+            )
+                .into_response()
+        }
+        Err(e) => {
+            error!("Failed to select page from DB: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+        }
+    }
+}
+
 pub async fn get_db(
     State(state): State<Arc<Mutex<AppState>>>,
     axum::extract::Path((token, db_name)): axum::extract::Path<(String, String)>,
+    Query(list_query): Query<ListQuery>,
     axum::extract::Json(payload): axum::extract::Json<Value>,
 ) -> impl IntoResponse {
     let state = state.lock().await;
@@ -571,6 +698,10 @@ pub async fn get_db(
         Err(_) => return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to get DB").into_response(),
     };
 
+    if list_query.is_requested() {
+        return get_db_page(&pool, request, &list_query).await;
+    }
+
     let result = crate::repositories::database_repo::select_from_db_with_options(&pool, request)
         .await
         .unwrap_or_else(|_| {
@@ -0,0 +1,72 @@
+use std::sync::Arc;
+
+use axum::{extract::State, response::IntoResponse};
+use reqwest::StatusCode;
+use serde_json::json;
+use tokio::sync::Mutex;
+use tracing::error;
+
+use crate::{
+    routes_manager::AppState, services::library_service::run_library_scan,
+    services::profile_service::resolve_token,
+};
+
+pub async fn scan_library_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+) -> impl IntoResponse {
+    let state = state.lock().await;
+    let global = state.global_vars.lock().await;
+    let config = state.config.lock().await;
+    let base_path = &config.base_path;
+
+    let resolved_token = match resolve_token(&token, base_path) {
+        Some(t) => t,
+        None => return (StatusCode::UNAUTHORIZED, "Invalid token").into_response(),
+    };
+
+    let pool = match crate::repositories::database_repo::get_db(
+        &resolved_token,
+        base_path,
+        global.opened_db.clone(),
+    )
+    .await
+    {
+        Ok(pool) => pool,
+        Err(_) => {
+            error!("Error getting database pool");
+            return (StatusCode::INTERNAL_SERVER_ERROR, "Failed to get DB").into_response();
+        }
+    };
+
+    let progress_status = state.global_vars.clone();
+    let covers_dir = format!("{}/public/FirstImagesOfAll", base_path);
+    tokio::spawn(async move {
+        let _ = run_library_scan(pool, id, token, progress_status, covers_dir).await;
+    });
+
+    (StatusCode::ACCEPTED, "Scan started").into_response()
+}
+
+pub async fn get_library_schedule_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+) -> impl IntoResponse {
+    let state = state.lock().await;
+    let config = state.config.lock().await;
+    let base_path = &config.base_path;
+
+    let resolved_token = match resolve_token(&token, base_path) {
+        Some(t) => t,
+        None => return (StatusCode::UNAUTHORIZED, "Invalid token").into_response(),
+    };
+
+    let scheduler = state.scheduler.lock().await;
+    let upcoming = scheduler.get_upcoming_runs(&resolved_token).await;
+    let recent = scheduler.get_recent_runs(&resolved_token);
+    (
+        StatusCode::OK,
+        axum::Json(json!({ "upcoming": upcoming, "recent": recent })),
+    )
+        .into_response()
+}
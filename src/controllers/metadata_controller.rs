@@ -0,0 +1,395 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    response::{IntoResponse, Response},
+};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_json::{Value, json};
+use tokio::sync::Mutex;
+use tracing::{error, info};
+
+use crate::{
+    controllers::common_controller::get_profile_db,
+    routes_manager::AppState,
+    services::collection_service::ItemType,
+    services::credit_service::{CreditKind, get_credited_works},
+    services::custom_field_service::{
+        FieldType, create_custom_field, delete_custom_field, get_custom_field,
+        get_item_field_values, list_custom_fields, set_item_field_values, update_custom_field,
+        validate_field_definition,
+    },
+    services::tag_service::{
+        create_tag, delete_tag, get_item_tags, list_tags, rename_tag, set_item_tags, tag_items,
+        untag_items,
+    },
+};
+
+#[derive(Deserialize)]
+pub struct TagPayload {
+    name: String,
+}
+
+#[derive(Deserialize)]
+pub struct ItemTagsPayload {
+    tags: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct BulkTagsPayload {
+    tags: Vec<String>,
+    #[serde(default)]
+    books: Vec<String>,
+    #[serde(default)]
+    series: Vec<String>,
+}
+
+impl BulkTagsPayload {
+    fn items(&self) -> Vec<(ItemType, String)> {
+        self.books
+            .iter()
+            .map(|id| (ItemType::Book, id.clone()))
+            .chain(self.series.iter().map(|id| (ItemType::Series, id.clone())))
+            .collect()
+    }
+}
+
+#[derive(Deserialize)]
+pub struct CustomFieldPayload {
+    name: String,
+    #[serde(rename = "type")]
+    field_type: FieldType,
+    target: ItemType,
+    #[serde(default)]
+    options: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateCustomFieldPayload {
+    name: String,
+    #[serde(default)]
+    options: Vec<String>,
+}
+
+#[derive(Deserialize)]
+pub struct CustomFieldsQuery {
+    target: Option<ItemType>,
+}
+
+fn internal_error(action: &str, e: sqlx::Error) -> Response {
+    error!("Failed to {}: {}", action, e);
+    (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+}
+
+fn is_unique_violation(e: &sqlx::Error) -> bool {
+    matches!(e, sqlx::Error::Database(db_error) if db_error.is_unique_violation())
+}
+
+pub async fn list_tags_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match list_tags(&pool).await {
+        Ok(tags) => (StatusCode::OK, Json(tags)).into_response(),
+        Err(e) => internal_error("list tags", e),
+    }
+}
+
+pub async fn create_tag_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Json(payload): Json<TagPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    if payload.name.trim().is_empty() {
+        return (StatusCode::BAD_REQUEST, "Name cannot be empty").into_response();
+    }
+    match create_tag(&pool, payload.name.trim()).await {
+        Ok(id) => (StatusCode::CREATED, Json(json!({ "id": id }))).into_response(),
+        Err(e) => internal_error("create tag", e),
+    }
+}
+
+pub async fn rename_tag_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+    Json(payload): Json<TagPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    if payload.name.trim().is_empty() {
+        return (StatusCode::BAD_REQUEST, "Name cannot be empty").into_response();
+    }
+    match rename_tag(&pool, id, payload.name.trim()).await {
+        Ok(true) => (StatusCode::OK, "Update successful").into_response(),
+        Ok(false) => (StatusCode::NOT_FOUND, "Tag not found").into_response(),
+        Err(e) if is_unique_violation(&e) => {
+            (StatusCode::CONFLICT, "A tag with this name already exists").into_response()
+        }
+        Err(e) => internal_error("rename tag", e),
+    }
+}
+
+pub async fn delete_tag_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match delete_tag(&pool, id).await {
+        Ok(true) => (StatusCode::OK, "Delete successful").into_response(),
+        Ok(false) => (StatusCode::NOT_FOUND, "Tag not found").into_response(),
+        Err(e) => internal_error("delete tag", e),
+    }
+}
+
+pub async fn tag_items_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Json(payload): Json<BulkTagsPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match tag_items(&pool, &payload.tags, &payload.items()).await {
+        Ok(added) => (StatusCode::OK, Json(json!({ "added": added }))).into_response(),
+        Err(e) => internal_error("tag items", e),
+    }
+}
+
+pub async fn untag_items_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Json(payload): Json<BulkTagsPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match untag_items(&pool, &payload.tags, &payload.items()).await {
+        Ok(removed) => (StatusCode::OK, Json(json!({ "removed": removed }))).into_response(),
+        Err(e) => internal_error("untag items", e),
+    }
+}
+
+pub async fn get_item_tags_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, item_type, item_id)): axum::extract::Path<(
+        String,
+        ItemType,
+        String,
+    )>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match get_item_tags(&pool, item_type, &item_id).await {
+        Ok(tags) => (StatusCode::OK, Json(tags)).into_response(),
+        Err(e) => internal_error("get item tags", e),
+    }
+}
+
+pub async fn set_item_tags_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, item_type, item_id)): axum::extract::Path<(
+        String,
+        ItemType,
+        String,
+    )>,
+    Json(payload): Json<ItemTagsPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match set_item_tags(&pool, item_type, &item_id, &payload.tags).await {
+        Ok(()) => (StatusCode::OK, "Update successful").into_response(),
+        Err(e) => internal_error("set item tags", e),
+    }
+}
+
+pub async fn list_custom_fields_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Query(query): Query<CustomFieldsQuery>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match list_custom_fields(&pool, query.target).await {
+        Ok(fields) => (StatusCode::OK, Json(fields)).into_response(),
+        Err(e) => internal_error("list custom fields", e),
+    }
+}
+
+pub async fn create_custom_field_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Json(payload): Json<CustomFieldPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    if payload.name.trim().is_empty() {
+        return (StatusCode::BAD_REQUEST, "Name cannot be empty").into_response();
+    }
+    if let Err(e) = validate_field_definition(payload.field_type, &payload.options) {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+    match create_custom_field(
+        &pool,
+        payload.name.trim(),
+        payload.field_type,
+        payload.target,
+        &payload.options,
+    )
+    .await
+    {
+        Ok(id) => {
+            info!("Created custom field {}", id);
+            (StatusCode::CREATED, Json(json!({ "id": id }))).into_response()
+        }
+        Err(e) if is_unique_violation(&e) => (
+            StatusCode::CONFLICT,
+            "A field with this name already exists",
+        )
+            .into_response(),
+        Err(e) => internal_error("create custom field", e),
+    }
+}
+
+pub async fn update_custom_field_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+    Json(payload): Json<UpdateCustomFieldPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    if payload.name.trim().is_empty() {
+        return (StatusCode::BAD_REQUEST, "Name cannot be empty").into_response();
+    }
+    let field = match get_custom_field(&pool, id).await {
+        Ok(Some(field)) => field,
+        Ok(None) => return (StatusCode::NOT_FOUND, "Custom field not found").into_response(),
+        Err(e) => return internal_error("get custom field", e),
+    };
+    if let Err(e) = validate_field_definition(field.field_type, &payload.options) {
+        return (StatusCode::BAD_REQUEST, e).into_response();
+    }
+    match update_custom_field(&pool, &field, payload.name.trim(), &payload.options).await {
+        Ok(true) => (StatusCode::OK, "Update successful").into_response(),
+        Ok(false) => (StatusCode::NOT_FOUND, "Custom field not found").into_response(),
+        Err(e) if is_unique_violation(&e) => (
+            StatusCode::CONFLICT,
+            "A field with this name already exists",
+        )
+            .into_response(),
+        Err(e) => internal_error("update custom field", e),
+    }
+}
+
+pub async fn delete_custom_field_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match delete_custom_field(&pool, id).await {
+        Ok(true) => (StatusCode::OK, "Delete successful").into_response(),
+        Ok(false) => (StatusCode::NOT_FOUND, "Custom field not found").into_response(),
+        Err(e) => internal_error("delete custom field", e),
+    }
+}
+
+pub async fn get_item_fields_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, item_type, item_id)): axum::extract::Path<(
+        String,
+        ItemType,
+        String,
+    )>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match get_item_field_values(&pool, item_type, &item_id).await {
+        Ok(values) => (StatusCode::OK, Json(values)).into_response(),
+        Err(e) => internal_error("get custom field values", e),
+    }
+}
+
+pub async fn set_item_fields_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, item_type, item_id)): axum::extract::Path<(
+        String,
+        ItemType,
+        String,
+    )>,
+    Json(payload): Json<HashMap<String, Value>>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match set_item_field_values(&pool, item_type, &item_id, &payload).await {
+        Ok(()) => (StatusCode::OK, "Update successful").into_response(),
+        Err(e) if e.downcast_ref::<sqlx::Error>().is_some() => {
+            error!("Failed to set custom field values: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+async fn credited_works(
+    state: Arc<Mutex<AppState>>,
+    token: &str,
+    kind: CreditKind,
+    id: &str,
+) -> Response {
+    let pool = match get_profile_db(&*state.lock().await, token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match get_credited_works(&pool, kind, id).await {
+        Ok(Some(works)) => (StatusCode::OK, Json(works)).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "Item not found").into_response(),
+        Err(e) => internal_error("list credited works", e),
+    }
+}
+
+pub async fn get_creator_works_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, String)>,
+) -> impl IntoResponse {
+    credited_works(state, &token, CreditKind::Creator, &id).await
+}
+
+pub async fn get_character_works_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, String)>,
+) -> impl IntoResponse {
+    credited_works(state, &token, CreditKind::Character, &id).await
+}
@@ -0,0 +1,66 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    response::IntoResponse,
+};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use tokio::sync::Mutex;
+use tracing::error;
+
+use crate::{
+    controllers::common_controller::get_profile_db,
+    routes_manager::AppState,
+    services::{
+        on_deck_service::{get_adjacent_books, get_on_deck},
+        pagination_service::MAX_PAGE_SIZE,
+    },
+};
+
+const DEFAULT_ON_DECK_LIMIT: u32 = 20;
+
+#[derive(Deserialize)]
+pub struct OnDeckQuery {
+    limit: Option<u32>,
+}
+
+fn on_deck_error(e: sqlx::Error) -> axum::response::Response {
+    error!("Failed to build reading queue: {}", e);
+    (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+}
+
+pub async fn get_on_deck_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Query(query): Query<OnDeckQuery>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_ON_DECK_LIMIT)
+        .clamp(1, MAX_PAGE_SIZE);
+    match get_on_deck(&pool, limit as usize).await {
+        Ok(on_deck) => (StatusCode::OK, Json(on_deck)).into_response(),
+        Err(e) => on_deck_error(e),
+    }
+}
+
+pub async fn get_adjacent_books_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, book_id)): axum::extract::Path<(String, String)>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match get_adjacent_books(&pool, &book_id).await {
+        Ok(Some(adjacent)) => (StatusCode::OK, Json(adjacent)).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "Book not found").into_response(),
+        Err(e) => on_deck_error(e),
+    }
+}
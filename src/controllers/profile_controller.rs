@@ -1,7 +1,11 @@
 use std::{fs, path::Path, sync::Arc};
 
 use axum::http::HeaderMap;
-use axum::{Json, extract::State, response::IntoResponse};
+use axum::{
+    Json,
+    extract::{Query, State},
+    response::IntoResponse,
+};
 use reqwest::StatusCode;
 use serde_json::json;
 use tokio::sync::Mutex;
@@ -9,7 +13,10 @@ use tracing::{error, info};
 
 use crate::{
     routes_manager::AppState,
-    services::profile_service::{CreateUserPayload, create_user_service, resolve_token},
+    services::pagination_service::{ListQuery, paginate_items, to_items},
+    services::profile_service::{
+        CreateUserPayload, create_user_service, resolve_token, schema_version_service,
+    },
 };
 
 pub async fn create_user(
@@ -247,6 +254,7 @@ pub async fn login_check(
 pub async fn discover_profiles(
     header_map: HeaderMap,
     State(state): State<Arc<Mutex<AppState>>>,
+    Query(query): Query<ListQuery>,
 ) -> impl IntoResponse {
     let state = &state.lock().await;
     let config = state.config.lock().await;
@@ -266,6 +274,15 @@ pub async fn discover_profiles(
     match crate::services::profile_service::discover_profiles_service(&base_path, protocol, host)
         .await
     {
+        Ok(profiles) if query.is_requested() => match paginate_items(
+            to_items(&profiles),
+            &query,
+            &["name"],
+            &["name", "image", "passcode"],
+        ) {
+            Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+            Err(e) => (StatusCode::BAD_REQUEST, e).into_response(),
+        },
         Ok(profiles) => {
             info!("Profiles discovered successfully");
             (StatusCode::OK, Json(profiles)).into_response()
@@ -277,6 +294,31 @@ pub async fn discover_profiles(
     }
 }
 
+pub async fn get_schema_version(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+) -> impl IntoResponse {
+    let state = &state.lock().await;
+    let base_path = state.config.lock().await.base_path.clone();
+
+    let name = match resolve_token(&token, &base_path) {
+        Some(name) => name,
+        None => return (StatusCode::UNAUTHORIZED, "Invalid token").into_response(),
+    };
+
+    match schema_version_service(&name, &base_path).await {
+        Ok(profile) => (StatusCode::OK, Json(profile)).into_response(),
+        Err(e) => {
+            error!("Error reading schema version: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Failed to read schema version",
+            )
+                .into_response()
+        }
+    }
+}
+
 pub async fn download_database(
     State(state): State<Arc<Mutex<AppState>>>,
     axum::extract::Path(token): axum::extract::Path<String>,
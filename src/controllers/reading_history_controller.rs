@@ -0,0 +1,87 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    response::IntoResponse,
+};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use tokio::sync::Mutex;
+use tracing::error;
+
+use crate::{
+    controllers::common_controller::get_profile_db,
+    routes_manager::AppState,
+    services::{
+        pagination_service::MAX_PAGE_SIZE,
+        reading_history_service::{
+            get_book_reading_summary, get_recently_read, list_reading_history,
+        },
+    },
+};
+
+const DEFAULT_HISTORY_LIMIT: u32 = 20;
+
+#[derive(Deserialize)]
+pub struct HistoryQuery {
+    book: Option<String>,
+    limit: Option<u32>,
+}
+
+impl HistoryQuery {
+    fn limit(&self) -> i64 {
+        self.limit
+            .unwrap_or(DEFAULT_HISTORY_LIMIT)
+            .clamp(1, MAX_PAGE_SIZE) as i64
+    }
+}
+
+fn history_error(e: sqlx::Error) -> axum::response::Response {
+    error!("Failed to read reading history: {}", e);
+    (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+}
+
+pub async fn list_reading_history_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Query(query): Query<HistoryQuery>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match list_reading_history(&pool, query.book.as_deref(), query.limit()).await {
+        Ok(sessions) => (StatusCode::OK, Json(sessions)).into_response(),
+        Err(e) => history_error(e),
+    }
+}
+
+pub async fn get_recently_read_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Query(query): Query<HistoryQuery>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match get_recently_read(&pool, query.limit()).await {
+        Ok(books) => (StatusCode::OK, Json(books)).into_response(),
+        Err(e) => history_error(e),
+    }
+}
+
+pub async fn get_book_reading_summary_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, book_id)): axum::extract::Path<(String, String)>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match get_book_reading_summary(&pool, &book_id).await {
+        Ok(summary) => (StatusCode::OK, Json(summary)).into_response(),
+        Err(e) => history_error(e),
+    }
+}
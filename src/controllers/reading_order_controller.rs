@@ -0,0 +1,316 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Multipart, State},
+    response::IntoResponse,
+};
+use reqwest::StatusCode;
+use serde::Deserialize;
+use serde_json::json;
+use tokio::sync::Mutex;
+use tracing::{error, info};
+
+use crate::{
+    controllers::common_controller::get_profile_db,
+    routes_manager::AppState,
+    services::cbl_service::{
+        get_cbl_report, import_cbl, parse_cbl, rematch_reading_order, set_entry_book,
+        set_entry_reference,
+    },
+    services::reading_order_service::{
+        CblReference, add_books_to_reading_order, create_reading_order, delete_reading_order,
+        get_next_in_reading_orders, get_reading_order, get_unknown_books, list_reading_orders,
+        reading_order_exists, remove_entry_from_reading_order, reorder_reading_order,
+        update_reading_order,
+    },
+};
+
+#[derive(Deserialize)]
+pub struct ReadingOrderPayload {
+    name: String,
+    description: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct AddBooksPayload {
+    books: Vec<String>,
+    position: Option<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct ReorderPayload {
+    entries: Vec<i64>,
+}
+
+#[derive(Deserialize)]
+pub struct FixEntryPayload {
+    book: Option<String>,
+    reference: Option<CblReference>,
+}
+
+fn internal_error(action: &str, e: sqlx::Error) -> axum::response::Response {
+    error!("Failed to {}: {}", action, e);
+    (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+}
+
+pub async fn list_reading_orders_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match list_reading_orders(&pool).await {
+        Ok(orders) => (StatusCode::OK, Json(orders)).into_response(),
+        Err(e) => internal_error("list reading orders", e),
+    }
+}
+
+pub async fn create_reading_order_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Json(payload): Json<ReadingOrderPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    if payload.name.trim().is_empty() {
+        return (StatusCode::BAD_REQUEST, "Name cannot be empty").into_response();
+    }
+    match create_reading_order(&pool, payload.name.trim(), payload.description.as_deref()).await {
+        Ok(id) => {
+            info!("Created reading order {}", id);
+            (StatusCode::CREATED, Json(json!({ "id": id }))).into_response()
+        }
+        Err(e) => internal_error("create reading order", e),
+    }
+}
+
+pub async fn get_reading_order_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match get_reading_order(&pool, id).await {
+        Ok(Some(order)) => (StatusCode::OK, Json(order)).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "Reading order not found").into_response(),
+        Err(e) => internal_error("get reading order", e),
+    }
+}
+
+pub async fn update_reading_order_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+    Json(payload): Json<ReadingOrderPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    if payload.name.trim().is_empty() {
+        return (StatusCode::BAD_REQUEST, "Name cannot be empty").into_response();
+    }
+    match update_reading_order(
+        &pool,
+        id,
+        payload.name.trim(),
+        payload.description.as_deref(),
+    )
+    .await
+    {
+        Ok(true) => (StatusCode::OK, "Update successful").into_response(),
+        Ok(false) => (StatusCode::NOT_FOUND, "Reading order not found").into_response(),
+        Err(e) => internal_error("update reading order", e),
+    }
+}
+
+pub async fn delete_reading_order_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match delete_reading_order(&pool, id).await {
+        Ok(true) => (StatusCode::OK, "Delete successful").into_response(),
+        Ok(false) => (StatusCode::NOT_FOUND, "Reading order not found").into_response(),
+        Err(e) => internal_error("delete reading order", e),
+    }
+}
+
+pub async fn add_reading_order_entries_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+    Json(payload): Json<AddBooksPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match reading_order_exists(&pool, id).await {
+        Ok(true) => {}
+        Ok(false) => return (StatusCode::NOT_FOUND, "Reading order not found").into_response(),
+        Err(e) => return internal_error("get reading order", e),
+    }
+    match get_unknown_books(&pool, &payload.books).await {
+        Ok(unknown) if unknown.is_empty() => {}
+        Ok(unknown) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                format!("Unknown books: {}", unknown.join(", ")),
+            )
+                .into_response();
+        }
+        Err(e) => return internal_error("get books", e),
+    }
+    match add_books_to_reading_order(&pool, id, &payload.books, payload.position).await {
+        Ok(_) => (StatusCode::OK, "Books added").into_response(),
+        Err(e) => internal_error("add books to reading order", e),
+    }
+}
+
+pub async fn reorder_reading_order_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+    Json(payload): Json<ReorderPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match reorder_reading_order(&pool, id, &payload.entries).await {
+        Ok(true) => (StatusCode::OK, "Reorder successful").into_response(),
+        Ok(false) => (
+            StatusCode::BAD_REQUEST,
+            "Entries must list every entry of the reading order exactly once",
+        )
+            .into_response(),
+        Err(e) => internal_error("reorder reading order", e),
+    }
+}
+
+pub async fn remove_reading_order_entry_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id, entry_id)): axum::extract::Path<(String, i64, i64)>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match remove_entry_from_reading_order(&pool, id, entry_id).await {
+        Ok(true) => (StatusCode::OK, "Delete successful").into_response(),
+        Ok(false) => (StatusCode::NOT_FOUND, "Entry not found").into_response(),
+        Err(e) => internal_error("remove reading order entry", e),
+    }
+}
+
+pub async fn next_in_reading_orders_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, book_id)): axum::extract::Path<(String, String)>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match get_next_in_reading_orders(&pool, &book_id).await {
+        Ok(next) => (StatusCode::OK, Json(next)).into_response(),
+        Err(e) => internal_error("get next book in reading orders", e),
+    }
+}
+
+pub async fn import_cbl_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    mut multipart: Multipart,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    let content = match multipart.next_field().await {
+        Ok(Some(field)) => match field.text().await {
+            Ok(content) => content,
+            Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        },
+        Ok(None) => return (StatusCode::BAD_REQUEST, "No file provided").into_response(),
+        Err(e) => return (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    };
+    let list = match parse_cbl(&content) {
+        Ok(list) => list,
+        Err(e) => return (StatusCode::BAD_REQUEST, e).into_response(),
+    };
+    match import_cbl(&pool, &list).await {
+        Ok(report) => {
+            info!(
+                "Imported reading list {}: {}/{} entries matched",
+                list.name, report.matched, report.total
+            );
+            (StatusCode::CREATED, Json(report)).into_response()
+        }
+        Err(e) => internal_error("import reading list", e),
+    }
+}
+
+pub async fn reading_order_report_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match get_cbl_report(&pool, id).await {
+        Ok(report) => (StatusCode::OK, Json(report)).into_response(),
+        Err(e) => internal_error("get reading list report", e),
+    }
+}
+
+pub async fn rematch_reading_order_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, i64)>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match rematch_reading_order(&pool, id).await {
+        Ok(report) => (StatusCode::OK, Json(report)).into_response(),
+        Err(e) => internal_error("rematch reading list", e),
+    }
+}
+
+pub async fn fix_reading_order_entry_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path((token, id, entry_id)): axum::extract::Path<(String, i64, i64)>,
+    Json(payload): Json<FixEntryPayload>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    let result = match (payload.book, payload.reference) {
+        (Some(book_id), _) => set_entry_book(&pool, id, entry_id, &book_id).await,
+        (None, Some(reference)) if !reference.series.trim().is_empty() => {
+            set_entry_reference(&pool, id, entry_id, &reference).await
+        }
+        _ => {
+            return (
+                StatusCode::BAD_REQUEST,
+                "Either a book or a reference is required",
+            )
+                .into_response();
+        }
+    };
+    match result {
+        Ok(true) => (StatusCode::OK, "Update successful").into_response(),
+        Ok(false) => (StatusCode::NOT_FOUND, "Entry not found").into_response(),
+        Err(e) => internal_error("fix reading order entry", e),
+    }
+}
@@ -0,0 +1,175 @@
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    Extension, Json,
+    extract::{Query, State},
+    response::{IntoResponse, Response},
+};
+use reqwest::StatusCode;
+use serde_json::Value;
+use tokio::sync::Mutex;
+use tracing::{error, info};
+
+use crate::{
+    controllers::common_controller::get_profile_db,
+    routes_manager::AppState,
+    services::pagination_service::ListQuery,
+    services::profile_service::resolve_token,
+    services::resource_service::{
+        Resource, create_resource, delete_resource, get_resource, list_resources, update_resource,
+    },
+    services::scheduler_service::get_library_schedules,
+};
+
+fn resource_error(resource: Resource, e: Box<dyn std::error::Error + Send + Sync>) -> Response {
+    match e.downcast_ref::<sqlx::Error>() {
+        Some(sqlx::Error::Database(db_error)) if db_error.is_unique_violation() => {
+            (StatusCode::CONFLICT, "Item already exists").into_response()
+        }
+        Some(e) => {
+            error!("Failed to write {}: {}", resource.as_str(), e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+        }
+        None => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+async fn sync_library_schedule(
+    state: &Arc<Mutex<AppState>>,
+    pool: &sqlx::SqlitePool,
+    token: &str,
+    library_id: &str,
+) {
+    let state = state.lock().await;
+    let base_path = state.config.lock().await.base_path.clone();
+    let (Some(resolved_token), Ok(library_id)) =
+        (resolve_token(token, &base_path), library_id.parse::<i64>())
+    else {
+        return;
+    };
+    let schedules = match get_library_schedules(pool).await {
+        Ok(schedules) => schedules,
+        Err(e) => {
+            error!("Failed to read library schedules: {}", e);
+            return;
+        }
+    };
+    let mut scheduler = state.scheduler.lock().await;
+    let result = match schedules.iter().find(|s| s.library_id == library_id) {
+        Some(schedule) => scheduler.schedule_library(&resolved_token, schedule).await,
+        None => {
+            scheduler
+                .unschedule_library(&resolved_token, library_id)
+                .await
+        }
+    };
+    if let Err(e) = result {
+        error!("Failed to reschedule library {}: {}", library_id, e);
+    }
+}
+
+pub async fn list_resources_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    Extension(resource): Extension<Resource>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Query(filters): Query<HashMap<String, String>>,
+    Query(query): Query<ListQuery>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match list_resources(&pool, resource, &filters, &query).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) => resource_error(resource, e),
+    }
+}
+
+pub async fn get_resource_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    Extension(resource): Extension<Resource>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, String)>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match get_resource(&pool, resource, &id).await {
+        Ok(Some(item)) => (StatusCode::OK, Json(item)).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "Item not found").into_response(),
+        Err(e) => resource_error(resource, e.into()),
+    }
+}
+
+pub async fn create_resource_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    Extension(resource): Extension<Resource>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Json(payload): Json<Value>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    let id = match create_resource(&pool, resource, &payload).await {
+        Ok(id) => id,
+        Err(e) => return resource_error(resource, e),
+    };
+    if resource == Resource::Libraries {
+        sync_library_schedule(&state, &pool, &token, &id).await;
+    }
+    info!("Created {} item: {}", resource.as_str(), id);
+    match get_resource(&pool, resource, &id).await {
+        Ok(Some(item)) => (StatusCode::CREATED, Json(item)).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "Item not found").into_response(),
+        Err(e) => resource_error(resource, e.into()),
+    }
+}
+
+pub async fn update_resource_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    Extension(resource): Extension<Resource>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, String)>,
+    Json(payload): Json<Value>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match update_resource(&pool, resource, &id, &payload).await {
+        Ok(true) => {}
+        Ok(false) => return (StatusCode::NOT_FOUND, "Item not found").into_response(),
+        Err(e) => return resource_error(resource, e),
+    }
+    if resource == Resource::Libraries {
+        sync_library_schedule(&state, &pool, &token, &id).await;
+    }
+    info!("Updated {} item: {}", resource.as_str(), id);
+    match get_resource(&pool, resource, &id).await {
+        Ok(Some(item)) => (StatusCode::OK, Json(item)).into_response(),
+        Ok(None) => (StatusCode::NOT_FOUND, "Item not found").into_response(),
+        Err(e) => resource_error(resource, e.into()),
+    }
+}
+
+pub async fn delete_resource_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    Extension(resource): Extension<Resource>,
+    axum::extract::Path((token, id)): axum::extract::Path<(String, String)>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match delete_resource(&pool, resource, &id).await {
+        Ok(true) => {
+            if resource == Resource::Libraries {
+                sync_library_schedule(&state, &pool, &token, &id).await;
+            }
+            info!("Deleted {} item: {}", resource.as_str(), id);
+            (StatusCode::OK, "Delete successful").into_response()
+        }
+        Ok(false) => (StatusCode::NOT_FOUND, "Item not found").into_response(),
+        Err(e) => resource_error(resource, e.into()),
+    }
+}
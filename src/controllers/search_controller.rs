@@ -0,0 +1,78 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    response::IntoResponse,
+};
+use reqwest::StatusCode;
+use tokio::sync::Mutex;
+use tracing::{error, info};
+
+use crate::{
+    controllers::common_controller::get_profile_db,
+    repositories::database_repo::rebuild_search_index,
+    routes_manager::AppState,
+    services::facet_service::{BrowseRequest, browse},
+    services::search_service::{SearchQuery, search},
+    services::smart_collection_service::SmartQuery,
+};
+
+pub async fn search_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Query(query): Query<SearchQuery>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match search(&pool, &query).await {
+        Ok(results) => (StatusCode::OK, Json(results)).into_response(),
+        Err(e) if e.downcast_ref::<sqlx::Error>().is_some() => {
+            error!("Failed to search: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+pub async fn rebuild_search_index_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match rebuild_search_index(&pool).await {
+        Ok(()) => {
+            info!("Rebuilt search index");
+            (StatusCode::OK, "Rebuild successful").into_response()
+        }
+        Err(e) => {
+            error!("Failed to rebuild search index: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+        }
+    }
+}
+
+pub async fn browse_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Query(query): Query<SmartQuery>,
+    Json(request): Json<BrowseRequest>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match browse(&pool, &request, &query).await {
+        Ok(page) => (StatusCode::OK, Json(page)).into_response(),
+        Err(e) if e.downcast_ref::<sqlx::Error>().is_some() => {
+            error!("Failed to browse library: {}", e);
+            (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
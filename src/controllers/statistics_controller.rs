@@ -0,0 +1,38 @@
+use std::sync::Arc;
+
+use axum::{
+    Json,
+    extract::{Query, State},
+    response::IntoResponse,
+};
+use chrono::Utc;
+use reqwest::StatusCode;
+use tokio::sync::Mutex;
+use tracing::error;
+
+use crate::{
+    controllers::common_controller::get_profile_db,
+    routes_manager::AppState,
+    services::statistics_service::{StatsQuery, get_reading_stats},
+};
+
+pub async fn get_reading_stats_controller(
+    State(state): State<Arc<Mutex<AppState>>>,
+    axum::extract::Path(token): axum::extract::Path<String>,
+    Query(query): Query<StatsQuery>,
+) -> impl IntoResponse {
+    let pool = match get_profile_db(&*state.lock().await, &token).await {
+        Ok(pool) => pool,
+        Err(response) => return response,
+    };
+    match get_reading_stats(&pool, &query, Utc::now().timestamp()).await {
+        Ok(stats) => (StatusCode::OK, Json(stats)).into_response(),
+        Err(e) => match e.downcast_ref::<sqlx::Error>() {
+            Some(e) => {
+                error!("Failed to compute reading statistics: {}", e);
+                (StatusCode::INTERNAL_SERVER_ERROR, "Database error").into_response()
+            }
+            None => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+        },
+    }
+}
@@ -8,14 +8,17 @@ use tokio::{fs::File, io::AsyncWriteExt};
 use tokio_util::io::ReaderStream;
 use tracing::{error, info};
 
+use crate::controllers::common_controller::get_profile_db;
 use crate::services::archive_service::unzip_and_process;
 use crate::services::profile_service::resolve_token;
+use crate::services::reading_history_service::record_current_page;
 use crate::utils::{
     VALID_BOOK_EXTENSION, VALID_IMAGE_EXTENSION, get_list_of_images, replace_html_address_path,
 };
 use axum::extract::{Multipart as AxumMultipart, Path};
 use axum::http::{HeaderMap, Response};
 use axum_macros::debug_handler;
+use sqlx::SqlitePool;
 
 pub async fn upload_comic_controller(
     State(state): State<Arc<tokio::sync::Mutex<AppState>>>,
@@ -213,6 +216,7 @@ pub async fn read_image(
 
     let met = headers.get("met").and_then(|v| v.to_str().ok());
     let page = headers.get("page").and_then(|v| v.to_str().ok());
+    let mut viewed_page = None;
 
     let file_path = match met {
         Some("DL") => {
@@ -231,20 +235,28 @@ pub async fn read_image(
                         Some(t) => t,
                         None => return (StatusCode::UNAUTHORIZED, "Invalid token").into_response(),
                     };
-                    Some(PathBuf::from(format!(
-                        "{}/profiles/{}/current_book/{}",
-                        base_path, resolved_token, pg
-                    )))
+                    let current_book_path =
+                        format!("{}/profiles/{}/current_book", base_path, resolved_token);
+                    viewed_page =
+                        Some((current_book_path.clone(), ViewedPage::Image(pg.to_string())));
+                    Some(PathBuf::from(format!("{}/{}", current_book_path, pg)))
                 }
                 _ => None,
             }
         }
         _ => None,
     };
+    drop(config);
 
     if let Some(path) = file_path {
         match File::open(&path).await {
             Ok(file) => {
+                if let Some((current_book_path, viewed_page)) = viewed_page
+                    && let Some(token) = headers.get("token").and_then(|v| v.to_str().ok())
+                    && let Ok(pool) = get_profile_db(&state, token).await
+                {
+                    spawn_page_record(pool, current_book_path, viewed_page, &headers);
+                }
                 let stream = ReaderStream::new(file);
                 Response::builder()
                     .status(StatusCode::OK)
@@ -273,8 +285,7 @@ pub async fn viewer_view_controller(
     if let Some(path) = path {
         let param = replace_html_address_path(path);
         info!("Received path: {}", param);
-        let mut tosend = get_list_of_images((&param).as_ref(), crate::utils::VALID_IMAGE_EXTENSION);
-        tosend.sort();
+        let tosend = get_viewer_images((&param).as_ref());
         info!("Sending list of images: {:?}", tosend);
         return match serde_json::to_string(&tosend) {
             Ok(json) => (StatusCode::OK, json).into_response(),
@@ -291,15 +302,61 @@ pub async fn viewer_view_controller(
     (StatusCode::BAD_REQUEST, "Missing or invalid 'path' header").into_response()
 }
 
+fn get_viewer_images(dir: &std::path::Path) -> Vec<String> {
+    let mut images = get_list_of_images(dir, VALID_IMAGE_EXTENSION);
+    images.sort();
+    images
+}
+
+enum ViewedPage {
+    Index { page: usize, page_count: usize },
+    Image(String),
+}
+
+fn spawn_page_record(
+    pool: SqlitePool,
+    current_book_path: String,
+    viewed_page: ViewedPage,
+    headers: &HeaderMap,
+) {
+    let user_agent = headers
+        .get("User-Agent")
+        .and_then(|v| v.to_str().ok())
+        .map(str::to_string);
+    tokio::spawn(async move {
+        let (page, page_count) = match viewed_page {
+            ViewedPage::Index { page, page_count } => (page, page_count),
+            ViewedPage::Image(image) => {
+                let images = get_viewer_images(current_book_path.as_ref());
+                match images.iter().position(|name| *name == image) {
+                    Some(page) => (page, images.len()),
+                    None => return,
+                }
+            }
+        };
+        if let Err(e) = record_current_page(
+            &pool,
+            current_book_path.as_ref(),
+            page,
+            page_count,
+            user_agent,
+        )
+        .await
+        {
+            error!("Failed to record reading session: {}", e);
+        }
+    });
+}
+
 pub async fn view_current_page_controller(
     Path((page, token)): Path<(usize, String)>,
+    headers: HeaderMap,
     State(state): State<Arc<tokio::sync::Mutex<AppState>>>,
 ) -> impl IntoResponse {
     let state = state.lock().await;
-    let config = state.config.lock().await;
-    let base_path = &config.base_path;
+    let base_path = state.config.lock().await.base_path.clone();
 
-    let resolved_token = match resolve_token(&token, base_path) {
+    let resolved_token = match resolve_token(&token, &base_path) {
         Some(t) => t,
         None => return (StatusCode::UNAUTHORIZED, "Invalid token").into_response(),
     };
@@ -312,6 +369,13 @@ pub async fn view_current_page_controller(
 
     if let Some(image) = list_of_images.get(page) {
         let image_path = format!("{}/{}", current_book_path, image);
+        if let Ok(pool) = get_profile_db(&state, &token).await {
+            let viewed_page = ViewedPage::Index {
+                page,
+                page_count: list_of_images.len(),
+            };
+            spawn_page_record(pool, current_book_path, viewed_page, &headers);
+        }
         return (StatusCode::OK, image_path).into_response();
     }
 
@@ -5,3 +5,13 @@ pub(crate) mod database_endpoints;
 pub(crate) mod settings_endpoints;
 pub(crate) mod viewer_endpoints;
 pub(crate) mod api_endpoints;
+pub(crate) mod library_endpoints;
+pub(crate) mod reading_order_endpoints;
+pub(crate) mod collection_endpoints;
+pub(crate) mod metadata_endpoints;
+pub(crate) mod search_endpoints;
+pub(crate) mod resource_endpoints;
+pub(crate) mod bookmark_endpoints;
+pub(crate) mod annotation_endpoints;
+pub(crate) mod reading_history_endpoints;
+pub(crate) mod on_deck_endpoints;
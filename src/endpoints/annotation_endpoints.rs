@@ -0,0 +1,26 @@
+use crate::controllers::annotation_controller::*;
+use crate::routes_manager::AppState;
+use axum::Router;
+use axum::routing::{get, patch};
+use std::sync::Arc;
+
+pub fn annotation_routes(state: Arc<tokio::sync::Mutex<AppState>>) -> Router {
+    Router::new()
+        .route(
+            "/annotations/{token}/{book_id}",
+            get(list_annotations_controller).post(create_annotation_controller),
+        )
+        .route(
+            "/annotations/{token}/{book_id}/export",
+            get(export_annotations_controller),
+        )
+        .route(
+            "/annotations/{token}/{book_id}/{id}",
+            patch(update_annotation_controller).delete(delete_annotation_controller),
+        )
+        .route(
+            "/viewer/manifest/{token}",
+            get(get_page_manifest_controller),
+        )
+        .with_state(state)
+}
@@ -0,0 +1,35 @@
+use crate::controllers::bookmark_controller::*;
+use crate::controllers::resource_controller::{get_resource_controller, list_resources_controller};
+use crate::routes_manager::AppState;
+use crate::services::resource_service::Resource;
+use axum::routing::{get, post};
+use axum::{Extension, Router};
+use std::sync::Arc;
+
+pub fn bookmark_routes(state: Arc<tokio::sync::Mutex<AppState>>) -> Router {
+    Router::new()
+        .route(
+            "/bookmarks/{token}",
+            get(list_resources_controller).post(create_bookmark_controller),
+        )
+        .route(
+            "/bookmarks/{token}/{id}",
+            get(get_resource_controller)
+                .patch(update_bookmark_controller)
+                .delete(delete_bookmark_controller),
+        )
+        .route(
+            "/bookmarks/{token}/{id}/thumbnail",
+            get(get_bookmark_thumbnail_controller),
+        )
+        .route(
+            "/bookmarks/{token}/{id}/jump",
+            post(jump_to_bookmark_controller),
+        )
+        .route(
+            "/books/{token}/{id}/bookmarks",
+            get(list_book_bookmarks_controller),
+        )
+        .layer(Extension(Resource::Bookmarks))
+        .with_state(state)
+}
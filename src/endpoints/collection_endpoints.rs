@@ -0,0 +1,48 @@
+use crate::controllers::collection_controller::*;
+use crate::routes_manager::AppState;
+use axum::Router;
+use axum::routing::{get, post, put};
+use std::sync::Arc;
+
+pub fn collection_routes(state: Arc<tokio::sync::Mutex<AppState>>) -> Router {
+    Router::new()
+        .route(
+            "/collections/{token}",
+            get(list_collections_controller)
+                .post(create_collection_controller)
+                .put(reorder_collections_controller),
+        )
+        .route(
+            "/collections/{token}/{id}",
+            get(get_collection_controller)
+                .put(update_collection_controller)
+                .delete(delete_collection_controller),
+        )
+        .route(
+            "/collections/{token}/{id}/items",
+            post(add_collection_items_controller)
+                .put(reorder_collection_items_controller)
+                .delete(remove_collection_items_controller),
+        )
+        .route(
+            "/collections/{token}/smart",
+            post(create_smart_collection_controller),
+        )
+        .route(
+            "/collections/{token}/smart/preview",
+            post(preview_smart_collection_controller),
+        )
+        .route(
+            "/collections/{token}/{id}/rules",
+            put(update_smart_rules_controller),
+        )
+        .route(
+            "/collections/{token}/{id}/members",
+            get(get_collection_members_controller),
+        )
+        .route(
+            "/collections/{token}/{id}/cover",
+            get(get_collection_cover_controller).post(refresh_collection_cover_controller),
+        )
+        .with_state(state)
+}
@@ -1,7 +1,7 @@
 use crate::controllers::collectionner_controller::{
-    fill_blank_images_controller, first_images_of_all_image_getter, insert_anilist_book,
-    insert_googlebooks_book, insert_marvel_book, insert_olib_book, refresh_meta_controller,
-    scrape_images_from_webpage_controller,
+    fill_blank_images_controller, first_images_of_all_image_getter, get_duplicates_controller,
+    insert_anilist_book, insert_googlebooks_book, insert_marvel_book, insert_olib_book,
+    refresh_meta_controller, scan_duplicates_controller, scrape_images_from_webpage_controller,
 };
 use crate::routes_manager::AppState;
 use axum::Router;
@@ -17,6 +17,8 @@ pub fn collectionner_routes(state: Arc<tokio::sync::Mutex<AppState>>) -> Router
         .route("/insert/ol/book", get(insert_olib_book))
         .route("/refreshMeta", post(refresh_meta_controller))
         .route("/downloadBook", post(scrape_images_from_webpage_controller))
+        .route("/duplicates/scan", post(scan_duplicates_controller))
+        .route("/duplicates/{token}", get(get_duplicates_controller))
         .route(
             "/FirstImagesOfAll/{image_name}",
             get(first_images_of_all_image_getter),
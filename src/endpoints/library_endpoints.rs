@@ -0,0 +1,17 @@
+use crate::controllers::library_controller::{
+    get_library_schedule_controller, scan_library_controller,
+};
+use crate::routes_manager::AppState;
+use axum::Router;
+use axum::routing::{get, post};
+use std::sync::Arc;
+
+pub fn library_routes(state: Arc<tokio::sync::Mutex<AppState>>) -> Router {
+    Router::new()
+        .route("/library/scan/{token}/{id}", post(scan_library_controller))
+        .route(
+            "/library/schedule/{token}",
+            get(get_library_schedule_controller),
+        )
+        .with_state(state)
+}
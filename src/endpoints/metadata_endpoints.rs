@@ -0,0 +1,46 @@
+use crate::controllers::metadata_controller::*;
+use crate::routes_manager::AppState;
+use axum::Router;
+use axum::routing::{get, post, put};
+use std::sync::Arc;
+
+pub fn metadata_routes(state: Arc<tokio::sync::Mutex<AppState>>) -> Router {
+    Router::new()
+        .route(
+            "/tags/{token}",
+            get(list_tags_controller).post(create_tag_controller),
+        )
+        .route(
+            "/tags/{token}/{id}",
+            put(rename_tag_controller).delete(delete_tag_controller),
+        )
+        .route(
+            "/tags/{token}/items",
+            post(tag_items_controller).delete(untag_items_controller),
+        )
+        .route(
+            "/tags/{token}/{item_type}/{item_id}",
+            get(get_item_tags_controller).put(set_item_tags_controller),
+        )
+        .route(
+            "/customFields/{token}",
+            get(list_custom_fields_controller).post(create_custom_field_controller),
+        )
+        .route(
+            "/customFields/{token}/{id}",
+            put(update_custom_field_controller).delete(delete_custom_field_controller),
+        )
+        .route(
+            "/customFields/{token}/{item_type}/{item_id}",
+            get(get_item_fields_controller).put(set_item_fields_controller),
+        )
+        .route(
+            "/creators/{token}/{id}/works",
+            get(get_creator_works_controller),
+        )
+        .route(
+            "/characters/{token}/{id}/works",
+            get(get_character_works_controller),
+        )
+        .with_state(state)
+}
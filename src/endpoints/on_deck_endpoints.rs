@@ -0,0 +1,15 @@
+use crate::controllers::on_deck_controller::*;
+use crate::routes_manager::AppState;
+use axum::Router;
+use axum::routing::get;
+use std::sync::Arc;
+
+pub fn on_deck_routes(state: Arc<tokio::sync::Mutex<AppState>>) -> Router {
+    Router::new()
+        .route("/on_deck/{token}", get(get_on_deck_controller))
+        .route(
+            "/books/{token}/{id}/adjacent",
+            get(get_adjacent_books_controller),
+        )
+        .with_state(state)
+}
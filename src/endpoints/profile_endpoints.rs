@@ -15,6 +15,7 @@ pub fn authentication_routes(state: Arc<tokio::sync::Mutex<AppState>>) -> Router
         .route("/profile/login/{name}/{passcode}", get(login))
         .route("/profile/logcheck/{token}", get(login_check))
         .route("/profile/discover", get(discover_profiles))
+        .route("/profile/schema/{token}", get(get_schema_version))
         .route("/profile/DLBDD/{token}", get(download_database))
         .route("/profile/logout/{token}", post(logout))
         .with_state(state)
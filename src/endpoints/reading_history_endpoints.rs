@@ -0,0 +1,18 @@
+use crate::controllers::reading_history_controller::*;
+use crate::controllers::statistics_controller::get_reading_stats_controller;
+use crate::routes_manager::AppState;
+use axum::Router;
+use axum::routing::get;
+use std::sync::Arc;
+
+pub fn reading_history_routes(state: Arc<tokio::sync::Mutex<AppState>>) -> Router {
+    Router::new()
+        .route("/history/{token}", get(list_reading_history_controller))
+        .route("/history/{token}/recent", get(get_recently_read_controller))
+        .route("/stats/{token}", get(get_reading_stats_controller))
+        .route(
+            "/books/{token}/{id}/history",
+            get(get_book_reading_summary_controller),
+        )
+        .with_state(state)
+}
@@ -0,0 +1,41 @@
+use crate::controllers::reading_order_controller::*;
+use crate::routes_manager::AppState;
+use axum::Router;
+use axum::routing::{get, post, put};
+use std::sync::Arc;
+
+pub fn reading_order_routes(state: Arc<tokio::sync::Mutex<AppState>>) -> Router {
+    Router::new()
+        .route(
+            "/readingOrders/{token}",
+            get(list_reading_orders_controller).post(create_reading_order_controller),
+        )
+        .route(
+            "/readingOrders/{token}/{id}",
+            get(get_reading_order_controller)
+                .put(update_reading_order_controller)
+                .delete(delete_reading_order_controller),
+        )
+        .route(
+            "/readingOrders/{token}/{id}/entries",
+            post(add_reading_order_entries_controller).put(reorder_reading_order_controller),
+        )
+        .route(
+            "/readingOrders/{token}/{id}/entries/{entry_id}",
+            put(fix_reading_order_entry_controller).delete(remove_reading_order_entry_controller),
+        )
+        .route(
+            "/readingOrders/{token}/{id}/report",
+            get(reading_order_report_controller),
+        )
+        .route(
+            "/readingOrders/{token}/{id}/rematch",
+            post(rematch_reading_order_controller),
+        )
+        .route("/readingOrders/import/{token}", post(import_cbl_controller))
+        .route(
+            "/readingOrders/next/{token}/{book_id}",
+            get(next_in_reading_orders_controller),
+        )
+        .with_state(state)
+}
@@ -0,0 +1,31 @@
+use crate::controllers::resource_controller::*;
+use crate::routes_manager::AppState;
+use crate::services::resource_service::Resource;
+use axum::routing::get;
+use axum::{Extension, Router};
+use std::sync::Arc;
+
+fn resource_router(resource: Resource) -> Router<Arc<tokio::sync::Mutex<AppState>>> {
+    Router::new()
+        .route(
+            &format!("/{}/{{token}}", resource.as_str()),
+            get(list_resources_controller).post(create_resource_controller),
+        )
+        .route(
+            &format!("/{}/{{token}}/{{id}}", resource.as_str()),
+            get(get_resource_controller)
+                .patch(update_resource_controller)
+                .delete(delete_resource_controller),
+        )
+        .layer(Extension(resource))
+}
+
+pub fn resource_routes(state: Arc<tokio::sync::Mutex<AppState>>) -> Router {
+    Resource::ALL
+        .into_iter()
+        .filter(|resource| *resource != Resource::Bookmarks)
+        .fold(Router::new(), |router, resource| {
+            router.merge(resource_router(resource))
+        })
+        .with_state(state)
+}
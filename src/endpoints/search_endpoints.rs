@@ -0,0 +1,16 @@
+use crate::controllers::search_controller::*;
+use crate::routes_manager::AppState;
+use axum::Router;
+use axum::routing::{get, post};
+use std::sync::Arc;
+
+pub fn search_routes(state: Arc<tokio::sync::Mutex<AppState>>) -> Router {
+    Router::new()
+        .route("/search/{token}", get(search_controller))
+        .route(
+            "/search/{token}/rebuild",
+            post(rebuild_search_index_controller),
+        )
+        .route("/browse/{token}", post(browse_controller))
+        .with_state(state)
+}
@@ -1,4 +1,5 @@
 use crate::routes_manager::create_router;
+use crate::services::scheduler_service::LibraryScheduler;
 use rust_embed::RustEmbed;
 use serde_json::{Value, json};
 use sqlx::sqlite::SqlitePool;
@@ -17,6 +18,7 @@ use tracing_subscriber::{self};
 
 mod controllers;
 mod endpoints;
+mod models;
 mod repositories;
 mod routes_manager;
 mod services;
@@ -29,6 +31,7 @@ struct Asset;
 pub struct AppConfig {
     pub base_path: String,
     pub version: String,
+    pub legacy_db_routes: bool,
 }
 
 pub struct ApiTokens {
@@ -369,10 +372,12 @@ async fn main() {
         std::env::var("GOOGLE_BOOKS_API_KEY").unwrap_or_else(|_| "".to_string());
     let open_library_api_key =
         std::env::var("OPEN_LIBRARY_API_KEY").unwrap_or_else(|_| "".to_string());
+    let legacy_db_routes = std::env::var("LEGACY_DB_ROUTES").map_or(true, |value| value != "false");
     let version = env!("CARGO_PKG_VERSION").to_string();
     let app_state = Arc::new(tokio::sync::Mutex::new(AppConfig {
         base_path: base_path.clone(),
         version: version.clone(),
+        legacy_db_routes,
     }));
 
     let api_tokens = Arc::new(tokio::sync::Mutex::new(ApiTokens {
@@ -384,7 +389,22 @@ async fn main() {
 
     let app_global_variables = Arc::new(tokio::sync::Mutex::new(AppGlobalVariables::new()));
 
-    let app = create_router(app_state, api_tokens, app_global_variables).layer(
+    let mut library_scheduler = LibraryScheduler::new(
+        scheduler,
+        base_path.clone(),
+        app_global_variables.clone(),
+        api_tokens.clone(),
+    );
+    library_scheduler.schedule_all_profiles().await;
+    let library_scheduler = Arc::new(tokio::sync::Mutex::new(library_scheduler));
+
+    let app = create_router(
+        app_state,
+        api_tokens,
+        app_global_variables,
+        library_scheduler,
+    )
+    .layer(
         ServiceBuilder::new().layer(
             CorsLayer::new()
                 .allow_origin(Any)
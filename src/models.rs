@@ -0,0 +1,10 @@
+pub mod annotation_model;
+pub mod book_model;
+pub mod bookmark_model;
+pub mod character_model;
+pub mod column_types;
+mod column_types_test;
+pub mod creator_model;
+pub mod library_model;
+pub mod reading_session_model;
+pub mod series_model;
@@ -0,0 +1,19 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct Annotation {
+    #[sqlx(rename = "ID_ANNOTATION")]
+    pub id: i64,
+    #[sqlx(rename = "BOOK_ID")]
+    pub book_id: String,
+    pub page: i64,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub note: String,
+    pub color: Option<String>,
+    pub created: Option<i64>,
+    pub updated: Option<i64>,
+}
@@ -0,0 +1,59 @@
+use crate::models::column_types::{Flag, Json, Number};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct Book {
+    #[sqlx(rename = "ID_book")]
+    pub id: String,
+    #[sqlx(rename = "NOM")]
+    pub name: String,
+    #[sqlx(rename = "PATH")]
+    pub path: String,
+    #[sqlx(rename = "API_ID")]
+    pub provider: Option<String>,
+    #[sqlx(rename = "ID_Series")]
+    pub series_id: Option<String>,
+    #[sqlx(rename = "note")]
+    pub rating: Number,
+    pub read: Flag,
+    pub reading: Flag,
+    pub unread: Flag,
+    pub favorite: Flag,
+    pub last_page: Number,
+    pub folder: Flag,
+    pub lock: Flag,
+    #[sqlx(rename = "URLCover")]
+    pub cover: Option<String>,
+    #[sqlx(rename = "issueNumber")]
+    pub issue: Number,
+    pub description: Option<String>,
+    pub format: Option<String>,
+    pub publisher: Option<String>,
+    #[sqlx(rename = "pageCount")]
+    pub page_count: Number,
+    #[sqlx(rename = "URLs")]
+    pub urls: Json,
+    pub series: Json,
+    pub creators: Json,
+    pub characters: Json,
+    pub prices: Json,
+    pub dates: Json,
+    #[sqlx(rename = "collectedIssues")]
+    pub collected_issues: Json,
+    pub collections: Json,
+    pub variants: Json,
+    #[sqlx(rename = "coverPlaceholder")]
+    pub cover_placeholder: Option<String>,
+    #[sqlx(rename = "contentHash")]
+    pub content_hash: Option<String>,
+    #[sqlx(rename = "coverHash")]
+    pub cover_hash: Option<String>,
+    #[sqlx(rename = "fileSize")]
+    pub file_size: Number,
+    #[sqlx(rename = "fileModified")]
+    pub file_modified: Number,
+    #[sqlx(rename = "fileInode")]
+    pub file_inode: Number,
+    pub missing: Flag,
+}
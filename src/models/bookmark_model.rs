@@ -0,0 +1,17 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct Bookmark {
+    #[sqlx(rename = "ID_BOOKMARK")]
+    pub id: i64,
+    #[sqlx(rename = "BOOK_ID")]
+    pub book_id: String,
+    #[sqlx(rename = "PATH")]
+    pub path: String,
+    pub page: i64,
+    pub name: Option<String>,
+    pub note: Option<String>,
+    pub thumbnail: Option<String>,
+    pub created: Option<i64>,
+}
@@ -0,0 +1,13 @@
+use crate::models::column_types::Json;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct Character {
+    #[sqlx(rename = "ID_CHAR")]
+    pub id: String,
+    pub name: Option<String>,
+    pub image: Json,
+    pub description: Option<String>,
+    pub url: Json,
+}
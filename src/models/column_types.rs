@@ -0,0 +1,111 @@
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::Value;
+use sqlx::error::BoxDynError;
+use sqlx::sqlite::{SqliteTypeInfo, SqliteValueRef};
+use sqlx::{Decode, Sqlite, Type, TypeInfo, ValueRef};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Flag(pub bool);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(transparent)]
+pub struct Number(pub Option<f64>);
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Json(pub Value);
+
+enum Raw<'r> {
+    Null,
+    Integer(i64),
+    Real(f64),
+    Text(&'r str),
+}
+
+fn read_raw(value: SqliteValueRef<'_>) -> Result<Raw<'_>, BoxDynError> {
+    if value.is_null() {
+        return Ok(Raw::Null);
+    }
+    let type_name = value.type_info().name().to_string();
+    Ok(match type_name.as_str() {
+        "INTEGER" | "BOOLEAN" => Raw::Integer(<i64 as Decode<Sqlite>>::decode(value)?),
+        "REAL" => Raw::Real(<f64 as Decode<Sqlite>>::decode(value)?),
+        _ => Raw::Text(<&str as Decode<Sqlite>>::decode(value)?),
+    })
+}
+
+fn is_empty_text(text: &str) -> bool {
+    matches!(text.trim(), "" | "null" | "NULL" | "undefined")
+}
+
+macro_rules! loose_sqlite_type {
+    ($name:ty) => {
+        impl Type<Sqlite> for $name {
+            fn type_info() -> SqliteTypeInfo {
+                <String as Type<Sqlite>>::type_info()
+            }
+
+            fn compatible(_ty: &SqliteTypeInfo) -> bool {
+                true
+            }
+        }
+    };
+}
+
+loose_sqlite_type!(Flag);
+loose_sqlite_type!(Number);
+loose_sqlite_type!(Json);
+
+impl<'r> Decode<'r, Sqlite> for Flag {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(Flag(match read_raw(value)? {
+            Raw::Null => false,
+            Raw::Integer(number) => number != 0,
+            Raw::Real(number) => number != 0.0,
+            Raw::Text(text) => matches!(text.trim().to_lowercase().as_str(), "true" | "1"),
+        }))
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for Number {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(Number(match read_raw(value)? {
+            Raw::Null => None,
+            Raw::Integer(number) => Some(number as f64),
+            Raw::Real(number) => Some(number),
+            Raw::Text(text) => text.trim().parse().ok().filter(|n: &f64| n.is_finite()),
+        }))
+    }
+}
+
+impl<'r> Decode<'r, Sqlite> for Json {
+    fn decode(value: SqliteValueRef<'r>) -> Result<Self, BoxDynError> {
+        Ok(Json(match read_raw(value)? {
+            Raw::Null => Value::Null,
+            Raw::Integer(number) => Value::from(number),
+            Raw::Real(number) => Value::from(number),
+            Raw::Text(text) if is_empty_text(text) => Value::Null,
+            Raw::Text(text) => match serde_json::from_str(text) {
+                Ok(Value::String(inner)) => match serde_json::from_str(&inner) {
+                    Ok(nested @ (Value::Object(_) | Value::Array(_))) => nested,
+                    _ => Value::String(inner),
+                },
+                Ok(value) => value,
+                Err(_) => Value::String(text.to_string()),
+            },
+        }))
+    }
+}
+
+impl Serialize for Number {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.0 {
+            Some(number) if number.fract() == 0.0 && number.abs() < 9.0e15 => {
+                serializer.serialize_i64(number as i64)
+            }
+            Some(number) => serializer.serialize_f64(number),
+            None => serializer.serialize_none(),
+        }
+    }
+}
@@ -0,0 +1,51 @@
+#[cfg(test)]
+mod tests {
+    use crate::models::column_types::{Flag, Json, Number};
+    use serde_json::json;
+    use sqlx::{Row, SqlitePool};
+
+    async fn select(sql: &str) -> sqlx::sqlite::SqliteRow {
+        let pool = SqlitePool::connect("sqlite::memory:").await.unwrap();
+        sqlx::query(sql).fetch_one(&pool).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_flag_accepts_integers_and_text() {
+        let row = select("SELECT 1 AS a, 0 AS b, 'true' AS c, 'false' AS d, NULL AS e").await;
+        let flags: Vec<bool> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|column| row.try_get::<Flag, _>(*column).unwrap().0)
+            .collect();
+        assert_eq!(flags, vec![true, false, true, false, false]);
+    }
+
+    #[tokio::test]
+    async fn test_number_serializes_integers_without_fraction() {
+        let row = select("SELECT 12 AS a, 1.5 AS b, '7' AS c, 'n/a' AS d, NULL AS e").await;
+        let numbers: Vec<Number> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|column| row.try_get::<Number, _>(*column).unwrap())
+            .collect();
+        assert_eq!(
+            serde_json::to_value(numbers).unwrap(),
+            json!([12, 1.5, 7, null, null])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_json_decodes_nested_and_double_encoded_values() {
+        let row = select(
+            r#"SELECT '{"english":"Berserk"}' AS a, '"[\"Action\",\"Horror\"]"' AS b,
+                      'plain title' AS c, 'null' AS d, 2006 AS e"#,
+        )
+        .await;
+        let values: Vec<Json> = ["a", "b", "c", "d", "e"]
+            .iter()
+            .map(|column| row.try_get::<Json, _>(*column).unwrap())
+            .collect();
+        assert_eq!(
+            serde_json::to_value(values).unwrap(),
+            json!([{"english": "Berserk"}, ["Action", "Horror"], "plain title", null, 2006])
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use crate::models::column_types::Flag;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct Library {
+    #[sqlx(rename = "ID_LIBRARY")]
+    pub id: i64,
+    #[sqlx(rename = "NAME")]
+    pub name: String,
+    #[sqlx(rename = "PATH")]
+    pub path: String,
+    #[sqlx(rename = "API_ID")]
+    pub provider: String,
+    #[sqlx(rename = "rescanSchedule")]
+    pub rescan_schedule: Option<String>,
+    #[sqlx(rename = "refreshSchedule")]
+    pub refresh_schedule: Option<String>,
+    #[sqlx(rename = "scheduleEnabled")]
+    pub schedule_enabled: Flag,
+}
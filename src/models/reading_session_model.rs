@@ -0,0 +1,18 @@
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct ReadingSession {
+    #[sqlx(rename = "ID_SESSION")]
+    pub id: i64,
+    #[sqlx(rename = "BOOK_ID")]
+    pub book_id: String,
+    pub started: i64,
+    pub ended: i64,
+    pub first_page: i64,
+    pub last_page: i64,
+    pub pages_read: i64,
+    pub page_count: Option<i64>,
+    pub completed: bool,
+    pub user_agent: Option<String>,
+}
@@ -0,0 +1,37 @@
+use crate::models::column_types::{Flag, Json, Number};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct Series {
+    #[sqlx(rename = "ID_Series")]
+    pub id: String,
+    pub title: Json,
+    #[sqlx(rename = "PATH")]
+    pub path: String,
+    #[sqlx(rename = "note")]
+    pub rating: Number,
+    #[sqlx(rename = "statut")]
+    pub status: Option<String>,
+    pub start_date: Json,
+    pub end_date: Json,
+    pub description: Option<String>,
+    #[sqlx(rename = "Score")]
+    pub score: Number,
+    pub genres: Json,
+    pub cover: Option<String>,
+    #[sqlx(rename = "BG")]
+    pub background: Option<String>,
+    #[sqlx(rename = "CHARACTERS")]
+    pub characters: Json,
+    #[sqlx(rename = "TRENDING")]
+    pub trending: Number,
+    #[sqlx(rename = "STAFF")]
+    pub staff: Json,
+    #[sqlx(rename = "SOURCE")]
+    pub source: Option<String>,
+    pub volumes: Number,
+    pub chapters: Number,
+    pub favorite: Flag,
+    pub lock: Flag,
+}
@@ -1,9 +1,10 @@
-use sqlx::sqlite::{SqliteConnectOptions, SqlitePool};
-use sqlx::{Column, Executor, Row, TypeInfo, ValueRef, query};
-use std::collections::HashMap;
+use sqlx::sqlite::{SqliteConnectOptions, SqliteConnection, SqlitePool, SqliteRow};
+use sqlx::{Column, Executor, Row, Statement, TypeInfo, ValueRef, query};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::iter::repeat;
 use std::path::Path;
+use std::sync::LazyLock;
 use tracing::{debug, error, info};
 
 use crate::utils::strip_outer_quotes;
@@ -47,7 +48,16 @@ pub async fn make_db(profile_owner: &str, base_path: &str) -> Result<(), sqlx::E
             collectedIssues TEXT,
             collections TEXT,
             variants TEXT,
-            lock BOOLEAN DEFAULT false NOT NULL
+            lock BOOLEAN DEFAULT false NOT NULL,
+            coverPlaceholder TEXT,
+            contentHash TEXT,
+            coverHash TEXT,
+            fileSize INTEGER,
+            fileModified INTEGER,
+            fileInode INTEGER,
+            missing BOOLEAN DEFAULT false NOT NULL,
+            ID_Series TEXT,
+            publisher TEXT
         );
         "#,
     )
@@ -179,19 +189,33 @@ pub async fn make_db(profile_owner: &str, base_path: &str) -> Result<(), sqlx::E
             NAME TEXT NOT NULL,
             PATH TEXT NOT NULL,
             API_ID TEXT NOT NULL,
+            rescanSchedule TEXT,
+            refreshSchedule TEXT,
+            scheduleEnabled BOOLEAN DEFAULT false NOT NULL,
             FOREIGN KEY (API_ID) REFERENCES API (ID_API)
         );
         "#,
     )
     .await?;
 
-    // Set the PRAGMA user_version
-    let version = env!("CARGO_PKG_VERSION").replace('.', "");
-    conn.execute(format!("PRAGMA user_version = {};", version).as_str())
-        .await?;
+    drop(conn);
+    migrate_db(&pool, None).await?;
 
     Ok(())
 }
+#[cfg(test)]
+pub async fn make_test_db() -> (tempfile::TempDir, SqlitePool) {
+    let temp = tempfile::tempdir().unwrap();
+    make_db("test_user", temp.path().to_str().unwrap())
+        .await
+        .unwrap();
+    let db_path = temp.path().join("profiles/test_user/CosmicComics.db");
+    let pool = SqlitePool::connect(&format!("sqlite://{}", db_path.display()))
+        .await
+        .unwrap();
+    (temp, pool)
+}
+
 pub async fn get_db(
     forwho: &str,
     base_path: &str,
@@ -205,10 +229,661 @@ pub async fn get_db(
     let mut opts: SqliteConnectOptions = format!("sqlite://{}", db_path).parse()?;
     opts = opts.foreign_keys(false);
     let pool = SqlitePool::connect_with(opts).await?;
+    if !MIGRATED_DBS.lock().unwrap().contains(&db_path) {
+        migrate_db(&pool, Some(&db_path)).await?;
+        MIGRATED_DBS.lock().unwrap().insert(db_path);
+    }
     opened_db.insert(forwho.to_string(), pool.clone());
     Ok(pool)
 }
 
+pub enum MigrationStep {
+    Execute(&'static str),
+    AddColumn(&'static str, &'static str, &'static str),
+    SearchIndex,
+}
+
+pub struct Migration {
+    pub version: i64,
+    pub name: &'static str,
+    pub steps: &'static [MigrationStep],
+}
+
+// Databases used to be stamped with the package version without dots (the Node
+// server and early releases of this one), so any stamp that is not a migration
+// version is read as a database that has not been migrated yet.
+pub const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1001,
+        name: "book_file_tracking",
+        steps: &[
+            MigrationStep::AddColumn("Books", "lock", "BOOLEAN DEFAULT false NOT NULL"),
+            MigrationStep::AddColumn("Series", "lock", "BOOLEAN DEFAULT false NOT NULL"),
+            MigrationStep::AddColumn("Books", "coverPlaceholder", "TEXT"),
+            MigrationStep::AddColumn("Books", "contentHash", "TEXT"),
+            MigrationStep::AddColumn("Books", "coverHash", "TEXT"),
+            MigrationStep::AddColumn("Books", "fileSize", "INTEGER"),
+            MigrationStep::AddColumn("Books", "fileModified", "INTEGER"),
+            MigrationStep::AddColumn("Books", "fileInode", "INTEGER"),
+            MigrationStep::AddColumn("Books", "missing", "BOOLEAN DEFAULT false NOT NULL"),
+            MigrationStep::AddColumn("Books", "ID_Series", "TEXT"),
+        ],
+    },
+    Migration {
+        version: 1002,
+        name: "library_schedules",
+        steps: &[
+            MigrationStep::AddColumn("Libraries", "rescanSchedule", "TEXT"),
+            MigrationStep::AddColumn("Libraries", "refreshSchedule", "TEXT"),
+            MigrationStep::AddColumn(
+                "Libraries",
+                "scheduleEnabled",
+                "BOOLEAN DEFAULT false NOT NULL",
+            ),
+        ],
+    },
+    Migration {
+        version: 1003,
+        name: "reading_orders",
+        steps: &[
+            MigrationStep::Execute(
+                r#"
+            CREATE TABLE IF NOT EXISTS ReadingOrders (
+                ID_READING_ORDER INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                NAME TEXT NOT NULL,
+                description TEXT
+            );
+                "#,
+            ),
+            MigrationStep::Execute(
+                r#"
+            CREATE TABLE IF NOT EXISTS ReadingOrderBooks (
+                ID_ENTRY INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                ID_READING_ORDER INTEGER NOT NULL,
+                ID_book TEXT,
+                position INTEGER NOT NULL,
+                cblSeries TEXT,
+                cblNumber TEXT,
+                cblVolume TEXT,
+                cblYear INTEGER,
+                confidence REAL,
+                FOREIGN KEY (ID_READING_ORDER) REFERENCES ReadingOrders (ID_READING_ORDER),
+                FOREIGN KEY (ID_book) REFERENCES Books (ID_book)
+            );
+                "#,
+            ),
+            MigrationStep::AddColumn("ReadingOrderBooks", "cblSeries", "TEXT"),
+            MigrationStep::AddColumn("ReadingOrderBooks", "cblNumber", "TEXT"),
+            MigrationStep::AddColumn("ReadingOrderBooks", "cblVolume", "TEXT"),
+            MigrationStep::AddColumn("ReadingOrderBooks", "cblYear", "INTEGER"),
+            MigrationStep::AddColumn("ReadingOrderBooks", "confidence", "REAL"),
+        ],
+    },
+    Migration {
+        version: 1004,
+        name: "collections",
+        steps: &[
+            MigrationStep::Execute(
+                r#"
+            CREATE TABLE IF NOT EXISTS Collections (
+                ID_COLLECTION INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                NAME TEXT NOT NULL,
+                description TEXT,
+                cover TEXT,
+                position INTEGER NOT NULL,
+                rules TEXT
+            );
+                "#,
+            ),
+            MigrationStep::Execute(
+                r#"
+            CREATE TABLE IF NOT EXISTS CollectionItems (
+                ID_COLLECTION_ITEM INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                ID_COLLECTION INTEGER NOT NULL,
+                itemType TEXT NOT NULL,
+                ID_item TEXT NOT NULL,
+                position INTEGER NOT NULL,
+                UNIQUE (ID_COLLECTION, itemType, ID_item),
+                FOREIGN KEY (ID_COLLECTION) REFERENCES Collections (ID_COLLECTION)
+            );
+                "#,
+            ),
+            MigrationStep::AddColumn("Collections", "rules", "TEXT"),
+        ],
+    },
+    Migration {
+        version: 1005,
+        name: "tags_and_custom_fields",
+        steps: &[
+            MigrationStep::Execute(
+                r#"
+            CREATE TABLE IF NOT EXISTS Tags (
+                ID_TAG INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                NAME TEXT NOT NULL UNIQUE COLLATE NOCASE
+            );
+                "#,
+            ),
+            MigrationStep::Execute(
+                r#"
+            CREATE TABLE IF NOT EXISTS TagLinks (
+                ID_TAG INTEGER NOT NULL,
+                itemType TEXT NOT NULL,
+                ID_item TEXT NOT NULL,
+                PRIMARY KEY (ID_TAG, itemType, ID_item),
+                FOREIGN KEY (ID_TAG) REFERENCES Tags (ID_TAG)
+            );
+                "#,
+            ),
+            MigrationStep::Execute(
+                r#"
+            CREATE TABLE IF NOT EXISTS CustomFields (
+                ID_FIELD INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                NAME TEXT NOT NULL COLLATE NOCASE,
+                fieldType TEXT NOT NULL,
+                target TEXT NOT NULL,
+                options TEXT,
+                UNIQUE (NAME, target)
+            );
+                "#,
+            ),
+            MigrationStep::Execute(
+                r#"
+            CREATE TABLE IF NOT EXISTS CustomFieldValues (
+                ID_FIELD INTEGER NOT NULL,
+                ID_item TEXT NOT NULL,
+                value,
+                PRIMARY KEY (ID_FIELD, ID_item),
+                FOREIGN KEY (ID_FIELD) REFERENCES CustomFields (ID_FIELD)
+            );
+                "#,
+            ),
+        ],
+    },
+    Migration {
+        version: 1006,
+        name: "search_index",
+        steps: &[MigrationStep::SearchIndex],
+    },
+    Migration {
+        version: 1007,
+        name: "creator_and_character_links",
+        steps: &[
+            MigrationStep::Execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS CreatorLinks (
+                    ID_CREATOR TEXT NOT NULL,
+                    itemType TEXT NOT NULL,
+                    ID_item TEXT NOT NULL,
+                    role TEXT NOT NULL DEFAULT '',
+                    PRIMARY KEY (ID_CREATOR, itemType, ID_item, role),
+                    FOREIGN KEY (ID_CREATOR) REFERENCES Creators (ID_CREATOR)
+                );
+                "#,
+            ),
+            MigrationStep::Execute(
+                "CREATE INDEX IF NOT EXISTS CreatorLinksItem ON CreatorLinks (itemType, ID_item);",
+            ),
+            MigrationStep::Execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS CharacterLinks (
+                    ID_CHAR TEXT NOT NULL,
+                    itemType TEXT NOT NULL,
+                    ID_item TEXT NOT NULL,
+                    role TEXT NOT NULL DEFAULT '',
+                    PRIMARY KEY (ID_CHAR, itemType, ID_item, role),
+                    FOREIGN KEY (ID_CHAR) REFERENCES Characters (ID_CHAR)
+                );
+                "#,
+            ),
+            MigrationStep::Execute(
+                "CREATE INDEX IF NOT EXISTS CharacterLinksItem ON CharacterLinks (itemType, ID_item);",
+            ),
+        ],
+    },
+    Migration {
+        version: 1008,
+        name: "bookmark_notes",
+        steps: &[
+            MigrationStep::Execute(
+                r#"
+            CREATE TABLE IF NOT EXISTS Bookmarks (
+                ID_BOOKMARK INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                BOOK_ID TEXT NOT NULL,
+                PATH TEXT NOT NULL,
+                page INTEGER NOT NULL,
+                FOREIGN KEY (BOOK_ID) REFERENCES Books (ID_book)
+            );
+                "#,
+            ),
+            MigrationStep::AddColumn("Bookmarks", "name", "TEXT"),
+            MigrationStep::AddColumn("Bookmarks", "note", "TEXT"),
+            MigrationStep::AddColumn("Bookmarks", "thumbnail", "TEXT"),
+            MigrationStep::AddColumn("Bookmarks", "created", "INTEGER"),
+        ],
+    },
+    Migration {
+        version: 1009,
+        name: "page_annotations",
+        steps: &[
+            MigrationStep::Execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS Annotations (
+                    ID_ANNOTATION INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                    BOOK_ID TEXT NOT NULL,
+                    page INTEGER NOT NULL,
+                    x REAL NOT NULL,
+                    y REAL NOT NULL,
+                    width REAL NOT NULL,
+                    height REAL NOT NULL,
+                    note TEXT NOT NULL,
+                    color TEXT,
+                    created INTEGER,
+                    updated INTEGER,
+                    FOREIGN KEY (BOOK_ID) REFERENCES Books (ID_book)
+                );
+                "#,
+            ),
+            MigrationStep::Execute(
+                "CREATE INDEX IF NOT EXISTS AnnotationsPage ON Annotations (BOOK_ID, page);",
+            ),
+        ],
+    },
+    Migration {
+        version: 1010,
+        name: "reading_sessions",
+        steps: &[
+            MigrationStep::Execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS ReadingSessions (
+                    ID_SESSION INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL,
+                    BOOK_ID TEXT NOT NULL,
+                    started INTEGER NOT NULL,
+                    ended INTEGER NOT NULL,
+                    first_page INTEGER NOT NULL,
+                    last_page INTEGER NOT NULL,
+                    pages_read INTEGER NOT NULL DEFAULT 1,
+                    page_count INTEGER,
+                    completed INTEGER NOT NULL DEFAULT 0,
+                    user_agent TEXT,
+                    FOREIGN KEY (BOOK_ID) REFERENCES Books (ID_book)
+                );
+                "#,
+            ),
+            MigrationStep::Execute(
+                r#"
+                CREATE TABLE IF NOT EXISTS ReadingSessionPages (
+                    ID_SESSION INTEGER NOT NULL,
+                    page INTEGER NOT NULL,
+                    viewed INTEGER NOT NULL,
+                    PRIMARY KEY (ID_SESSION, page),
+                    FOREIGN KEY (ID_SESSION) REFERENCES ReadingSessions (ID_SESSION)
+                );
+                "#,
+            ),
+            MigrationStep::Execute(
+                "CREATE INDEX IF NOT EXISTS ReadingSessionsBook ON ReadingSessions (BOOK_ID, ended);",
+            ),
+            MigrationStep::Execute(
+                "CREATE INDEX IF NOT EXISTS ReadingSessionsEnded ON ReadingSessions (ended);",
+            ),
+        ],
+    },
+    Migration {
+        version: 1011,
+        name: "book_publishers",
+        steps: &[MigrationStep::AddColumn("Books", "publisher", "TEXT")],
+    },
+];
+
+pub struct SearchIndex {
+    pub entity: &'static str,
+    pub table: &'static str,
+    pub fts: &'static str,
+    pub key: &'static str,
+    pub image: &'static str,
+    pub item_type: Option<&'static str>,
+    pub columns: &'static [(&'static str, &'static str)],
+}
+
+const JSON_NAMES: &str = "(CASE WHEN json_valid({value}) THEN (SELECT group_concat(value, ' ') FROM json_tree({value}) \
+     WHERE type = 'text' AND (key IN ('name', 'full', 'native') OR typeof(key) = 'integer')) ELSE {value} END)";
+const TAG_NAMES: &str = "(SELECT group_concat(Tags.NAME, ' ') FROM TagLinks JOIN Tags ON Tags.ID_TAG = TagLinks.ID_TAG \
+     WHERE TagLinks.itemType = '{item_type}' AND TagLinks.ID_item = {row}.{key})";
+const CUSTOM_VALUES: &str = "(SELECT group_concat(CustomFieldValues.value, ' ') FROM CustomFieldValues \
+     JOIN CustomFields ON CustomFields.ID_FIELD = CustomFieldValues.ID_FIELD \
+     WHERE CustomFields.target = '{item_type}' AND CustomFields.fieldType != 'number' \
+     AND CustomFieldValues.ID_item = {row}.{key})";
+
+pub const SEARCH_INDEXES: &[SearchIndex] = &[
+    SearchIndex {
+        entity: "books",
+        table: "Books",
+        fts: "BooksSearch",
+        key: "ID_book",
+        image: "URLCover",
+        item_type: Some("book"),
+        columns: &[
+            ("title", "{row}.NOM"),
+            ("description", "{row}.description"),
+            ("creators", "{names:{row}.creators}"),
+            ("characters", "{names:{row}.characters}"),
+            ("path", "{row}.PATH"),
+        ],
+    },
+    SearchIndex {
+        entity: "series",
+        table: "Series",
+        fts: "SeriesSearch",
+        key: "ID_Series",
+        image: "cover",
+        item_type: Some("series"),
+        columns: &[
+            (
+                "title",
+                "(CASE WHEN json_valid({row}.title) AND json_type({row}.title) = 'object' \
+                 THEN trim(coalesce(json_extract({row}.title, '$.english'), '') || ' ' || \
+                 coalesce(json_extract({row}.title, '$.romaji'), '') || ' ' || \
+                 coalesce(json_extract({row}.title, '$.native'), '')) ELSE {row}.title END)",
+            ),
+            ("description", "{row}.description"),
+            ("creators", "{names:{row}.STAFF}"),
+            ("characters", "{names:{row}.CHARACTERS}"),
+            ("path", "{row}.PATH"),
+        ],
+    },
+    SearchIndex {
+        entity: "creators",
+        table: "Creators",
+        fts: "CreatorsSearch",
+        key: "ID_CREATOR",
+        image: "image",
+        item_type: None,
+        columns: &[
+            ("title", "{row}.name"),
+            ("description", "{row}.description"),
+        ],
+    },
+    SearchIndex {
+        entity: "characters",
+        table: "Characters",
+        fts: "CharactersSearch",
+        key: "ID_CHAR",
+        image: "image",
+        item_type: None,
+        columns: &[
+            ("title", "{row}.name"),
+            ("description", "{row}.description"),
+        ],
+    },
+];
+
+impl SearchIndex {
+    pub fn column_names(&self) -> Vec<&'static str> {
+        let mut names: Vec<&'static str> = self.columns.iter().map(|(name, _)| *name).collect();
+        if self.item_type.is_some() {
+            names.extend(["tags", "custom"]);
+        }
+        names
+    }
+
+    fn values(&self, row: &str) -> Vec<String> {
+        let mut values: Vec<String> = self
+            .columns
+            .iter()
+            .map(|(_, expr)| match expr.strip_prefix("{names:") {
+                Some(value) => JSON_NAMES.replace("{value}", value.trim_end_matches('}')),
+                None => expr.to_string(),
+            })
+            .collect();
+        if let Some(item_type) = self.item_type {
+            values.push(TAG_NAMES.replace("{item_type}", item_type));
+            values.push(CUSTOM_VALUES.replace("{item_type}", item_type));
+        }
+        values
+            .into_iter()
+            .map(|value| value.replace("{row}", row).replace("{key}", self.key))
+            .collect()
+    }
+
+    fn insert(&self, row: &str) -> String {
+        format!(
+            "INSERT INTO {}(rowid, {}, {}) VALUES ({}.rowid, {}.{}, {});",
+            self.fts,
+            self.key,
+            self.column_names().join(", "),
+            row,
+            row,
+            self.key,
+            self.values(row).join(", ")
+        )
+    }
+
+    fn touch(&self, condition: &str) -> String {
+        format!(
+            "UPDATE {} SET {} = {} WHERE {};",
+            self.table, self.key, self.key, condition
+        )
+    }
+
+    fn statements(&self) -> Vec<String> {
+        let mut sources: Vec<String> = self
+            .columns
+            .iter()
+            .flat_map(|(_, expr)| {
+                expr.split("{row}.")
+                    .skip(1)
+                    .map(|rest| {
+                        rest.split(|c: char| !c.is_alphanumeric() && c != '_')
+                            .next()
+                            .unwrap_or_default()
+                            .to_string()
+                    })
+                    .collect::<Vec<_>>()
+            })
+            .chain(std::iter::once(self.key.to_string()))
+            .collect();
+        sources.sort();
+        sources.dedup();
+        let mut statements = vec![
+            format!(
+                "CREATE VIRTUAL TABLE IF NOT EXISTS {} USING fts5({} UNINDEXED, {}, tokenize = 'unicode61 remove_diacritics 2');",
+                self.fts,
+                self.key,
+                self.column_names().join(", ")
+            ),
+            format!(
+                "CREATE TRIGGER IF NOT EXISTS {fts}_ai AFTER INSERT ON {table} BEGIN {insert} END;",
+                fts = self.fts,
+                table = self.table,
+                insert = self.insert("NEW")
+            ),
+            format!(
+                "CREATE TRIGGER IF NOT EXISTS {fts}_au AFTER UPDATE OF {sources} ON {table} BEGIN \
+                 DELETE FROM {fts} WHERE rowid = OLD.rowid; {insert} END;",
+                fts = self.fts,
+                table = self.table,
+                sources = sources.join(", "),
+                insert = self.insert("NEW")
+            ),
+            format!(
+                "CREATE TRIGGER IF NOT EXISTS {fts}_ad AFTER DELETE ON {table} BEGIN \
+                 DELETE FROM {fts} WHERE rowid = OLD.rowid; END;",
+                fts = self.fts,
+                table = self.table
+            ),
+        ];
+        if let Some(item_type) = self.item_type {
+            for (event, row) in [("INSERT", "NEW"), ("DELETE", "OLD")] {
+                statements.push(format!(
+                    "CREATE TRIGGER IF NOT EXISTS {fts}_tags_{suffix} AFTER {event} ON TagLinks \
+                     WHEN {row}.itemType = '{item_type}' BEGIN {touch} END;",
+                    fts = self.fts,
+                    suffix = event.to_lowercase(),
+                    touch = self.touch(&format!("{} = {}.ID_item", self.key, row))
+                ));
+            }
+            statements.push(format!(
+                "CREATE TRIGGER IF NOT EXISTS {fts}_tags_rename AFTER UPDATE OF NAME ON Tags BEGIN {touch} END;",
+                fts = self.fts,
+                touch = self.touch(&format!(
+                    "{} IN (SELECT ID_item FROM TagLinks WHERE ID_TAG = NEW.ID_TAG AND itemType = '{}')",
+                    self.key, item_type
+                ))
+            ));
+            for (event, row) in [("INSERT", "NEW"), ("UPDATE", "NEW"), ("DELETE", "OLD")] {
+                statements.push(format!(
+                    "CREATE TRIGGER IF NOT EXISTS {fts}_custom_{suffix} AFTER {event} ON CustomFieldValues \
+                     WHEN (SELECT target FROM CustomFields WHERE ID_FIELD = {row}.ID_FIELD) = '{item_type}' \
+                     BEGIN {touch} END;",
+                    fts = self.fts,
+                    suffix = event.to_lowercase(),
+                    touch = self.touch(&format!("{} = {}.ID_item", self.key, row))
+                ));
+            }
+        }
+        statements.push(format!(
+            "INSERT INTO {fts}(rowid, {key}, {columns}) SELECT {table}.rowid, {table}.{key}, {values} \
+             FROM {table} WHERE {table}.rowid NOT IN (SELECT rowid FROM {fts});",
+            fts = self.fts,
+            key = self.key,
+            table = self.table,
+            columns = self.column_names().join(", "),
+            values = self.values(self.table).join(", ")
+        ));
+        statements
+    }
+}
+
+pub async fn create_search_index(conn: &mut SqliteConnection) -> Result<(), sqlx::Error> {
+    for index in SEARCH_INDEXES {
+        for statement in index.statements() {
+            query(&statement).execute(&mut *conn).await?;
+        }
+    }
+    Ok(())
+}
+
+pub async fn rebuild_search_index(pool: &SqlitePool) -> Result<(), sqlx::Error> {
+    for index in SEARCH_INDEXES {
+        query(&format!("DELETE FROM {};", index.fts))
+            .execute(pool)
+            .await?;
+    }
+    create_search_index(&mut *pool.acquire().await?).await
+}
+
+static MIGRATED_DBS: LazyLock<std::sync::Mutex<HashSet<String>>> =
+    LazyLock::new(|| std::sync::Mutex::new(HashSet::new()));
+
+pub fn latest_schema_version() -> i64 {
+    MIGRATIONS.last().map_or(0, |migration| migration.version)
+}
+
+fn schema_version_from_stamp(stamp: i64) -> i64 {
+    if MIGRATIONS
+        .iter()
+        .any(|migration| migration.version == stamp)
+    {
+        stamp
+    } else {
+        0
+    }
+}
+
+pub async fn get_schema_version(pool: &SqlitePool) -> Result<i64, sqlx::Error> {
+    let stamp: i64 = sqlx::query_scalar("PRAGMA user_version;")
+        .fetch_one(pool)
+        .await?;
+    Ok(schema_version_from_stamp(stamp))
+}
+
+async fn apply_migration_step(
+    conn: &mut SqliteConnection,
+    step: &MigrationStep,
+) -> Result<(), sqlx::Error> {
+    match step {
+        MigrationStep::Execute(statement) => {
+            query(statement).execute(&mut *conn).await?;
+        }
+        MigrationStep::AddColumn(table, column, definition) => {
+            let rows = query(&format!("PRAGMA table_info({});", table))
+                .fetch_all(&mut *conn)
+                .await?;
+            let exists = rows
+                .iter()
+                .any(|row| row.try_get::<String, _>("name").ok().as_deref() == Some(*column));
+            if rows.is_empty() {
+                return Err(sqlx::Error::Protocol(format!(
+                    "Cannot add column {} to missing table {}",
+                    column, table
+                )));
+            }
+            if !exists {
+                info!("Adding column {} to {}", column, table);
+                query(&format!(
+                    "ALTER TABLE {} ADD COLUMN {} {};",
+                    table, column, definition
+                ))
+                .execute(&mut *conn)
+                .await?;
+            }
+        }
+        MigrationStep::SearchIndex => create_search_index(conn).await?,
+    }
+    Ok(())
+}
+
+pub async fn backup_db(
+    pool: &SqlitePool,
+    db_path: &str,
+    version: i64,
+) -> Result<String, sqlx::Error> {
+    let backup_path = format!("{}.v{}.bak", db_path, version);
+    if Path::new(&backup_path).exists() {
+        fs::remove_file(&backup_path)?;
+    }
+    query("VACUUM INTO ?;")
+        .bind(&backup_path)
+        .execute(pool)
+        .await?;
+    Ok(backup_path)
+}
+
+pub async fn migrate_db(pool: &SqlitePool, db_path: Option<&str>) -> Result<i64, sqlx::Error> {
+    let stamp: i64 = sqlx::query_scalar("PRAGMA user_version;")
+        .fetch_one(pool)
+        .await?;
+    let current = schema_version_from_stamp(stamp);
+    if current >= latest_schema_version() {
+        return Ok(current);
+    }
+    if let Some(db_path) = db_path {
+        let backup_path = backup_db(pool, db_path, stamp).await?;
+        info!("Backed up {} to {} before migrating", db_path, backup_path);
+    }
+    for migration in MIGRATIONS {
+        let mut tx = pool.begin_with("BEGIN IMMEDIATE").await?;
+        let stamp: i64 = sqlx::query_scalar("PRAGMA user_version;")
+            .fetch_one(&mut *tx)
+            .await?;
+        if schema_version_from_stamp(stamp) >= migration.version {
+            continue;
+        }
+        info!(
+            "Applying migration {} ({})",
+            migration.version, migration.name
+        );
+        for step in migration.steps {
+            apply_migration_step(&mut tx, step).await?;
+        }
+        query(&format!("PRAGMA user_version = {};", migration.version))
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+    }
+    get_schema_version(pool).await
+}
+
 use sqlx::{QueryBuilder, Sqlite};
 
 pub async fn update_db(
@@ -316,6 +991,27 @@ pub async fn insert_into_db(
     Ok(())
 }
 
+pub fn row_to_map(row: &SqliteRow) -> Result<HashMap<String, String>, sqlx::Error> {
+    let mut row_map = HashMap::new();
+    for (i, column) in row.columns().iter().enumerate() {
+        let raw_value = row.try_get_raw(i)?;
+        let value_str = if raw_value.is_null() {
+            "NULL".to_string()
+        } else {
+            match raw_value.type_info().name() {
+                "INTEGER" => row.try_get::<i64, _>(i).map(|v| v.to_string()),
+                "TEXT" => row.try_get::<String, _>(i),
+                "BOOLEAN" => row.try_get::<bool, _>(i).map(|v| v.to_string()),
+                "REAL" => row.try_get::<f64, _>(i).map(|v| v.to_string()),
+                _ => Ok("<unsupported>".to_string()),
+            }
+            .unwrap_or_else(|_| "<error>".to_string())
+        };
+        row_map.insert(column.name().to_string(), value_str);
+    }
+    Ok(row_map)
+}
+
 pub async fn select_from_db(
     db_pool: &SqlitePool,
     table: &str,
@@ -345,29 +1041,7 @@ pub async fn select_from_db(
     }
     info!("Executing query: {}", query_str);
     let rows = query(&query_str).fetch_all(db_pool).await?;
-    let mut results = Vec::new();
-    for row in rows {
-        let mut row_map = HashMap::new();
-        for (i, column) in row.columns().iter().enumerate() {
-            let raw_value = row.try_get_raw(i)?;
-            let value_str = if raw_value.is_null() {
-                "NULL".to_string()
-            } else {
-                match raw_value.type_info().name() {
-                    "INTEGER" => row.try_get::<i64, _>(i).map(|v| v.to_string()),
-                    "TEXT" => row.try_get::<String, _>(i),
-                    "BOOLEAN" => row.try_get::<bool, _>(i).map(|v| v.to_string()),
-                    "REAL" => row.try_get::<f64, _>(i).map(|v| v.to_string()),
-                    _ => Ok("<unsupported>".to_string()),
-                }
-                .unwrap_or_else(|_| "<error>".to_string())
-            };
-
-            row_map.insert(column.name().to_string(), value_str);
-        }
-        results.push(row_map);
-    }
-    Ok(results)
+    rows.iter().map(row_to_map).collect()
 }
 
 pub async fn select_from_db_with_options(
@@ -377,32 +1051,67 @@ pub async fn select_from_db_with_options(
     let query_str = format!("SELECT {};", option);
     info!("Executing query: {}", query_str);
     let rows = query(&query_str).fetch_all(db_pool).await?;
-    let mut results = Vec::new();
-    for row in rows {
-        let mut row_map = HashMap::new();
-        for (i, column) in row.columns().iter().enumerate() {
-            let raw_value = row.try_get_raw(i)?;
-            let value_str = if raw_value.is_null() {
-                "NULL".to_string()
-            } else {
-                match raw_value.type_info().name() {
-                    "INTEGER" => row.try_get::<i64, _>(i).map(|v| v.to_string()),
-                    "TEXT" => row.try_get::<String, _>(i),
-                    "BOOLEAN" => row.try_get::<bool, _>(i).map(|v| v.to_string()),
-                    "REAL" => row.try_get::<f64, _>(i).map(|v| v.to_string()),
-                    _ => Ok("<unsupported>".to_string()),
-                }
-                .unwrap_or_else(|_| "<error>".to_string())
-            };
-
-            row_map.insert(
-                column.name().to_string(),
-                strip_outer_quotes(&value_str[..]).to_string(),
-            );
-        }
-        results.push(row_map);
-    }
-    Ok(results)
+    rows.iter()
+        .map(|row| {
+            Ok(row_to_map(row)?
+                .into_iter()
+                .map(|(column, value)| (column, strip_outer_quotes(&value).to_string()))
+                .collect())
+        })
+        .collect()
+}
+
+fn trim_select_options(option: &str) -> &str {
+    option.trim().trim_end_matches(';').trim_end()
+}
+
+pub async fn get_select_columns(
+    db_pool: &SqlitePool,
+    option: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    let query_str = format!("SELECT {}", trim_select_options(option));
+    let statement = db_pool.prepare(&query_str).await?;
+    Ok(statement
+        .columns()
+        .iter()
+        .map(|column| column.name().to_string())
+        .collect())
+}
+
+pub async fn select_page_from_db_with_options(
+    db_pool: &SqlitePool,
+    option: &str,
+    sort: Option<(&str, &str)>,
+    limit: i64,
+    offset: i64,
+) -> Result<(Vec<HashMap<String, String>>, i64), sqlx::Error> {
+    let option = trim_select_options(option);
+    let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM (SELECT {})", option))
+        .fetch_one(db_pool)
+        .await?;
+    let order_by = sort
+        .map(|(column, order)| format!(" ORDER BY \"{}\" {}", column.replace('"', "\"\""), order))
+        .unwrap_or_default();
+    let query_str = format!(
+        "SELECT * FROM (SELECT {}){} LIMIT ? OFFSET ?;",
+        option, order_by
+    );
+    info!("Executing query: {}", query_str);
+    let rows = query(&query_str)
+        .bind(limit)
+        .bind(offset)
+        .fetch_all(db_pool)
+        .await?;
+    let items = rows
+        .iter()
+        .map(|row| {
+            Ok(row_to_map(row)?
+                .into_iter()
+                .map(|(column, value)| (column, strip_outer_quotes(&value).to_string()))
+                .collect())
+        })
+        .collect::<Result<_, sqlx::Error>>()?;
+    Ok((items, total))
 }
 
 pub async fn delete_from_db(
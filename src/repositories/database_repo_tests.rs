@@ -1,8 +1,9 @@
 #[cfg(test)]
 mod tests {
     use crate::repositories::database_repo::{
-        delete_from_db, get_db, insert_into_db, make_db, select_from_db,
-        select_from_db_with_options, update_db,
+        delete_from_db, get_db, get_schema_version, get_select_columns, insert_into_db,
+        latest_schema_version, make_db, migrate_db, select_from_db, select_from_db_with_options,
+        select_page_from_db_with_options, update_db,
     };
     use sqlx::SqlitePool;
     use std::collections::HashMap;
@@ -166,4 +167,153 @@ mod tests {
         assert!(!results.is_empty());
         assert_eq!(results[0]["name"], "API");
     }
+
+    #[tokio::test]
+    async fn test_select_page_from_db_with_options_limits_and_sorts() {
+        let (base_path, profile) = get_test_paths();
+        let pool = setup_db(&base_path, &profile).await;
+
+        let columns = get_select_columns(&pool, "ID_API, NOM FROM API;")
+            .await
+            .unwrap();
+        assert_eq!(columns, vec!["ID_API", "NOM"]);
+
+        let (results, total) = select_page_from_db_with_options(
+            &pool,
+            "ID_API, NOM FROM API;",
+            Some(("NOM", "DESC")),
+            2,
+            1,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(total, 5);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["NOM"], "Marvel");
+        assert_eq!(results[1]["NOM"], "MANUAL");
+    }
+
+    #[tokio::test]
+    async fn test_make_db_is_at_latest_schema_version() {
+        let (base_path, profile) = get_test_paths();
+        let pool = setup_db(&base_path, &profile).await;
+
+        assert_eq!(
+            get_schema_version(&pool).await.unwrap(),
+            latest_schema_version()
+        );
+        assert_eq!(
+            migrate_db(&pool, None).await.unwrap(),
+            latest_schema_version()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_get_db_migrates_legacy_node_database() {
+        let (base_path, profile) = get_test_paths();
+        let db_dir = format!("{}/profiles/{}", base_path, profile);
+        std::fs::create_dir_all(&db_dir).unwrap();
+        let db_path = format!("{}/CosmicComics.db", db_dir);
+        let legacy = SqlitePool::connect(&format!("sqlite://{}?mode=rwc", db_path))
+            .await
+            .unwrap();
+        for statement in [
+            "CREATE TABLE Books (ID_book TEXT PRIMARY KEY NOT NULL, API_ID TEXT, NOM TEXT NOT NULL, \
+             note INTEGER, read BOOLEAN NOT NULL, reading BOOLEAN NOT NULL, unread BOOLEAN NOT NULL, \
+             favorite BOOLEAN NOT NULL, last_page INTEGER NOT NULL, folder BOOLEAN NOT NULL, \
+             PATH TEXT NOT NULL, URLCover TEXT, issueNumber INTEGER, description TEXT, format TEXT, \
+             pageCount INTEGER, URLs TEXT, series TEXT, creators TEXT, characters TEXT, prices TEXT, \
+             dates TEXT, collectedIssues TEXT, collections TEXT, variants TEXT);",
+            "CREATE TABLE Series (ID_Series TEXT PRIMARY KEY NOT NULL, title TEXT NOT NULL, \
+             description TEXT, cover TEXT, CHARACTERS TEXT, STAFF TEXT, favorite BOOLEAN NOT NULL, \
+             PATH TEXT NOT NULL);",
+            "CREATE TABLE Creators (ID_CREATOR TEXT PRIMARY KEY NOT NULL, name TEXT, image TEXT, \
+             description TEXT, url TEXT);",
+            "CREATE TABLE Characters (ID_CHAR TEXT PRIMARY KEY NOT NULL, name TEXT, image TEXT, \
+             description TEXT, url TEXT);",
+            "CREATE TABLE Libraries (ID_LIBRARY INTEGER PRIMARY KEY AUTOINCREMENT NOT NULL, \
+             NAME TEXT NOT NULL, PATH TEXT NOT NULL, API_ID TEXT NOT NULL);",
+            "INSERT INTO Books (ID_book, NOM, read, reading, unread, favorite, last_page, folder, PATH) \
+             VALUES ('b1', 'Saga #1', 0, 0, 1, 0, 0, 0, '/comics/saga1.cbz');",
+            "PRAGMA user_version = 210;",
+        ] {
+            sqlx::query(statement).execute(&legacy).await.unwrap();
+        }
+        legacy.close().await;
+
+        let pool = get_db(&profile, &base_path, HashMap::new()).await.unwrap();
+
+        assert_eq!(
+            get_schema_version(&pool).await.unwrap(),
+            latest_schema_version()
+        );
+        assert!(Path::new(&format!("{}.v210.bak", db_path)).exists());
+        let (missing, schedule_enabled): (bool, bool) = sqlx::query_as(
+            "SELECT missing, (SELECT COUNT(*) FROM Libraries WHERE scheduleEnabled) FROM Books;",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert!(!missing && !schedule_enabled);
+        let hits: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM BooksSearch WHERE BooksSearch MATCH 'saga';")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(hits, 1);
+        for table in [
+            "Bookmarks",
+            "Collections",
+            "Tags",
+            "ReadingOrderBooks",
+            "CustomFieldValues",
+        ] {
+            let count: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM {};", table))
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+            assert_eq!(count, 0);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_migrate_db_fails_when_a_column_targets_a_missing_table() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("CosmicComics.db");
+        let pool = SqlitePool::connect(&format!("sqlite://{}?mode=rwc", db_path.display()))
+            .await
+            .unwrap();
+        sqlx::query("PRAGMA user_version = 210;")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let error = migrate_db(&pool, None).await.unwrap_err();
+
+        assert!(error.to_string().contains("missing table Books"));
+        let stamp: i64 = sqlx::query_scalar("PRAGMA user_version;")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(stamp, 210);
+    }
+
+    #[tokio::test]
+    async fn test_migrate_db_runs_every_migration_for_a_legacy_stamp() {
+        let dir = tempdir().unwrap();
+        let db_path = dir.path().join("CosmicComics.db");
+        let pool = SqlitePool::connect(&format!("sqlite://{}?mode=rwc", db_path.display()))
+            .await
+            .unwrap();
+        sqlx::query("PRAGMA user_version = 2110;")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        assert_eq!(get_schema_version(&pool).await.unwrap(), 0);
+        let error = migrate_db(&pool, None).await.unwrap_err();
+
+        assert!(error.to_string().contains("missing table Books"));
+    }
 }
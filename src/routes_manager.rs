@@ -1,13 +1,24 @@
 use crate::ApiTokens;
 use crate::AppConfig;
 use crate::AppGlobalVariables;
+use crate::endpoints::annotation_endpoints::annotation_routes;
 use crate::endpoints::api_endpoints::api_routes;
+use crate::endpoints::bookmark_endpoints::bookmark_routes;
+use crate::endpoints::collection_endpoints::collection_routes;
 use crate::endpoints::collectionner_endpoints::collectionner_routes;
 use crate::endpoints::common_endpoints::common_routes;
 use crate::endpoints::database_endpoints::database_routes;
+use crate::endpoints::library_endpoints::library_routes;
+use crate::endpoints::metadata_endpoints::metadata_routes;
+use crate::endpoints::on_deck_endpoints::on_deck_routes;
 use crate::endpoints::profile_endpoints::authentication_routes;
+use crate::endpoints::reading_history_endpoints::reading_history_routes;
+use crate::endpoints::reading_order_endpoints::reading_order_routes;
+use crate::endpoints::resource_endpoints::resource_routes;
+use crate::endpoints::search_endpoints::search_routes;
 use crate::endpoints::settings_endpoints::settings_routes;
 use crate::endpoints::viewer_endpoints::viewer_routes;
+use crate::services::scheduler_service::LibraryScheduler;
 use axum::Router;
 use axum::middleware::from_fn;
 use axum::{
@@ -24,6 +35,7 @@ pub struct AppState {
     pub config: Arc<tokio::sync::Mutex<AppConfig>>,
     pub creds: Arc<tokio::sync::Mutex<ApiTokens>>,
     pub global_vars: Arc<tokio::sync::Mutex<AppGlobalVariables>>,
+    pub scheduler: Arc<tokio::sync::Mutex<LibraryScheduler>>,
 }
 
 pub async fn log_request(req: Request<Body>, next: Next) -> impl IntoResponse {
@@ -106,11 +118,13 @@ pub fn create_router(
     config: Arc<tokio::sync::Mutex<AppConfig>>,
     creds: Arc<tokio::sync::Mutex<ApiTokens>>,
     global_vars: Arc<tokio::sync::Mutex<AppGlobalVariables>>,
+    scheduler: Arc<tokio::sync::Mutex<LibraryScheduler>>,
 ) -> Router {
     let state = Arc::new(tokio::sync::Mutex::new(AppState {
         config: config.clone(),
         creds: creds.clone(),
         global_vars: global_vars.clone(),
+        scheduler: scheduler.clone(),
     }));
     Router::new()
         .merge(common_routes(state.clone()))
@@ -119,6 +133,16 @@ pub fn create_router(
         .merge(collectionner_routes(state.clone()))
         .merge(viewer_routes(state.clone()))
         .merge(database_routes(state.clone()))
+        .merge(library_routes(state.clone()))
+        .merge(reading_order_routes(state.clone()))
+        .merge(collection_routes(state.clone()))
+        .merge(metadata_routes(state.clone()))
+        .merge(resource_routes(state.clone()))
+        .merge(bookmark_routes(state.clone()))
+        .merge(annotation_routes(state.clone()))
+        .merge(reading_history_routes(state.clone()))
+        .merge(on_deck_routes(state.clone()))
+        .merge(search_routes(state.clone()))
         .merge(api_routes(state.clone()))
         .fallback(fallback_handler)
         .layer(from_fn(log_request))
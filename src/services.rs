@@ -1,18 +1,58 @@
 pub mod anilist_service;
 mod anilist_service_test;
+pub mod annotation_service;
+mod annotation_service_test;
 pub mod archive_service;
 mod archive_service_test;
 pub mod book_service;
 mod book_service_test;
+pub mod bookmark_service;
+mod bookmark_service_test;
+pub mod cbl_service;
+mod cbl_service_test;
+pub mod collection_service;
+mod collection_service_test;
 pub mod collectionner_service;
 mod collectionner_service_test;
 pub mod converter_service;
 mod converter_service_test;
+pub mod credit_service;
+mod credit_service_test;
+pub mod custom_field_service;
+mod custom_field_service_test;
+pub mod duplicate_service;
+mod duplicate_service_test;
+pub mod facet_service;
+mod facet_service_test;
 pub mod googlebooks_service;
 mod googlebooks_service_test;
+pub mod library_service;
+mod library_service_test;
 pub mod marvel_service;
 mod marvel_service_test;
+pub mod on_deck_service;
+mod on_deck_service_test;
 pub mod openlibrary_service;
 mod openlibrary_service_test;
+pub mod pagination_service;
+mod pagination_service_test;
+pub mod parser_service;
+mod parser_service_test;
 pub mod profile_service;
 mod profile_service_test;
+pub mod reading_history_service;
+mod reading_history_service_test;
+pub mod reading_order_service;
+mod reading_order_service_test;
+pub mod resource_service;
+mod resource_service_test;
+pub mod scheduler_service;
+mod scheduler_service_test;
+pub mod search_service;
+mod search_service_test;
+pub mod smart_collection_service;
+mod smart_collection_service_test;
+pub mod statistics_service;
+mod statistics_service_test;
+pub mod tag_service;
+mod tag_service_test;
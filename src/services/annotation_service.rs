@@ -0,0 +1,272 @@
+use crate::models::annotation_model::Annotation;
+use crate::utils::{VALID_IMAGE_EXTENSION, get_list_of_images};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sqlx::SqlitePool;
+use std::fs;
+use std::path::Path;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnnotationPayload {
+    pub page: i64,
+    pub x: f64,
+    pub y: f64,
+    pub width: f64,
+    pub height: f64,
+    pub note: String,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct AnnotationChanges {
+    pub x: Option<f64>,
+    pub y: Option<f64>,
+    pub width: Option<f64>,
+    pub height: Option<f64>,
+    pub note: Option<String>,
+    pub color: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    Markdown,
+    Json,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ManifestPage {
+    pub index: usize,
+    pub image: String,
+    pub annotations: Vec<Annotation>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PageManifest {
+    pub book_id: Option<String>,
+    pub path: String,
+    pub pages: Vec<ManifestPage>,
+}
+
+fn validate_region(x: f64, y: f64, width: f64, height: f64) -> Result<(), String> {
+    let inside = |start: f64, size: f64| {
+        (0.0..=1.0).contains(&start) && size > 0.0 && start + size <= 1.0 + f64::EPSILON
+    };
+    if inside(x, width) && inside(y, height) {
+        Ok(())
+    } else {
+        Err("Invalid region: coordinates must be normalised between 0 and 1".to_string())
+    }
+}
+
+fn validate_color(color: Option<&str>) -> Result<(), String> {
+    match color {
+        Some(color)
+            if color.len() != 7
+                || !color.starts_with('#')
+                || !color[1..].chars().all(|c| c.is_ascii_hexdigit()) =>
+        {
+            Err(format!("Invalid color: {}", color))
+        }
+        _ => Ok(()),
+    }
+}
+
+fn validate_annotation(payload: &AnnotationPayload) -> Result<(), String> {
+    if payload.page < 0 {
+        return Err(format!("Invalid page: {}", payload.page));
+    }
+    if payload.note.trim().is_empty() {
+        return Err("Note cannot be empty".to_string());
+    }
+    validate_region(payload.x, payload.y, payload.width, payload.height)?;
+    validate_color(payload.color.as_deref())
+}
+
+pub async fn get_annotation(
+    db_pool: &SqlitePool,
+    id: i64,
+) -> Result<Option<Annotation>, sqlx::Error> {
+    sqlx::query_as("SELECT * FROM Annotations WHERE ID_ANNOTATION = ?;")
+        .bind(id)
+        .fetch_optional(db_pool)
+        .await
+}
+
+pub async fn list_book_annotations(
+    db_pool: &SqlitePool,
+    book_id: &str,
+    page: Option<i64>,
+) -> Result<Vec<Annotation>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT * FROM Annotations WHERE BOOK_ID = ?1 AND (?2 IS NULL OR page = ?2) \
+         ORDER BY page, y, x, ID_ANNOTATION;",
+    )
+    .bind(book_id)
+    .bind(page)
+    .fetch_all(db_pool)
+    .await
+}
+
+pub async fn create_annotation(
+    db_pool: &SqlitePool,
+    book_id: &str,
+    payload: &AnnotationPayload,
+) -> Result<Annotation, Box<dyn std::error::Error + Send + Sync>> {
+    validate_annotation(payload)?;
+    let exists: Option<String> = sqlx::query_scalar("SELECT ID_book FROM Books WHERE ID_book = ?;")
+        .bind(book_id)
+        .fetch_optional(db_pool)
+        .await?;
+    if exists.is_none() {
+        return Err(format!("Unknown book: {}", book_id).into());
+    }
+    let now = Utc::now().timestamp();
+    let id = sqlx::query(
+        "INSERT INTO Annotations (BOOK_ID, page, x, y, width, height, note, color, created, updated) \
+         VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?);",
+    )
+    .bind(book_id)
+    .bind(payload.page)
+    .bind(payload.x)
+    .bind(payload.y)
+    .bind(payload.width)
+    .bind(payload.height)
+    .bind(payload.note.trim())
+    .bind(&payload.color)
+    .bind(now)
+    .bind(now)
+    .execute(db_pool)
+    .await?
+    .last_insert_rowid();
+    get_annotation(db_pool, id)
+        .await?
+        .ok_or_else(|| "Annotation was not created".into())
+}
+
+pub async fn update_annotation(
+    db_pool: &SqlitePool,
+    book_id: &str,
+    id: i64,
+    changes: &AnnotationChanges,
+) -> Result<Option<Annotation>, Box<dyn std::error::Error + Send + Sync>> {
+    let Some(annotation) = get_annotation(db_pool, id).await? else {
+        return Ok(None);
+    };
+    if annotation.book_id != book_id {
+        return Ok(None);
+    }
+    let payload = AnnotationPayload {
+        page: annotation.page,
+        x: changes.x.unwrap_or(annotation.x),
+        y: changes.y.unwrap_or(annotation.y),
+        width: changes.width.unwrap_or(annotation.width),
+        height: changes.height.unwrap_or(annotation.height),
+        note: changes.note.clone().unwrap_or(annotation.note),
+        color: changes.color.clone().or(annotation.color),
+    };
+    validate_annotation(&payload)?;
+    sqlx::query(
+        "UPDATE Annotations SET x = ?, y = ?, width = ?, height = ?, note = ?, color = ?, updated = ? \
+         WHERE ID_ANNOTATION = ?;",
+    )
+    .bind(payload.x)
+    .bind(payload.y)
+    .bind(payload.width)
+    .bind(payload.height)
+    .bind(payload.note.trim())
+    .bind(&payload.color)
+    .bind(Utc::now().timestamp())
+    .bind(id)
+    .execute(db_pool)
+    .await?;
+    Ok(get_annotation(db_pool, id).await?)
+}
+
+pub async fn delete_annotation(
+    db_pool: &SqlitePool,
+    book_id: &str,
+    id: i64,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("DELETE FROM Annotations WHERE ID_ANNOTATION = ? AND BOOK_ID = ?;")
+        .bind(id)
+        .bind(book_id)
+        .execute(db_pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn export_annotations(
+    db_pool: &SqlitePool,
+    book_id: &str,
+    format: ExportFormat,
+) -> Result<Option<String>, sqlx::Error> {
+    let name: Option<String> = sqlx::query_scalar("SELECT NOM FROM Books WHERE ID_book = ?;")
+        .bind(book_id)
+        .fetch_optional(db_pool)
+        .await?;
+    let Some(name) = name else {
+        return Ok(None);
+    };
+    let annotations = list_book_annotations(db_pool, book_id, None).await?;
+    if format == ExportFormat::Json {
+        let export = json!({ "book_id": book_id, "name": name, "annotations": annotations });
+        return Ok(Some(export.to_string()));
+    }
+    let mut markdown = format!("# {}\n", name);
+    let mut current_page = None;
+    for annotation in &annotations {
+        if current_page != Some(annotation.page) {
+            markdown.push_str(&format!("\n## Page {}\n\n", annotation.page + 1));
+            current_page = Some(annotation.page);
+        }
+        markdown.push_str(&format!(
+            "- ({:.2}, {:.2}, {:.2} × {:.2}) {}\n",
+            annotation.x,
+            annotation.y,
+            annotation.width,
+            annotation.height,
+            annotation.note.replace('\n', "\n  ")
+        ));
+    }
+    Ok(Some(markdown))
+}
+
+pub async fn get_page_manifest(
+    db_pool: &SqlitePool,
+    current_book: &Path,
+) -> Result<Option<PageManifest>, sqlx::Error> {
+    let Ok(path) = fs::read_to_string(current_book.join("path.txt")) else {
+        return Ok(None);
+    };
+    let path = path.trim().to_string();
+    let book_id: Option<String> = sqlx::query_scalar("SELECT ID_book FROM Books WHERE PATH = ?;")
+        .bind(&path)
+        .fetch_optional(db_pool)
+        .await?;
+    let mut annotations = match &book_id {
+        Some(book_id) => list_book_annotations(db_pool, book_id, None).await?,
+        None => Vec::new(),
+    };
+    let pages = get_list_of_images(current_book, VALID_IMAGE_EXTENSION)
+        .into_iter()
+        .enumerate()
+        .map(|(index, image)| {
+            let (page_annotations, rest) = annotations
+                .drain(..)
+                .partition(|annotation| annotation.page == index as i64);
+            annotations = rest;
+            ManifestPage {
+                index,
+                image,
+                annotations: page_annotations,
+            }
+        })
+        .collect();
+    Ok(Some(PageManifest {
+        book_id,
+        path,
+        pages,
+    }))
+}
@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod tests {
+    use crate::repositories::database_repo::make_test_db;
+    use crate::services::annotation_service::*;
+    use crate::services::resource_service::{Resource, create_resource, delete_resource};
+    use serde_json::{Value, json};
+    use sqlx::SqlitePool;
+    use std::fs;
+    use tempfile::TempDir;
+
+    async fn setup_db() -> (TempDir, SqlitePool) {
+        let (temp, pool) = make_test_db().await;
+        create_resource(
+            &pool,
+            Resource::Books,
+            &json!({"id": "b1", "name": "Saga #1", "path": "/comics/saga1.cbz"}),
+        )
+        .await
+        .unwrap();
+        (temp, pool)
+    }
+
+    fn payload(page: i64, x: f64, y: f64, note: &str) -> AnnotationPayload {
+        AnnotationPayload {
+            page,
+            x,
+            y,
+            width: 0.25,
+            height: 0.1,
+            note: note.to_string(),
+            color: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_annotation_crud_and_validation() {
+        let (_temp, pool) = setup_db().await;
+        let panel = create_annotation(&pool, "b1", &payload(2, 0.5, 0.5, " Panel "))
+            .await
+            .unwrap();
+        create_annotation(&pool, "b1", &payload(0, 0.1, 0.1, "Title"))
+            .await
+            .unwrap();
+        assert_eq!(panel.note, "Panel");
+        assert!(panel.created.is_some());
+
+        for (annotation, book_id, message) in [
+            (payload(-1, 0.0, 0.0, "x"), "b1", "Invalid page: -1"),
+            (payload(0, 0.0, 0.0, " "), "b1", "Note cannot be empty"),
+            (
+                payload(0, 0.9, 0.0, "x"),
+                "b1",
+                "Invalid region: coordinates must be normalised between 0 and 1",
+            ),
+            (
+                AnnotationPayload {
+                    color: Some("red".to_string()),
+                    ..payload(0, 0.0, 0.0, "x")
+                },
+                "b1",
+                "Invalid color: red",
+            ),
+            (payload(0, 0.0, 0.0, "x"), "nope", "Unknown book: nope"),
+        ] {
+            let error = create_annotation(&pool, book_id, &annotation)
+                .await
+                .unwrap_err();
+            assert_eq!(error.to_string(), message);
+        }
+
+        let pages: Vec<i64> = list_book_annotations(&pool, "b1", None)
+            .await
+            .unwrap()
+            .iter()
+            .map(|annotation| annotation.page)
+            .collect();
+        assert_eq!(pages, vec![0, 2]);
+        assert_eq!(
+            list_book_annotations(&pool, "b1", Some(2))
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+
+        let changes = AnnotationChanges {
+            color: Some("#ffcc00".to_string()),
+            ..Default::default()
+        };
+        let updated = update_annotation(&pool, "b1", panel.id, &changes)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.color.as_deref(), Some("#ffcc00"));
+        assert_eq!(updated.x, 0.5);
+        let moved = AnnotationChanges {
+            x: Some(0.8),
+            ..Default::default()
+        };
+        assert!(
+            update_annotation(&pool, "b1", panel.id, &moved)
+                .await
+                .is_err()
+        );
+        assert!(
+            update_annotation(&pool, "b2", panel.id, &changes)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        assert!(!delete_annotation(&pool, "b2", panel.id).await.unwrap());
+        assert!(delete_annotation(&pool, "b1", panel.id).await.unwrap());
+        delete_resource(&pool, Resource::Books, "b1").await.unwrap();
+        let left: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM Annotations;")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(left, 0);
+    }
+
+    #[tokio::test]
+    async fn test_export_and_page_manifest() {
+        let (temp, pool) = setup_db().await;
+        create_annotation(&pool, "b1", &payload(1, 0.0, 0.5, "Second"))
+            .await
+            .unwrap();
+        create_annotation(&pool, "b1", &payload(1, 0.0, 0.0, "First"))
+            .await
+            .unwrap();
+
+        let markdown = export_annotations(&pool, "b1", ExportFormat::Markdown)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(markdown.starts_with("# Saga #1\n\n## Page 2\n"));
+        assert!(markdown.find("First").unwrap() < markdown.find("Second").unwrap());
+        let export: Value = serde_json::from_str(
+            &export_annotations(&pool, "b1", ExportFormat::Json)
+                .await
+                .unwrap()
+                .unwrap(),
+        )
+        .unwrap();
+        assert_eq!(export["annotations"].as_array().unwrap().len(), 2);
+        assert!(
+            export_annotations(&pool, "nope", ExportFormat::Json)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        let current_book = temp.path().join("current_book");
+        assert!(
+            get_page_manifest(&pool, &current_book)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        fs::create_dir_all(&current_book).unwrap();
+        fs::write(current_book.join("path.txt"), "/comics/saga1.cbz\n").unwrap();
+        for page in ["01.jpg", "02.jpg"] {
+            fs::write(current_book.join(page), b"").unwrap();
+        }
+        let manifest = get_page_manifest(&pool, &current_book)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(manifest.book_id.as_deref(), Some("b1"));
+        assert_eq!(manifest.pages.len(), 2);
+        assert!(manifest.pages[0].annotations.is_empty());
+        assert_eq!(manifest.pages[1].annotations.len(), 2);
+    }
+}
@@ -1,11 +1,15 @@
-use crate::{AppGlobalVariables, utils::is_image_file};
+use crate::{
+    AppGlobalVariables,
+    utils::{get_xml_attribute, is_image_file, natural_cmp},
+};
 use futures::executor;
 use headless_chrome::{Browser, LaunchOptionsBuilder};
 use pdfium_render::prelude::*;
+use regex::Regex;
 use serde_json::Value;
 use std::{
     fs::{self, File},
-    io::{self, Write},
+    io::{self, Read, Write},
     os::unix::fs::PermissionsExt,
     path::Path,
     sync::Arc,
@@ -72,9 +76,140 @@ pub async fn extract_first_image(
             extract_first_image_from_zip(zip_path, extract_dir, file_name)
         }
         "rar" | "cbr" => extract_first_image_from_rar(zip_path, extract_dir, file_name),
+        "pdf" => extract_first_image_from_pdf(zip_path, extract_dir, file_name),
+        "epub" | "ebook" => extract_first_image_from_epub(zip_path, extract_dir, file_name),
+        "folder" => extract_first_image_from_folder(zip_path, extract_dir, file_name),
         _ => Err(format!("Unsupported extension: {}", extension).into()),
     }
 }
+
+fn extract_first_image_from_pdf<P: AsRef<Path>>(
+    pdf_path: P,
+    extract_dir: P,
+    file_name: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let pdfium = Pdfium::default();
+    let doc = pdfium.load_pdf_from_file(pdf_path.as_ref(), None)?;
+    let page = doc.pages().get(0)?;
+    let image = page
+        .render_with_config(
+            &PdfRenderConfig::new()
+                .set_target_width(1200)
+                .render_form_data(true),
+        )?
+        .as_image()
+        .into_rgb8();
+
+    let out_path = extract_dir.as_ref().join(format!("{}.jpg", file_name));
+    image.save_with_format(out_path, image::ImageFormat::Jpeg)?;
+    info!("First page rendered from PDF.");
+    Ok(())
+}
+
+fn extract_first_image_from_epub<P: AsRef<Path>>(
+    epub_path: P,
+    extract_dir: P,
+    file_name: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let file = File::open(&epub_path)?;
+    let mut archive = ZipArchive::new(file)?;
+
+    let cover_path = match find_epub_cover_path(&mut archive) {
+        Some(cover_path) => cover_path,
+        None => {
+            info!("No cover declared in the EPUB metadata, using the first image.");
+            return extract_first_image_from_zip(epub_path, extract_dir, file_name);
+        }
+    };
+
+    let mut img_file = archive.by_name(&cover_path)?;
+    let out_path = extract_dir.as_ref().join(format!("{}.jpg", file_name));
+    let mut out_file = File::create(out_path)?;
+    io::copy(&mut img_file, &mut out_file)?;
+    info!("Cover extracted from EPUB: {}", cover_path);
+    Ok(())
+}
+
+fn find_epub_cover_path(archive: &mut ZipArchive<File>) -> Option<String> {
+    let container = read_zip_entry_to_string(archive, "META-INF/container.xml")?;
+    let rootfile_regex = Regex::new(r#"<(?:\w+:)?rootfile\s[^>]*>"#).unwrap();
+    let opf_path = rootfile_regex
+        .find(&container)
+        .and_then(|tag| get_xml_attribute(tag.as_str(), "full-path"))?;
+    let opf = read_zip_entry_to_string(archive, &opf_path)?;
+
+    let item_regex = Regex::new(r#"<(?:\w+:)?item\s[^>]*>"#).unwrap();
+    let items: Vec<&str> = item_regex.find_iter(&opf).map(|m| m.as_str()).collect();
+
+    let mut cover_href = items
+        .iter()
+        .find(|item| {
+            get_xml_attribute(item, "properties")
+                .map(|props| props.split_whitespace().any(|p| p == "cover-image"))
+                .unwrap_or(false)
+        })
+        .and_then(|item| get_xml_attribute(item, "href"));
+
+    if cover_href.is_none() {
+        let meta_regex = Regex::new(r#"<(?:\w+:)?meta\s[^>]*>"#).unwrap();
+        let cover_id = meta_regex
+            .find_iter(&opf)
+            .map(|m| m.as_str())
+            .find(|meta| get_xml_attribute(meta, "name").as_deref() == Some("cover"))
+            .and_then(|meta| get_xml_attribute(meta, "content"));
+        if let Some(cover_id) = cover_id {
+            cover_href = items
+                .iter()
+                .find(|item| get_xml_attribute(item, "id").as_deref() == Some(cover_id.as_str()))
+                .and_then(|item| get_xml_attribute(item, "href"));
+        }
+    }
+
+    let cover_href = urlencoding::decode(&cover_href?).ok()?.into_owned();
+    let mut segments: Vec<&str> = opf_path.split('/').collect();
+    segments.pop();
+    for segment in cover_href.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                segments.pop();
+            }
+            _ => segments.push(segment),
+        }
+    }
+    Some(segments.join("/"))
+}
+
+fn read_zip_entry_to_string(archive: &mut ZipArchive<File>, name: &str) -> Option<String> {
+    let mut entry = archive.by_name(name).ok()?;
+    let mut content = String::new();
+    entry.read_to_string(&mut content).ok()?;
+    Some(content)
+}
+
+fn extract_first_image_from_folder<P: AsRef<Path>>(
+    folder_path: P,
+    extract_dir: P,
+    file_name: &str,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let mut images: Vec<String> = fs::read_dir(&folder_path)?
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().to_str().map(|name| name.to_string()))
+        .filter(|name| is_image_file(name))
+        .collect();
+    images.sort_by(|a, b| natural_cmp(a, b));
+
+    if let Some(first_image) = images.first() {
+        let out_path = extract_dir.as_ref().join(format!("{}.jpg", file_name));
+        fs::copy(folder_path.as_ref().join(first_image), out_path)?;
+        info!("Image copied from folder: {}", first_image);
+        Ok(())
+    } else {
+        error!("No image file found in folder.");
+        Err("No image file found in folder".into())
+    }
+}
 fn extract_first_image_from_zip<P: AsRef<Path>>(
     zip_path: P,
     extract_dir: P,
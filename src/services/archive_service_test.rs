@@ -82,6 +82,82 @@ mod tests {
         assert!(out_dir.join("img.jpg").exists());
     }
 
+    #[tokio::test]
+    async fn test_extract_first_image_from_epub_uses_declared_cover() {
+        let temp = tempdir().unwrap();
+        let epub_path = temp.path().join("test.epub");
+        let out_dir = temp.path().join("out");
+        fs::create_dir_all(&out_dir).unwrap();
+        {
+            let mut zip = zip::ZipWriter::new(File::create(&epub_path).unwrap());
+            let options: zip::write::FileOptions<()> =
+                FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+            zip.start_file("META-INF/container.xml", options).unwrap();
+            zip.write_all(
+                br#"<container><rootfiles><rootfile full-path="OEBPS/content.opf" media-type="application/oebps-package+xml"/></rootfiles></container>"#,
+            )
+            .unwrap();
+            zip.start_file("OEBPS/content.opf", options).unwrap();
+            zip.write_all(
+                br#"<package><metadata><meta name="cover" content="cover-img"/></metadata><manifest><item id="page" href="images/page1.jpg" media-type="image/jpeg"/><item id="cover-img" href="../covers/front.jpg" media-type="image/jpeg"/></manifest></package>"#,
+            )
+            .unwrap();
+            zip.start_file("OEBPS/images/page1.jpg", options).unwrap();
+            zip.write_all(b"firstpage").unwrap();
+            zip.start_file("covers/front.jpg", options).unwrap();
+            zip.write_all(b"coverimage").unwrap();
+            zip.finish().unwrap();
+        }
+
+        let result = extract_first_image(
+            epub_path.to_str().unwrap().to_string(),
+            out_dir.to_str().unwrap().to_string(),
+            "epub",
+            "img",
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(fs::read(out_dir.join("img.jpg")).unwrap(), b"coverimage");
+    }
+
+    #[tokio::test]
+    async fn test_extract_first_image_from_folder_uses_natural_order() {
+        let temp = tempdir().unwrap();
+        let book_dir = temp.path().join("book");
+        let out_dir = temp.path().join("out");
+        fs::create_dir_all(&book_dir).unwrap();
+        fs::create_dir_all(&out_dir).unwrap();
+        fs::write(book_dir.join("page10.jpg"), b"page10").unwrap();
+        fs::write(book_dir.join("page2.jpg"), b"page2").unwrap();
+        fs::write(book_dir.join("notes.txt"), b"notes").unwrap();
+
+        let result = extract_first_image(
+            book_dir.to_str().unwrap().to_string(),
+            out_dir.to_str().unwrap().to_string(),
+            "folder",
+            "img",
+        )
+        .await;
+
+        assert!(result.is_ok());
+        assert_eq!(fs::read(out_dir.join("img.jpg")).unwrap(), b"page2");
+
+        fs::remove_file(out_dir.join("img.jpg")).unwrap();
+        fs::remove_file(book_dir.join("page10.jpg")).unwrap();
+        fs::remove_file(book_dir.join("page2.jpg")).unwrap();
+        let result = extract_first_image(
+            book_dir.to_str().unwrap().to_string(),
+            out_dir.to_str().unwrap().to_string(),
+            "folder",
+            "img",
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(!out_dir.join("img.jpg").exists());
+    }
+
     #[tokio::test]
     async fn test_extract_pdf_from_epub_minimal() {
         let temp = tempdir().unwrap();
@@ -42,10 +42,16 @@ pub async fn fill_blank_images(
         let filename = book["ID_book"].clone();
         let path = book["PATH"].clone();
 
-        if let Some(ext) = path::Path::new(path.clone().as_str())
-            .extension()
-            .and_then(|e| e.to_str())
-        {
+        let book_path = path::Path::new(path.as_str());
+        let ext = if book_path.is_dir() {
+            Some("folder".to_string())
+        } else {
+            book_path
+                .extension()
+                .and_then(|e| e.to_str())
+                .map(|e| e.to_lowercase())
+        };
+        if let Some(ext) = ext.as_deref() {
             let output_path = format!("{}/{}.jpg", output_dir, filename);
 
             fs::create_dir_all(&output_dir.clone())?;
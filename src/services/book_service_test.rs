@@ -14,7 +14,7 @@ mod tests {
             .await
             .unwrap();
 
-        sqlx::query("CREATE TABLE Books (ID_book TEXT, PATH TEXT, NOM TEXT, URLCover TEXT);")
+        sqlx::query("CREATE TABLE Books (ID_book TEXT, PATH TEXT, NOM TEXT, URLCover TEXT, coverPlaceholder TEXT);")
             .execute(&db)
             .await
             .unwrap();
@@ -43,7 +43,7 @@ mod tests {
             .await
             .unwrap();
 
-        sqlx::query("CREATE TABLE Books (ID_book TEXT, PATH TEXT, NOM TEXT, URLCover TEXT);")
+        sqlx::query("CREATE TABLE Books (ID_book TEXT, PATH TEXT, NOM TEXT, URLCover TEXT, coverPlaceholder TEXT);")
             .execute(&db)
             .await
             .unwrap();
@@ -89,7 +89,7 @@ mod tests {
             .await
             .unwrap();
 
-        sqlx::query("CREATE TABLE Books (ID_book TEXT, PATH TEXT, NOM TEXT, URLCover TEXT);")
+        sqlx::query("CREATE TABLE Books (ID_book TEXT, PATH TEXT, NOM TEXT, URLCover TEXT, coverPlaceholder TEXT);")
             .execute(&db)
             .await
             .unwrap();
@@ -0,0 +1,217 @@
+use crate::AppGlobalVariables;
+use crate::models::bookmark_model::Bookmark;
+use crate::services::archive_service::unzip_and_process;
+use crate::services::converter_service::encode_to_webp;
+use crate::utils::{VALID_BOOK_EXTENSION, VALID_IMAGE_EXTENSION, get_list_of_images};
+use chrono::Utc;
+use image::ImageReader;
+use image::imageops::FilterType;
+use serde::{Deserialize, Serialize};
+use sqlx::{QueryBuilder, Sqlite, SqlitePool};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+pub const THUMBNAIL_WIDTH: u32 = 200;
+
+#[derive(Debug, Deserialize)]
+pub struct NewBookmark {
+    pub book_id: String,
+    pub page: i64,
+    pub name: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct BookmarkChanges {
+    pub name: Option<String>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BookmarkJump {
+    pub bookmark: Bookmark,
+    pub image: Option<String>,
+}
+
+fn clean_text(value: &Option<String>) -> Option<String> {
+    value
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+}
+
+pub async fn get_bookmark(db_pool: &SqlitePool, id: i64) -> Result<Option<Bookmark>, sqlx::Error> {
+    sqlx::query_as("SELECT * FROM Bookmarks WHERE ID_BOOKMARK = ?;")
+        .bind(id)
+        .fetch_optional(db_pool)
+        .await
+}
+
+pub async fn list_book_bookmarks(
+    db_pool: &SqlitePool,
+    book_id: &str,
+) -> Result<Vec<Bookmark>, sqlx::Error> {
+    sqlx::query_as("SELECT * FROM Bookmarks WHERE BOOK_ID = ? ORDER BY page, ID_BOOKMARK;")
+        .bind(book_id)
+        .fetch_all(db_pool)
+        .await
+}
+
+pub async fn create_bookmark(
+    db_pool: &SqlitePool,
+    bookmark: &NewBookmark,
+) -> Result<Bookmark, Box<dyn std::error::Error + Send + Sync>> {
+    if bookmark.page < 0 {
+        return Err(format!("Invalid page: {}", bookmark.page).into());
+    }
+    let path: Option<String> = sqlx::query_scalar("SELECT PATH FROM Books WHERE ID_book = ?;")
+        .bind(&bookmark.book_id)
+        .fetch_optional(db_pool)
+        .await?;
+    let path = path.ok_or_else(|| format!("Unknown book: {}", bookmark.book_id))?;
+    let id = sqlx::query(
+        "INSERT INTO Bookmarks (BOOK_ID, PATH, page, name, note, created) VALUES (?, ?, ?, ?, ?, ?);",
+    )
+    .bind(&bookmark.book_id)
+    .bind(path)
+    .bind(bookmark.page)
+    .bind(clean_text(&bookmark.name))
+    .bind(clean_text(&bookmark.note))
+    .bind(Utc::now().timestamp())
+    .execute(db_pool)
+    .await?
+    .last_insert_rowid();
+    get_bookmark(db_pool, id)
+        .await?
+        .ok_or_else(|| "Bookmark was not created".into())
+}
+
+pub async fn update_bookmark(
+    db_pool: &SqlitePool,
+    id: i64,
+    changes: &BookmarkChanges,
+) -> Result<Option<Bookmark>, Box<dyn std::error::Error + Send + Sync>> {
+    if changes.name.is_none() && changes.note.is_none() {
+        return Err("No fields to update".into());
+    }
+    let mut qb = QueryBuilder::<Sqlite>::new("UPDATE Bookmarks SET ");
+    let mut fields = qb.separated(", ");
+    for (column, value) in [("name", &changes.name), ("note", &changes.note)] {
+        if value.is_some() {
+            fields
+                .push(format!("{} = ", column))
+                .push_bind_unseparated(clean_text(value));
+        }
+    }
+    qb.push(" WHERE ID_BOOKMARK = ").push_bind(id);
+    if qb.build().execute(db_pool).await?.rows_affected() == 0 {
+        return Ok(None);
+    }
+    Ok(get_bookmark(db_pool, id).await?)
+}
+
+pub async fn delete_bookmark(
+    db_pool: &SqlitePool,
+    id: i64,
+) -> Result<Option<Bookmark>, sqlx::Error> {
+    let Some(bookmark) = get_bookmark(db_pool, id).await? else {
+        return Ok(None);
+    };
+    sqlx::query("DELETE FROM Bookmarks WHERE ID_BOOKMARK = ?;")
+        .bind(id)
+        .execute(db_pool)
+        .await?;
+    if let Some(thumbnail) = &bookmark.thumbnail {
+        let _ = fs::remove_file(thumbnail);
+    }
+    Ok(Some(bookmark))
+}
+
+pub fn get_current_book_page(current_book: &Path, book_path: &str, page: i64) -> Option<PathBuf> {
+    let opened = fs::read_to_string(current_book.join("path.txt")).ok()?;
+    if opened.trim() != book_path || page < 0 {
+        return None;
+    }
+    get_list_of_images(current_book, VALID_IMAGE_EXTENSION)
+        .get(page as usize)
+        .map(|image| current_book.join(image))
+}
+
+pub fn build_page_thumbnail(
+    source: &Path,
+    output_path: &Path,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let image = ImageReader::open(source)?.with_guessed_format()?.decode()?;
+    let thumbnail = image.resize(THUMBNAIL_WIDTH, u32::MAX, FilterType::Triangle);
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    encode_to_webp(&thumbnail, output_path).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub async fn refresh_bookmark_thumbnail(
+    db_pool: &SqlitePool,
+    bookmark: &Bookmark,
+    current_book: &Path,
+    output_dir: &Path,
+) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+    let Some(source) = get_current_book_page(current_book, &bookmark.path, bookmark.page) else {
+        return Ok(None);
+    };
+    let output_path = output_dir.join(format!("{}.webp", bookmark.id));
+    let thumbnail_path = output_path.clone();
+    tokio::task::spawn_blocking(move || build_page_thumbnail(&source, &thumbnail_path)).await??;
+    let thumbnail = output_path.to_string_lossy().to_string();
+    sqlx::query("UPDATE Bookmarks SET thumbnail = ? WHERE ID_BOOKMARK = ?;")
+        .bind(&thumbnail)
+        .bind(bookmark.id)
+        .execute(db_pool)
+        .await?;
+    Ok(Some(thumbnail))
+}
+
+pub async fn jump_to_bookmark(
+    db_pool: &SqlitePool,
+    id: i64,
+    current_book: &Path,
+    output_dir: &Path,
+    token: String,
+    progress_status: &Arc<Mutex<AppGlobalVariables>>,
+) -> Result<Option<BookmarkJump>, Box<dyn std::error::Error + Send + Sync>> {
+    let Some(mut bookmark) = get_bookmark(db_pool, id).await? else {
+        return Ok(None);
+    };
+    if get_current_book_page(current_book, &bookmark.path, 0).is_none() {
+        let ext = Path::new(&bookmark.path)
+            .extension()
+            .and_then(|e| e.to_str())
+            .unwrap_or_default();
+        if !VALID_BOOK_EXTENSION.contains(&ext) {
+            return Err(format!("Unsupported extension: {}", ext).into());
+        }
+        unzip_and_process(
+            &bookmark.path,
+            &current_book.to_string_lossy(),
+            ext,
+            token,
+            progress_status,
+        )
+        .await?;
+    }
+    sqlx::query("UPDATE Books SET last_page = ? WHERE ID_book = ?;")
+        .bind(bookmark.page)
+        .bind(&bookmark.book_id)
+        .execute(db_pool)
+        .await?;
+    if bookmark.thumbnail.is_none() {
+        bookmark.thumbnail =
+            refresh_bookmark_thumbnail(db_pool, &bookmark, current_book, output_dir).await?;
+    }
+    let image = get_current_book_page(current_book, &bookmark.path, bookmark.page)
+        .map(|image| image.to_string_lossy().to_string());
+    Ok(Some(BookmarkJump { bookmark, image }))
+}
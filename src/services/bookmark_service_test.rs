@@ -0,0 +1,147 @@
+#[cfg(test)]
+mod tests {
+    use crate::AppGlobalVariables;
+    use crate::repositories::database_repo::make_test_db;
+    use crate::services::bookmark_service::*;
+    use crate::services::resource_service::{Resource, create_resource};
+    use image::{ImageReader, RgbImage};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::fs;
+    use std::sync::Arc;
+    use tempfile::TempDir;
+    use tokio::sync::Mutex;
+
+    async fn setup_db() -> (TempDir, SqlitePool) {
+        let (temp, pool) = make_test_db().await;
+        create_resource(
+            &pool,
+            Resource::Books,
+            &json!({"id": "b1", "name": "Saga #1", "path": "/comics/saga1.cbz"}),
+        )
+        .await
+        .unwrap();
+        (temp, pool)
+    }
+
+    fn new_bookmark(page: i64, name: Option<&str>) -> NewBookmark {
+        NewBookmark {
+            book_id: "b1".to_string(),
+            page,
+            name: name.map(str::to_string),
+            note: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_bookmark_crud() {
+        let (_temp, pool) = setup_db().await;
+        let later = create_bookmark(&pool, &new_bookmark(12, Some("Cliffhanger")))
+            .await
+            .unwrap();
+        let first = create_bookmark(&pool, &new_bookmark(3, Some("  ")))
+            .await
+            .unwrap();
+        assert_eq!(first.path, "/comics/saga1.cbz");
+        assert_eq!(first.name, None);
+        assert!(first.created.is_some());
+
+        for (bookmark, message) in [
+            (new_bookmark(-1, None), "Invalid page: -1"),
+            (
+                NewBookmark {
+                    book_id: "nope".to_string(),
+                    ..new_bookmark(1, None)
+                },
+                "Unknown book: nope",
+            ),
+        ] {
+            let error = create_bookmark(&pool, &bookmark).await.unwrap_err();
+            assert_eq!(error.to_string(), message);
+        }
+
+        let pages: Vec<i64> = list_book_bookmarks(&pool, "b1")
+            .await
+            .unwrap()
+            .iter()
+            .map(|bookmark| bookmark.page)
+            .collect();
+        assert_eq!(pages, vec![3, 12]);
+
+        let changes = BookmarkChanges {
+            name: None,
+            note: Some("Check the splash page".to_string()),
+        };
+        let updated = update_bookmark(&pool, later.id, &changes)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated.name.as_deref(), Some("Cliffhanger"));
+        assert_eq!(updated.note.as_deref(), Some("Check the splash page"));
+        assert!(
+            update_bookmark(&pool, later.id, &BookmarkChanges::default())
+                .await
+                .is_err()
+        );
+        assert!(
+            update_bookmark(&pool, 999, &changes)
+                .await
+                .unwrap()
+                .is_none()
+        );
+
+        assert!(delete_bookmark(&pool, first.id).await.unwrap().is_some());
+        assert!(delete_bookmark(&pool, first.id).await.unwrap().is_none());
+        assert_eq!(list_book_bookmarks(&pool, "b1").await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_jump_builds_thumbnail_from_opened_book() {
+        let (temp, pool) = setup_db().await;
+        let current_book = temp.path().join("current_book");
+        let output_dir = temp.path().join("bookmarks");
+        fs::create_dir_all(&current_book).unwrap();
+        fs::write(current_book.join("path.txt"), "/comics/saga1.cbz\n").unwrap();
+        RgbImage::new(400, 600)
+            .save(current_book.join("page.png"))
+            .unwrap();
+
+        let bookmark = create_bookmark(&pool, &new_bookmark(0, None))
+            .await
+            .unwrap();
+        assert!(get_current_book_page(&current_book, "/comics/other.cbz", 0).is_none());
+        assert!(get_current_book_page(&current_book, "/comics/saga1.cbz", 1).is_none());
+
+        let progress = Arc::new(Mutex::new(AppGlobalVariables::new()));
+        let jump = jump_to_bookmark(
+            &pool,
+            bookmark.id,
+            &current_book,
+            &output_dir,
+            "token".to_string(),
+            &progress,
+        )
+        .await
+        .unwrap()
+        .unwrap();
+
+        let thumbnail = jump.bookmark.thumbnail.unwrap();
+        let size = ImageReader::open(&thumbnail)
+            .unwrap()
+            .with_guessed_format()
+            .unwrap()
+            .into_dimensions()
+            .unwrap();
+        assert_eq!(size, (THUMBNAIL_WIDTH, 300));
+        assert!(jump.image.unwrap().ends_with("page.png"));
+        let last_page: i64 =
+            sqlx::query_scalar("SELECT last_page FROM Books WHERE ID_book = 'b1';")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(last_page, 0);
+
+        delete_bookmark(&pool, bookmark.id).await.unwrap();
+        assert!(!std::path::Path::new(&thumbnail).exists());
+    }
+}
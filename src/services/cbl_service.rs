@@ -0,0 +1,300 @@
+use crate::services::parser_service::{normalize_title, parse_book_path};
+use crate::services::reading_order_service::{CblReference, ENTRY_QUERY, row_to_entry};
+use crate::utils::get_xml_attribute;
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use sqlx::{Row, SqlitePool};
+use std::path::Path;
+use std::sync::LazyLock;
+
+pub const MIN_MATCH_CONFIDENCE: f64 = 0.6;
+
+static LIST_NAME: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"(?s)<Name>\s*(.*?)\s*</Name>").unwrap());
+static BOOK_TAG: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"<Book\s[^>]*>").unwrap());
+
+#[derive(Debug, PartialEq)]
+pub struct CblList {
+    pub name: String,
+    pub books: Vec<CblReference>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnmatchedEntry {
+    pub entry_id: i64,
+    pub position: i64,
+    #[serde(flatten)]
+    pub reference: CblReference,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CblReport {
+    pub order_id: i64,
+    pub total: usize,
+    pub matched: usize,
+    pub unmatched: Vec<UnmatchedEntry>,
+}
+
+#[derive(Debug)]
+struct Candidate {
+    id: String,
+    series: Vec<String>,
+    number: Option<f32>,
+    year: Option<u16>,
+}
+
+fn unescape_xml(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+fn get_attribute(tag: &str, attribute: &str) -> Option<String> {
+    get_xml_attribute(tag, attribute)
+        .map(|value| unescape_xml(value.trim()))
+        .filter(|value| !value.is_empty())
+}
+
+pub fn parse_cbl(content: &str) -> Result<CblList, String> {
+    if !content.contains("<ReadingList") {
+        return Err("Not a ComicRack reading list".to_string());
+    }
+    let name = LIST_NAME
+        .captures(content)
+        .map(|caps| unescape_xml(&caps[1]))
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| "Imported reading list".to_string());
+    let books: Vec<CblReference> = BOOK_TAG
+        .find_iter(content)
+        .filter_map(|tag| {
+            let tag = tag.as_str();
+            Some(CblReference {
+                series: get_attribute(tag, "Series")?,
+                number: get_attribute(tag, "Number"),
+                volume: get_attribute(tag, "Volume"),
+                year: get_attribute(tag, "Year").and_then(|year| year.parse().ok()),
+            })
+        })
+        .collect();
+    if books.is_empty() {
+        return Err("Reading list contains no books".to_string());
+    }
+    Ok(CblList { name, books })
+}
+
+fn get_series_keys(title: &str) -> Vec<String> {
+    match serde_json::from_str::<Value>(title) {
+        Ok(Value::Object(titles)) => titles
+            .values()
+            .filter_map(|title| title.as_str())
+            .map(normalize_title)
+            .collect(),
+        _ => vec![normalize_title(title)],
+    }
+}
+
+async fn load_candidates(db_pool: &SqlitePool) -> Result<Vec<Candidate>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT Books.ID_book, Books.PATH, Books.issueNumber, Series.title FROM Books \
+         LEFT JOIN Series ON Series.ID_Series = Books.ID_Series \
+         WHERE NOT Books.missing ORDER BY Books.ID_book;",
+    )
+    .fetch_all(db_pool)
+    .await?;
+    let mut candidates = Vec::new();
+    for row in rows {
+        let path: String = row.try_get("PATH")?;
+        let parsed = parse_book_path(Path::new(&path));
+        let mut series = vec![normalize_title(&parsed.series)];
+        if let Some(title) = row.try_get::<Option<String>, _>("title")? {
+            series.extend(get_series_keys(&title));
+        }
+        series.retain(|key| !key.is_empty());
+        let issue_number: Option<i64> = row.try_get("issueNumber").ok().flatten();
+        candidates.push(Candidate {
+            id: row.try_get("ID_book")?,
+            series,
+            number: parsed.issue.or(issue_number.map(|n| n as f32)),
+            year: parsed.year,
+        });
+    }
+    Ok(candidates)
+}
+
+fn score_candidate(reference: &CblReference, candidate: &Candidate) -> f64 {
+    let series = normalize_title(&reference.series);
+    if series.is_empty() || !candidate.series.contains(&series) {
+        return 0.0;
+    }
+    let mut score = 0.6;
+    let number = reference
+        .number
+        .as_deref()
+        .and_then(|number| number.parse::<f32>().ok());
+    match (number, candidate.number) {
+        (Some(expected), Some(actual)) if expected == actual => score += 0.3,
+        (Some(_), Some(_)) => return 0.0,
+        (Some(_), None) => score -= 0.15,
+        (None, _) => {}
+    }
+    let years: Vec<u16> = reference
+        .year
+        .into_iter()
+        .chain(reference.volume.as_deref().and_then(|v| v.parse().ok()))
+        .filter(|year| (1900..2100).contains(year))
+        .collect();
+    if let Some(year) = candidate.year
+        && !years.is_empty()
+    {
+        score += if years.contains(&year) { 0.1 } else { -0.2 };
+    }
+    score
+}
+
+fn find_best_match(reference: &CblReference, candidates: &[Candidate]) -> Option<(String, f64)> {
+    let mut best: Option<(&Candidate, f64)> = None;
+    let mut ties = 0;
+    for candidate in candidates {
+        let score = score_candidate(reference, candidate);
+        match best {
+            Some((_, best_score)) if score == best_score => ties += 1,
+            Some((_, best_score)) if score < best_score => {}
+            _ => {
+                best = Some((candidate, score));
+                ties = 0;
+            }
+        }
+    }
+    let (candidate, score) = best?;
+    let confidence = if ties > 0 { score - 0.1 } else { score };
+    (confidence >= MIN_MATCH_CONFIDENCE).then(|| {
+        (
+            candidate.id.clone(),
+            (confidence.min(1.0) * 100.0).round() / 100.0,
+        )
+    })
+}
+
+pub async fn import_cbl(db_pool: &SqlitePool, list: &CblList) -> Result<CblReport, sqlx::Error> {
+    let candidates = load_candidates(db_pool).await?;
+    let mut tx = db_pool.begin().await?;
+    let order_id = sqlx::query("INSERT INTO ReadingOrders (NAME) VALUES (?);")
+        .bind(&list.name)
+        .execute(&mut *tx)
+        .await?
+        .last_insert_rowid();
+    for (position, reference) in list.books.iter().enumerate() {
+        let matched = find_best_match(reference, &candidates);
+        sqlx::query(
+            "INSERT INTO ReadingOrderBooks (ID_READING_ORDER, ID_book, position, cblSeries, cblNumber, cblVolume, cblYear, confidence) \
+             VALUES (?, ?, ?, ?, ?, ?, ?, ?);",
+        )
+        .bind(order_id)
+        .bind(matched.as_ref().map(|(id, _)| id))
+        .bind(position as i64)
+        .bind(&reference.series)
+        .bind(&reference.number)
+        .bind(&reference.volume)
+        .bind(reference.year)
+        .bind(matched.as_ref().map(|(_, confidence)| confidence))
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+    get_cbl_report(db_pool, order_id).await
+}
+
+pub async fn get_cbl_report(db_pool: &SqlitePool, order_id: i64) -> Result<CblReport, sqlx::Error> {
+    let entries = sqlx::query(&format!(
+        "{} WHERE ReadingOrderBooks.ID_READING_ORDER = ? ORDER BY ReadingOrderBooks.position;",
+        ENTRY_QUERY
+    ))
+    .bind(order_id)
+    .fetch_all(db_pool)
+    .await?
+    .iter()
+    .map(row_to_entry)
+    .collect::<Result<Vec<_>, _>>()?;
+    let matched = entries.iter().filter(|e| e.book_id.is_some()).count();
+    let unmatched = entries
+        .iter()
+        .filter(|entry| entry.book_id.is_none())
+        .filter_map(|entry| {
+            Some(UnmatchedEntry {
+                entry_id: entry.id,
+                position: entry.position,
+                reference: entry.reference.clone()?,
+            })
+        })
+        .collect();
+    Ok(CblReport {
+        order_id,
+        total: entries.len(),
+        matched,
+        unmatched,
+    })
+}
+
+pub async fn rematch_reading_order(
+    db_pool: &SqlitePool,
+    order_id: i64,
+) -> Result<CblReport, sqlx::Error> {
+    let candidates = load_candidates(db_pool).await?;
+    let report = get_cbl_report(db_pool, order_id).await?;
+    let mut tx = db_pool.begin().await?;
+    for entry in &report.unmatched {
+        let Some((book_id, confidence)) = find_best_match(&entry.reference, &candidates) else {
+            continue;
+        };
+        sqlx::query("UPDATE ReadingOrderBooks SET ID_book = ?, confidence = ? WHERE ID_ENTRY = ?;")
+            .bind(book_id)
+            .bind(confidence)
+            .bind(entry.entry_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    get_cbl_report(db_pool, order_id).await
+}
+
+pub async fn set_entry_book(
+    db_pool: &SqlitePool,
+    order_id: i64,
+    entry_id: i64,
+    book_id: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE ReadingOrderBooks SET ID_book = ?, confidence = 1.0 WHERE ID_ENTRY = ? AND ID_READING_ORDER = ?;",
+    )
+    .bind(book_id)
+    .bind(entry_id)
+    .bind(order_id)
+    .execute(db_pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn set_entry_reference(
+    db_pool: &SqlitePool,
+    order_id: i64,
+    entry_id: i64,
+    reference: &CblReference,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE ReadingOrderBooks SET cblSeries = ?, cblNumber = ?, cblVolume = ?, cblYear = ?, ID_book = NULL, confidence = NULL \
+         WHERE ID_ENTRY = ? AND ID_READING_ORDER = ?;",
+    )
+    .bind(&reference.series)
+    .bind(&reference.number)
+    .bind(&reference.volume)
+    .bind(reference.year)
+    .bind(entry_id)
+    .bind(order_id)
+    .execute(db_pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
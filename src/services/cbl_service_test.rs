@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use crate::repositories::database_repo::make_test_db;
+    use crate::services::cbl_service::*;
+    use crate::services::reading_order_service::{CblReference, get_reading_order};
+    use sqlx::SqlitePool;
+    use tempfile::TempDir;
+
+    const CIVIL_WAR_CBL: &str = r#"<?xml version="1.0"?>
+<ReadingList xmlns:xsd="http://www.w3.org/2001/XMLSchema">
+  <Name>Civil War &amp; Tie-ins</Name>
+  <Books>
+    <Book Series="Civil War" Number="1" Volume="2006" Year="2006">
+      <Id>0b4d8f5e-1111-4d0c-9a3e-000000000001</Id>
+    </Book>
+    <Book Series="Amazing Spider-Man" Number="532" Volume="1963" Year="2006" />
+    <Book Series="Civil War" Number="2" Volume="2006" Year="2006" />
+    <Book Series="Frontline" Number="1" Volume="2006" Year="2006" />
+  </Books>
+</ReadingList>"#;
+
+    async fn setup_db(books: &[(&str, &str)]) -> (TempDir, SqlitePool) {
+        let (temp, pool) = make_test_db().await;
+        for (id, path) in books {
+            sqlx::query(
+                "INSERT INTO Books (ID_book, NOM, read, reading, unread, favorite, last_page, folder, PATH) \
+                 VALUES (?, ?, false, false, true, false, 0, false, ?);",
+            )
+            .bind(id)
+            .bind(id)
+            .bind(path)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        (temp, pool)
+    }
+
+    #[test]
+    fn test_parse_cbl_reads_name_and_books() {
+        let list = parse_cbl(CIVIL_WAR_CBL).unwrap();
+
+        assert_eq!(list.name, "Civil War & Tie-ins");
+        assert_eq!(list.books.len(), 4);
+        assert_eq!(
+            list.books[1],
+            CblReference {
+                series: "Amazing Spider-Man".to_string(),
+                number: Some("532".to_string()),
+                volume: Some("1963".to_string()),
+                year: Some(2006),
+            }
+        );
+        assert!(parse_cbl("<ComicInfo></ComicInfo>").is_err());
+        assert!(parse_cbl("<ReadingList><Books></Books></ReadingList>").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_import_cbl_matches_books_and_reports_unmatched() {
+        let (_temp, pool) = setup_db(&[
+            ("cw1", "/comics/Civil War/Civil War 001 (2006).cbz"),
+            ("cw2", "/comics/Civil War/Civil War 002 (2006).cbz"),
+            ("cw3", "/comics/Civil War/Civil War 003 (2006).cbz"),
+            ("asm", "/comics/Amazing Spider-Man 532.cbr"),
+        ])
+        .await;
+
+        let report = import_cbl(&pool, &parse_cbl(CIVIL_WAR_CBL).unwrap())
+            .await
+            .unwrap();
+
+        assert_eq!(report.total, 4);
+        assert_eq!(report.matched, 3);
+        assert_eq!(report.unmatched.len(), 1);
+        assert_eq!(report.unmatched[0].position, 3);
+        assert_eq!(report.unmatched[0].reference.series, "Frontline");
+
+        let detail = get_reading_order(&pool, report.order_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(detail.order.name, "Civil War & Tie-ins");
+        let ids: Vec<Option<&str>> = detail
+            .entries
+            .iter()
+            .map(|entry| entry.book_id.as_deref())
+            .collect();
+        assert_eq!(ids, vec![Some("cw1"), Some("asm"), Some("cw2"), None]);
+        assert_eq!(detail.entries[0].confidence, Some(1.0));
+        assert_eq!(detail.entries[1].confidence, Some(0.9));
+    }
+
+    #[tokio::test]
+    async fn test_rematch_after_manual_fixes() {
+        let (_temp, pool) = setup_db(&[
+            ("cw1", "/comics/Civil War/Civil War 001.cbz"),
+            (
+                "fl1",
+                "/comics/Civil War Front Line/Civil War Front Line 01.cbz",
+            ),
+        ])
+        .await;
+        let list = parse_cbl(CIVIL_WAR_CBL).unwrap();
+        let report = import_cbl(&pool, &list).await.unwrap();
+        assert_eq!(report.matched, 1);
+
+        let frontline = report
+            .unmatched
+            .iter()
+            .find(|entry| entry.reference.series == "Frontline")
+            .unwrap();
+        let reference = CblReference {
+            series: "Civil War Front Line".to_string(),
+            ..frontline.reference.clone()
+        };
+        assert!(
+            set_entry_reference(&pool, report.order_id, frontline.entry_id, &reference)
+                .await
+                .unwrap()
+        );
+        let asm = report.unmatched[0].entry_id;
+        assert!(
+            set_entry_book(&pool, report.order_id, asm, "cw1")
+                .await
+                .unwrap()
+        );
+
+        let report = rematch_reading_order(&pool, report.order_id).await.unwrap();
+        assert_eq!(report.matched, 3);
+        assert_eq!(report.unmatched.len(), 1);
+        assert_eq!(report.unmatched[0].reference.number.as_deref(), Some("2"));
+    }
+}
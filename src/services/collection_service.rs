@@ -0,0 +1,479 @@
+use crate::services::converter_service::encode_to_webp;
+use crate::services::smart_collection_service::{
+    SmartItem, SmartQuery, SmartRules, evaluate_smart_rules, get_smart_rules,
+};
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageReader, RgbImage};
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqlitePool};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const MOSAIC_TILES: usize = 4;
+const MOSAIC_WIDTH: u32 = 480;
+const MOSAIC_HEIGHT: u32 = 720;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Collection {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub cover: Option<String>,
+    pub position: i64,
+    pub books: i64,
+    pub series: i64,
+    pub rules: Option<SmartRules>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CollectionItem {
+    pub id: i64,
+    pub position: i64,
+    pub item_type: String,
+    pub item_id: String,
+    pub name: Option<String>,
+    pub cover: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CollectionDetail {
+    #[serde(flatten)]
+    pub collection: Collection,
+    pub items: Vec<CollectionItem>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemType {
+    Book,
+    Series,
+}
+
+impl ItemType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ItemType::Book => "book",
+            ItemType::Series => "series",
+        }
+    }
+}
+
+const COLLECTION_QUERY: &str = "SELECT Collections.ID_COLLECTION, Collections.NAME, Collections.description, \
+     Collections.cover, Collections.position, Collections.rules, \
+     COUNT(CASE WHEN CollectionItems.itemType = 'book' THEN 1 END) AS book_count, \
+     COUNT(CASE WHEN CollectionItems.itemType = 'series' THEN 1 END) AS series_count \
+     FROM Collections \
+     LEFT JOIN CollectionItems ON CollectionItems.ID_COLLECTION = Collections.ID_COLLECTION";
+
+fn row_to_collection(row: &SqliteRow) -> Result<Collection, sqlx::Error> {
+    Ok(Collection {
+        id: row.try_get("ID_COLLECTION")?,
+        name: row.try_get("NAME")?,
+        description: row.try_get("description")?,
+        cover: row.try_get("cover")?,
+        position: row.try_get("position")?,
+        books: row.try_get("book_count")?,
+        series: row.try_get("series_count")?,
+        rules: row
+            .try_get::<Option<String>, _>("rules")?
+            .and_then(|rules| serde_json::from_str(&rules).ok()),
+    })
+}
+
+pub async fn list_collections(db_pool: &SqlitePool) -> Result<Vec<Collection>, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "{} GROUP BY Collections.ID_COLLECTION ORDER BY Collections.position;",
+        COLLECTION_QUERY
+    ))
+    .fetch_all(db_pool)
+    .await?;
+    rows.iter().map(row_to_collection).collect()
+}
+
+pub async fn create_collection(
+    db_pool: &SqlitePool,
+    name: &str,
+    description: Option<&str>,
+) -> Result<i64, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT INTO Collections (NAME, description, position) \
+         VALUES (?, ?, (SELECT COUNT(*) FROM Collections));",
+    )
+    .bind(name)
+    .bind(description)
+    .execute(db_pool)
+    .await?;
+    Ok(result.last_insert_rowid())
+}
+
+pub async fn update_collection(
+    db_pool: &SqlitePool,
+    collection_id: i64,
+    name: &str,
+    description: Option<&str>,
+) -> Result<bool, sqlx::Error> {
+    let result =
+        sqlx::query("UPDATE Collections SET NAME = ?, description = ? WHERE ID_COLLECTION = ?;")
+            .bind(name)
+            .bind(description)
+            .bind(collection_id)
+            .execute(db_pool)
+            .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn delete_collection(
+    db_pool: &SqlitePool,
+    collection_id: i64,
+) -> Result<bool, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    sqlx::query("DELETE FROM CollectionItems WHERE ID_COLLECTION = ?;")
+        .bind(collection_id)
+        .execute(&mut *tx)
+        .await?;
+    let result = sqlx::query("DELETE FROM Collections WHERE ID_COLLECTION = ?;")
+        .bind(collection_id)
+        .execute(&mut *tx)
+        .await?;
+    let remaining: Vec<(i64,)> =
+        sqlx::query_as("SELECT ID_COLLECTION FROM Collections ORDER BY position;")
+            .fetch_all(&mut *tx)
+            .await?;
+    for (position, (id,)) in remaining.iter().enumerate() {
+        sqlx::query("UPDATE Collections SET position = ? WHERE ID_COLLECTION = ?;")
+            .bind(position as i64)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn reorder_collections(
+    db_pool: &SqlitePool,
+    collection_ids: &[i64],
+) -> Result<bool, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    let existing: HashSet<i64> =
+        sqlx::query_as::<_, (i64,)>("SELECT ID_COLLECTION FROM Collections;")
+            .fetch_all(&mut *tx)
+            .await?
+            .into_iter()
+            .map(|(id,)| id)
+            .collect();
+    let requested: HashSet<i64> = collection_ids.iter().copied().collect();
+    if existing != requested || requested.len() != collection_ids.len() {
+        return Ok(false);
+    }
+    for (position, id) in collection_ids.iter().enumerate() {
+        sqlx::query("UPDATE Collections SET position = ? WHERE ID_COLLECTION = ?;")
+            .bind(position as i64)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(true)
+}
+
+pub async fn collection_exists(
+    db_pool: &SqlitePool,
+    collection_id: i64,
+) -> Result<bool, sqlx::Error> {
+    let (exists,): (bool,) =
+        sqlx::query_as("SELECT EXISTS(SELECT 1 FROM Collections WHERE ID_COLLECTION = ?);")
+            .bind(collection_id)
+            .fetch_one(db_pool)
+            .await?;
+    Ok(exists)
+}
+
+pub async fn get_collection(
+    db_pool: &SqlitePool,
+    collection_id: i64,
+) -> Result<Option<CollectionDetail>, sqlx::Error> {
+    let Some(row) = sqlx::query(&format!(
+        "{} WHERE Collections.ID_COLLECTION = ? GROUP BY Collections.ID_COLLECTION;",
+        COLLECTION_QUERY
+    ))
+    .bind(collection_id)
+    .fetch_optional(db_pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+    let collection = row_to_collection(&row)?;
+    let items = sqlx::query(
+        "SELECT CollectionItems.ID_COLLECTION_ITEM, CollectionItems.position, CollectionItems.itemType, CollectionItems.ID_item, \
+         COALESCE(Books.NOM, Series.title) AS name, COALESCE(Books.URLCover, Series.cover) AS cover \
+         FROM CollectionItems \
+         LEFT JOIN Books ON CollectionItems.itemType = 'book' AND Books.ID_book = CollectionItems.ID_item \
+         LEFT JOIN Series ON CollectionItems.itemType = 'series' AND Series.ID_Series = CollectionItems.ID_item \
+         WHERE CollectionItems.ID_COLLECTION = ? ORDER BY CollectionItems.position;",
+    )
+    .bind(collection_id)
+    .fetch_all(db_pool)
+    .await?
+    .iter()
+    .map(|row| {
+        Ok(CollectionItem {
+            id: row.try_get("ID_COLLECTION_ITEM")?,
+            position: row.try_get("position")?,
+            item_type: row.try_get("itemType")?,
+            item_id: row.try_get("ID_item")?,
+            name: row.try_get("name")?,
+            cover: row.try_get("cover")?,
+        })
+    })
+    .collect::<Result<Vec<_>, sqlx::Error>>()?;
+    Ok(Some(CollectionDetail { collection, items }))
+}
+
+async fn get_item_ids(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    collection_id: i64,
+) -> Result<Vec<i64>, sqlx::Error> {
+    let rows: Vec<(i64,)> = sqlx::query_as(
+        "SELECT ID_COLLECTION_ITEM FROM CollectionItems WHERE ID_COLLECTION = ? ORDER BY position;",
+    )
+    .bind(collection_id)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows.into_iter().map(|(id,)| id).collect())
+}
+
+async fn set_item_positions(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    item_ids: &[i64],
+) -> Result<(), sqlx::Error> {
+    for (position, item_id) in item_ids.iter().enumerate() {
+        sqlx::query("UPDATE CollectionItems SET position = ? WHERE ID_COLLECTION_ITEM = ?;")
+            .bind(position as i64)
+            .bind(item_id)
+            .execute(&mut **tx)
+            .await?;
+    }
+    Ok(())
+}
+
+pub async fn add_items_to_collection(
+    db_pool: &SqlitePool,
+    collection_id: i64,
+    items: &[(ItemType, String)],
+) -> Result<u64, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    let mut position = get_item_ids(&mut tx, collection_id).await?.len() as i64;
+    let mut added = 0;
+    for (item_type, item_id) in items {
+        let result = sqlx::query(
+            "INSERT OR IGNORE INTO CollectionItems (ID_COLLECTION, itemType, ID_item, position) VALUES (?, ?, ?, ?);",
+        )
+        .bind(collection_id)
+        .bind(item_type.as_str())
+        .bind(item_id)
+        .bind(position)
+        .execute(&mut *tx)
+        .await?;
+        if result.rows_affected() > 0 {
+            position += 1;
+            added += 1;
+        }
+    }
+    tx.commit().await?;
+    Ok(added)
+}
+
+pub async fn remove_items_from_collection(
+    db_pool: &SqlitePool,
+    collection_id: i64,
+    items: &[(ItemType, String)],
+) -> Result<u64, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    let mut removed = 0;
+    for (item_type, item_id) in items {
+        removed += sqlx::query(
+            "DELETE FROM CollectionItems WHERE ID_COLLECTION = ? AND itemType = ? AND ID_item = ?;",
+        )
+        .bind(collection_id)
+        .bind(item_type.as_str())
+        .bind(item_id)
+        .execute(&mut *tx)
+        .await?
+        .rows_affected();
+    }
+    let remaining = get_item_ids(&mut tx, collection_id).await?;
+    set_item_positions(&mut tx, &remaining).await?;
+    tx.commit().await?;
+    Ok(removed)
+}
+
+pub async fn reorder_collection_items(
+    db_pool: &SqlitePool,
+    collection_id: i64,
+    item_ids: &[i64],
+) -> Result<bool, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    let existing: HashSet<i64> = get_item_ids(&mut tx, collection_id)
+        .await?
+        .into_iter()
+        .collect();
+    let requested: HashSet<i64> = item_ids.iter().copied().collect();
+    if existing != requested || requested.len() != item_ids.len() {
+        return Ok(false);
+    }
+    set_item_positions(&mut tx, item_ids).await?;
+    tx.commit().await?;
+    Ok(true)
+}
+
+fn find_book_cover(covers_dir: &str, book_id: &str, url_cover: Option<String>) -> Option<PathBuf> {
+    url_cover
+        .map(PathBuf::from)
+        .into_iter()
+        .chain([
+            Path::new(covers_dir).join(format!("{}.jpg.webp", book_id)),
+            Path::new(covers_dir).join(format!("{}.jpg", book_id)),
+        ])
+        .find(|path| path.is_file())
+}
+
+async fn get_first_book_of_series(
+    db_pool: &SqlitePool,
+    series_id: &str,
+) -> Result<Option<(String, Option<String>)>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT ID_book, URLCover FROM Books WHERE ID_Series = ? ORDER BY issueNumber, NOM LIMIT 1;",
+    )
+    .bind(series_id)
+    .fetch_optional(db_pool)
+    .await
+}
+
+async fn get_smart_member_books(
+    db_pool: &SqlitePool,
+    rules: &SmartRules,
+) -> Result<Vec<(String, Option<String>)>, Box<dyn std::error::Error + Send + Sync>> {
+    let query = SmartQuery {
+        limit: Some((MOSAIC_TILES * 3) as u32),
+        ..Default::default()
+    };
+    let mut books = Vec::new();
+    for item in evaluate_smart_rules(db_pool, rules, &query).await?.items {
+        match item {
+            SmartItem::Book(book) => books.push((book.id, book.cover)),
+            SmartItem::Series(series) => {
+                books.extend(get_first_book_of_series(db_pool, &series.id).await?)
+            }
+        }
+    }
+    Ok(books)
+}
+
+pub async fn get_collection_cover_images(
+    db_pool: &SqlitePool,
+    collection_id: i64,
+    covers_dir: &str,
+) -> Result<Vec<PathBuf>, Box<dyn std::error::Error + Send + Sync>> {
+    let books = match get_smart_rules(db_pool, collection_id).await? {
+        Some(rules) => get_smart_member_books(db_pool, &rules).await?,
+        None => sqlx::query_as(
+            "SELECT Books.ID_book, Books.URLCover FROM CollectionItems \
+             JOIN Books ON Books.ID_book = CASE WHEN CollectionItems.itemType = 'book' THEN CollectionItems.ID_item \
+             ELSE (SELECT ID_book FROM Books WHERE Books.ID_Series = CollectionItems.ID_item \
+                   ORDER BY issueNumber, NOM LIMIT 1) END \
+             WHERE CollectionItems.ID_COLLECTION = ? ORDER BY CollectionItems.position;",
+        )
+        .bind(collection_id)
+        .fetch_all(db_pool)
+        .await?,
+    };
+    Ok(books
+        .into_iter()
+        .filter_map(|(book_id, url_cover)| find_book_cover(covers_dir, &book_id, url_cover))
+        .take(MOSAIC_TILES)
+        .collect())
+}
+
+fn get_mosaic_tiles(count: usize) -> Vec<(u32, u32, u32, u32)> {
+    let (half_width, half_height) = (MOSAIC_WIDTH / 2, MOSAIC_HEIGHT / 2);
+    match count {
+        1 => vec![(0, 0, MOSAIC_WIDTH, MOSAIC_HEIGHT)],
+        2 => vec![
+            (0, 0, half_width, MOSAIC_HEIGHT),
+            (half_width, 0, half_width, MOSAIC_HEIGHT),
+        ],
+        3 => vec![
+            (0, 0, half_width, MOSAIC_HEIGHT),
+            (half_width, 0, half_width, half_height),
+            (half_width, half_height, half_width, half_height),
+        ],
+        _ => vec![
+            (0, 0, half_width, half_height),
+            (half_width, 0, half_width, half_height),
+            (0, half_height, half_width, half_height),
+            (half_width, half_height, half_width, half_height),
+        ],
+    }
+}
+
+pub fn build_cover_mosaic(
+    images: &[PathBuf],
+    output_path: &Path,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let decoded: Vec<DynamicImage> = images
+        .iter()
+        .take(MOSAIC_TILES)
+        .filter_map(|path| {
+            ImageReader::open(path)
+                .ok()?
+                .with_guessed_format()
+                .ok()?
+                .decode()
+                .ok()
+        })
+        .collect();
+    if decoded.is_empty() {
+        return Err("No cover images available".into());
+    }
+    let mut mosaic = RgbImage::new(MOSAIC_WIDTH, MOSAIC_HEIGHT);
+    for (img, (x, y, width, height)) in decoded.iter().zip(get_mosaic_tiles(decoded.len())) {
+        let tile = img
+            .resize_to_fill(width, height, FilterType::Triangle)
+            .to_rgb8();
+        image::imageops::replace(&mut mosaic, &tile, x as i64, y as i64);
+    }
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    encode_to_webp(&DynamicImage::ImageRgb8(mosaic), output_path).map_err(|e| e.to_string())?;
+    Ok(())
+}
+
+pub async fn refresh_collection_cover(
+    db_pool: &SqlitePool,
+    collection_id: i64,
+    covers_dir: &str,
+    output_dir: &str,
+) -> Result<Option<String>, Box<dyn std::error::Error + Send + Sync>> {
+    let images = get_collection_cover_images(db_pool, collection_id, covers_dir).await?;
+    let output_path = Path::new(output_dir).join(format!("{}.webp", collection_id));
+    let cover = if images.is_empty() {
+        if output_path.exists() {
+            fs::remove_file(&output_path)?;
+        }
+        None
+    } else {
+        let mosaic_path = output_path.clone();
+        tokio::task::spawn_blocking(move || build_cover_mosaic(&images, &mosaic_path)).await??;
+        Some(output_path.to_string_lossy().to_string())
+    };
+    sqlx::query("UPDATE Collections SET cover = ? WHERE ID_COLLECTION = ?;")
+        .bind(&cover)
+        .bind(collection_id)
+        .execute(db_pool)
+        .await?;
+    Ok(cover)
+}
@@ -0,0 +1,174 @@
+#[cfg(test)]
+mod tests {
+    use crate::repositories::database_repo::make_test_db;
+    use crate::services::collection_service::*;
+    use sqlx::SqlitePool;
+    use std::fs;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    async fn setup_db() -> (TempDir, SqlitePool) {
+        let (temp, pool) = make_test_db().await;
+        sqlx::query(
+            "INSERT INTO Series (ID_Series, title, favorite, PATH) VALUES ('s1', 'Saga', false, '/saga');",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        for (id, series, issue) in [("a", "s1", 2), ("b", "s1", 1), ("c", "", 0)] {
+            sqlx::query(
+                "INSERT INTO Books (ID_book, NOM, read, reading, unread, favorite, last_page, folder, PATH, ID_Series, issueNumber) \
+                 VALUES (?, ?, false, false, true, false, 0, false, ?, ?, ?);",
+            )
+            .bind(id)
+            .bind(format!("Book {}", id))
+            .bind(format!("/books/{}.cbz", id))
+            .bind(series)
+            .bind(issue)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        (temp, pool)
+    }
+
+    fn create_cover(dir: &Path, book_id: &str, color: [u8; 3]) {
+        fs::create_dir_all(dir).unwrap();
+        image::RgbImage::from_pixel(40, 60, image::Rgb(color))
+            .save(dir.join(format!("{}.jpg", book_id)))
+            .unwrap();
+    }
+
+    fn items(books: &[&str], series: &[&str]) -> Vec<(ItemType, String)> {
+        books
+            .iter()
+            .map(|id| (ItemType::Book, id.to_string()))
+            .chain(series.iter().map(|id| (ItemType::Series, id.to_string())))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_collections_crud_and_ordering() {
+        let (_temp, pool) = setup_db().await;
+        let lend = create_collection(&pool, "To lend", None).await.unwrap();
+        let best = create_collection(&pool, "Best of 2024", Some("Favourites"))
+            .await
+            .unwrap();
+
+        let names: Vec<String> = list_collections(&pool)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| c.name)
+            .collect();
+        assert_eq!(names, vec!["To lend", "Best of 2024"]);
+
+        assert!(!reorder_collections(&pool, &[best]).await.unwrap());
+        assert!(reorder_collections(&pool, &[best, lend]).await.unwrap());
+        assert!(
+            update_collection(&pool, lend, "To lend to Alex", None)
+                .await
+                .unwrap()
+        );
+        let collections = list_collections(&pool).await.unwrap();
+        assert_eq!(collections[0].id, best);
+        assert_eq!(collections[1].name, "To lend to Alex");
+
+        assert!(delete_collection(&pool, best).await.unwrap());
+        assert!(!delete_collection(&pool, best).await.unwrap());
+        let collections = list_collections(&pool).await.unwrap();
+        assert_eq!(collections.len(), 1);
+        assert_eq!(collections[0].position, 0);
+    }
+
+    #[tokio::test]
+    async fn test_bulk_add_remove_and_reorder_items() {
+        let (_temp, pool) = setup_db().await;
+        let id = create_collection(&pool, "Shelf", None).await.unwrap();
+
+        let added = add_items_to_collection(&pool, id, &items(&["a", "c"], &["s1"]))
+            .await
+            .unwrap();
+        assert_eq!(added, 3);
+        let added = add_items_to_collection(&pool, id, &items(&["a"], &[]))
+            .await
+            .unwrap();
+        assert_eq!(added, 0);
+
+        let detail = get_collection(&pool, id).await.unwrap().unwrap();
+        assert_eq!(detail.collection.books, 2);
+        assert_eq!(detail.collection.series, 1);
+        assert_eq!(detail.items[2].name.as_deref(), Some("Saga"));
+        let item_ids: Vec<i64> = detail.items.iter().map(|item| item.id).collect();
+
+        assert!(
+            reorder_collection_items(&pool, id, &[item_ids[2], item_ids[0], item_ids[1]])
+                .await
+                .unwrap()
+        );
+        let removed = remove_items_from_collection(&pool, id, &items(&["a"], &[]))
+            .await
+            .unwrap();
+        assert_eq!(removed, 1);
+
+        let detail = get_collection(&pool, id).await.unwrap().unwrap();
+        let order: Vec<(&str, i64)> = detail
+            .items
+            .iter()
+            .map(|item| (item.item_id.as_str(), item.position))
+            .collect();
+        assert_eq!(order, vec![("s1", 0), ("c", 1)]);
+    }
+
+    #[tokio::test]
+    async fn test_refresh_collection_cover_builds_mosaic() {
+        let (temp, pool) = setup_db().await;
+        let covers_dir = temp.path().join("FirstImagesOfAll");
+        let output_dir = temp.path().join("profiles/test_user/collections");
+        create_cover(&covers_dir, "b", [255, 0, 0]);
+        create_cover(&covers_dir, "c", [0, 0, 255]);
+        let id = create_collection(&pool, "Shelf", None).await.unwrap();
+        add_items_to_collection(&pool, id, &items(&["c"], &["s1"]))
+            .await
+            .unwrap();
+
+        let images = get_collection_cover_images(&pool, id, covers_dir.to_str().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(
+            images,
+            vec![covers_dir.join("c.jpg"), covers_dir.join("b.jpg")]
+        );
+
+        let cover = refresh_collection_cover(
+            &pool,
+            id,
+            covers_dir.to_str().unwrap(),
+            output_dir.to_str().unwrap(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        let mosaic = image::open(&cover).unwrap().to_rgb8();
+        assert_eq!(mosaic.dimensions(), (480, 720));
+        assert!(mosaic.get_pixel(100, 360)[2] > 200);
+        assert!(mosaic.get_pixel(380, 360)[0] > 200);
+        let detail = get_collection(&pool, id).await.unwrap().unwrap();
+        assert_eq!(detail.collection.cover.as_deref(), Some(cover.as_str()));
+
+        remove_items_from_collection(&pool, id, &items(&["c"], &["s1"]))
+            .await
+            .unwrap();
+        let cover_path = cover.clone();
+        let cover = refresh_collection_cover(
+            &pool,
+            id,
+            covers_dir.to_str().unwrap(),
+            output_dir.to_str().unwrap(),
+        )
+        .await
+        .unwrap();
+        assert!(cover.is_none());
+        assert!(!Path::new(&cover_path).exists());
+    }
+}
@@ -1,8 +1,12 @@
 use crate::repositories::database_repo::insert_into_db;
 use crate::repositories::database_repo::update_db;
 use crate::services::anilist_service::api_anilist_get_by_id;
+use crate::services::collection_service::ItemType;
+use crate::services::credit_service::{Credit, CreditKind, set_item_credits};
 use crate::services::googlebooks_service::get_gbapi_comics_by_id;
-use crate::services::marvel_service::{get_marvel_api_comics_by_id, get_marvel_api_series_by_id};
+use crate::services::marvel_service::{
+    CreatorSummary, SeriesSummary, get_marvel_api_comics_by_id, get_marvel_api_series_by_id,
+};
 use crate::services::openlibrary_service::{get_olapi_comics_by_id, get_olapi_search};
 use axum::Json;
 use serde_json::json;
@@ -10,6 +14,83 @@ use sqlx::{Row, SqlitePool};
 use std::{fs, io};
 use tracing::{error, info};
 
+fn marvel_credit_id(resource_uri: &str, provider: i32) -> String {
+    let id = resource_uri.rsplit('/').next().unwrap_or_default();
+    format!("{}_{}", id, provider)
+}
+
+async fn save_marvel_credits(
+    pool: &SqlitePool,
+    item_type: ItemType,
+    item_id: &str,
+    provider: i32,
+    creators: &[CreatorSummary],
+    characters: &[SeriesSummary],
+) -> Result<(), sqlx::Error> {
+    let creators = creators
+        .iter()
+        .map(|creator| {
+            Credit::new(
+                marvel_credit_id(&creator.resource_uri, provider),
+                &creator.name,
+                creator.role.as_deref(),
+            )
+        })
+        .collect::<Vec<Credit>>();
+    let characters = characters
+        .iter()
+        .map(|character| {
+            Credit::new(
+                marvel_credit_id(&character.resource_uri, provider),
+                &character.name,
+                None,
+            )
+        })
+        .collect::<Vec<Credit>>();
+    set_item_credits(pool, CreditKind::Creator, item_type, item_id, &creators).await?;
+    set_item_credits(pool, CreditKind::Character, item_type, item_id, &characters).await
+}
+
+async fn save_author_credits(
+    pool: &SqlitePool,
+    book_id: &str,
+    provider: i32,
+    authors: &[String],
+) -> Result<(), sqlx::Error> {
+    let creators = authors
+        .iter()
+        .map(|author| {
+            Credit::new(
+                format!("{}_{}", author.trim(), provider),
+                author,
+                Some("author"),
+            )
+        })
+        .collect::<Vec<Credit>>();
+    set_item_credits(
+        pool,
+        CreditKind::Creator,
+        ItemType::Book,
+        book_id,
+        &creators,
+    )
+    .await
+}
+
+async fn set_book_publisher(
+    pool: &SqlitePool,
+    book_id: &str,
+    publisher: Option<&str>,
+) -> Result<(), sqlx::Error> {
+    let publisher = publisher.map(str::trim).filter(|publisher| !publisher.is_empty());
+    sqlx::query("UPDATE Books SET publisher = ? WHERE ID_book = ?;")
+        .bind(publisher)
+        .bind(book_id)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
 pub async fn handle_marvel_book(
     pool: &SqlitePool,
     id: &str,
@@ -70,7 +151,17 @@ pub async fn handle_marvel_book(
         .map(|v| v.to_string())
         .collect::<Vec<String>>();
 
-    update_db(pool, "edit", columns, values, "Books", "PATH", &book_path).await
+    update_db(pool, "edit", columns, values, "Books", "PATH", &book_path).await?;
+    set_book_publisher(pool, &book_id, Some("Marvel")).await?;
+    save_marvel_credits(
+        pool,
+        ItemType::Book,
+        &book_id,
+        provider,
+        &result.creators.items,
+        &result.characters.items,
+    )
+    .await
 }
 
 use regex::Regex;
@@ -122,7 +213,16 @@ pub async fn handle_marvel_series(
         .map(|v| v.to_string())
         .collect::<Vec<String>>();
 
-    update_db(pool, "edit", columns, values, "Series", "PATH", &path).await
+    update_db(pool, "edit", columns, values, "Series", "PATH", &path).await?;
+    save_marvel_credits(
+        pool,
+        ItemType::Series,
+        id,
+        provider,
+        &res2.creators.items,
+        &res2.characters.items,
+    )
+    .await
 }
 
 pub async fn handle_anilist_series(
@@ -184,6 +284,40 @@ pub async fn handle_anilist_series(
         Err(e) => error!("Error updating database: {}", e),
     };
 
+    let staff_credits = result
+        .staff
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, staff)| {
+            Credit::new(
+                format!("{}_{}", staff.id, provider),
+                staff.name.full.clone().unwrap_or_default(),
+                result
+                    .staff
+                    .edges
+                    .get(i)
+                    .and_then(|edge| edge.role.as_deref()),
+            )
+        })
+        .collect::<Vec<Credit>>();
+    let character_credits = result
+        .characters
+        .nodes
+        .iter()
+        .enumerate()
+        .map(|(i, character)| {
+            Credit::new(
+                format!("{}_{}", character.id, provider),
+                character.name.full.clone().unwrap_or_default(),
+                result
+                    .characters
+                    .edges
+                    .get(i)
+                    .and_then(|edge| edge.role.as_deref()),
+            )
+        })
+        .collect::<Vec<Credit>>();
     let staff_object = result.staff.nodes.clone();
     let characters_object = result.characters.nodes.clone();
     let relations_nodes = result.relations.nodes.clone();
@@ -304,7 +438,22 @@ pub async fn handle_anilist_series(
             .await
             .expect("Failed to insert into relations");
     }
-    Ok(())
+    set_item_credits(
+        pool,
+        CreditKind::Creator,
+        ItemType::Series,
+        id,
+        &staff_credits,
+    )
+    .await?;
+    set_item_credits(
+        pool,
+        CreditKind::Character,
+        ItemType::Series,
+        id,
+        &character_credits,
+    )
+    .await
 }
 
 pub async fn handle_openlibrary_book(
@@ -375,7 +524,16 @@ pub async fn handle_openlibrary_book(
         .map(|v| v.to_string())
         .collect::<Vec<String>>();
 
-    update_db(pool, "edit", columns, values, "Books", "PATH", &path).await
+    update_db(pool, "edit", columns, values, "Books", "PATH", &path).await?;
+    let publisher = details.publishers.iter().flatten().next();
+    set_book_publisher(pool, id, publisher.map(String::as_str)).await?;
+    let authors = details
+        .authors
+        .iter()
+        .flatten()
+        .map(|author| author.name.clone())
+        .collect::<Vec<String>>();
+    save_author_credits(pool, id, provider, &authors).await
 }
 
 pub async fn handle_google_book(
@@ -425,6 +583,8 @@ pub async fn handle_google_book(
     asso.insert("URLs".to_string(), json!(res.volume_info.info_link));
     asso.insert("dates".to_string(), json!(res.volume_info.published_date));
     asso.insert("prices".to_string(), json!(price));
+    let authors = res.volume_info.authors.clone().unwrap_or_default();
+    let publisher = res.volume_info.publisher.clone();
     asso.insert("creators".to_string(), json!(res.volume_info.authors));
     asso.insert("characters".to_string(), json!("null"));
     asso.insert("series".to_string(), json!("null"));
@@ -439,7 +599,41 @@ pub async fn handle_google_book(
         .map(|v| v.to_string())
         .collect::<Vec<String>>();
 
-    update_db(pool, "edit", columns, values, "Books", "PATH", &path).await
+    update_db(pool, "edit", columns, values, "Books", "PATH", &path).await?;
+    set_book_publisher(pool, id, publisher.as_deref()).await?;
+    save_author_credits(pool, id, provider, &authors).await
+}
+
+pub async fn refresh_item_metadata(
+    pool: &SqlitePool,
+    id: &str,
+    item_type: &str,
+    provider: i32,
+    marvel_priv_key: String,
+    marvel_pub_key: String,
+) -> Result<(), sqlx::Error> {
+    match provider {
+        1 => {
+            if item_type == "book" {
+                handle_marvel_book(pool, id, provider, marvel_priv_key, marvel_pub_key).await
+            } else {
+                handle_marvel_series(pool, id, provider, marvel_priv_key, marvel_pub_key).await
+            }
+        }
+        2 => {
+            if item_type == "book" {
+                Ok(())
+            } else {
+                handle_anilist_series(pool, id, provider).await
+            }
+        }
+        3 => handle_openlibrary_book(pool, id, provider).await,
+        4 => handle_google_book(pool, id, provider).await,
+        _ => Err(sqlx::Error::Protocol(format!(
+            "Unknown provider {}",
+            provider
+        ))),
+    }
 }
 
 pub async fn get_list_of_files_and_folders(
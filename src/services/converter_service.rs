@@ -1,12 +1,16 @@
 use crate::repositories::database_repo::update_db;
-use image::ImageReader;
+use image::imageops::FilterType;
+use image::{DynamicImage, ImageReader};
 use sqlx::SqlitePool;
+use std::path::Path;
 use webp::Encoder;
 
-fn convert_to_webp(input_path: &str, output_path: &str) -> Result<(), Box<dyn std::error::Error>> {
-    println!("Converting {}", input_path);
-    let reader = ImageReader::open(&input_path)?.with_guessed_format()?;
-    let img = reader.decode()?;
+pub const COVER_SIZES: &[(&str, u32)] = &[("thumb", 240), ("detail", 720)];
+
+pub fn encode_to_webp(
+    img: &DynamicImage,
+    output_path: &Path,
+) -> Result<(), Box<dyn std::error::Error>> {
     let rgb_img = img.to_rgb8();
     let (width, height) = rgb_img.dimensions();
     let encoder = Encoder::from_rgb(&rgb_img, width, height);
@@ -14,6 +18,63 @@ fn convert_to_webp(input_path: &str, output_path: &str) -> Result<(), Box<dyn st
     std::fs::write(output_path, &*webp_data)?;
     Ok(())
 }
+
+fn get_dominant_color(img: &DynamicImage) -> String {
+    let small_img = img.thumbnail(16, 16).to_rgb8();
+    let pixel_count = (small_img.width() * small_img.height()).max(1) as u64;
+    let (r, g, b) = small_img.pixels().fold((0u64, 0u64, 0u64), |acc, pixel| {
+        (
+            acc.0 + pixel[0] as u64,
+            acc.1 + pixel[1] as u64,
+            acc.2 + pixel[2] as u64,
+        )
+    });
+    format!(
+        "#{:02x}{:02x}{:02x}",
+        r / pixel_count,
+        g / pixel_count,
+        b / pixel_count
+    )
+}
+
+fn convert_to_webp(
+    input_path: &str,
+    output_path: &str,
+) -> Result<String, Box<dyn std::error::Error>> {
+    println!("Converting {}", input_path);
+    let reader = ImageReader::open(input_path)?.with_guessed_format()?;
+    let img = reader.decode()?;
+    let output_path = Path::new(output_path);
+    encode_to_webp(&img, output_path)?;
+
+    let output_dir = output_path.parent().unwrap_or(Path::new("."));
+    let output_name = output_path.file_name().unwrap_or_default();
+    for (size_name, max_width) in COVER_SIZES {
+        let sized_dir = output_dir.join(size_name);
+        std::fs::create_dir_all(&sized_dir)?;
+        let sized_img = if img.width() > *max_width {
+            img.resize(*max_width, u32::MAX, FilterType::Triangle)
+        } else {
+            img.clone()
+        };
+        encode_to_webp(&sized_img, &sized_dir.join(output_name))?;
+    }
+    Ok(get_dominant_color(&img))
+}
+
+pub fn get_cover_path(covers_dir: &str, image_name: &str, size: Option<&str>) -> String {
+    match size {
+        Some(size_name) if COVER_SIZES.iter().any(|(name, _)| *name == size_name) => {
+            let sized_path = format!("{}/{}/{}", covers_dir, size_name, image_name);
+            if Path::new(&sized_path).exists() {
+                return sized_path;
+            }
+            format!("{}/{}", covers_dir, image_name)
+        }
+        _ => format!("{}/{}", covers_dir, image_name),
+    }
+}
+
 pub async fn convert_all_images_in_directory(
     dir_path: &str,
     output_dir: &str,
@@ -32,14 +93,16 @@ pub async fn convert_all_images_in_directory(
         let path = entry.path();
         if path.is_file() {
             if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-                if valid_extensions.contains(&ext) {
+                if valid_extensions.contains(&ext) && ext != "webp" {
                     let file_name = path.file_name().unwrap().to_str().unwrap();
+                    let book_id = path.file_stem().unwrap().to_str().unwrap();
                     let output_path = output_dir.join(format!("{}.webp", file_name));
-                    convert_to_webp(path.to_str().unwrap(), output_path.to_str().unwrap())?;
-                    let col_vec = vec!["URLCover".to_string()];
-                    let val_vec = vec![output_path.to_str().unwrap().to_string()];
+                    let placeholder =
+                        convert_to_webp(path.to_str().unwrap(), output_path.to_str().unwrap())?;
+                    let col_vec = vec!["URLCover".to_string(), "coverPlaceholder".to_string()];
+                    let val_vec = vec![output_path.to_str().unwrap().to_string(), placeholder];
                     update_db(
-                        &db_pool, "noedit", col_vec, val_vec, "Books", "ID_book", file_name,
+                        &db_pool, "edit", col_vec, val_vec, "Books", "ID_book", book_id,
                     )
                     .await?;
                 }
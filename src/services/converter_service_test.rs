@@ -4,7 +4,7 @@ mod tests {
     use std::path::Path;
     use tempfile::tempdir;
 
-    use crate::services::converter_service::convert_all_images_in_directory;
+    use crate::services::converter_service::{convert_all_images_in_directory, get_cover_path};
 
     fn create_test_image(path: &Path) {
         let img = image::RgbImage::new(100, 100);
@@ -26,12 +26,12 @@ mod tests {
             .await
             .expect("Failed to create DB");
 
-        sqlx::query("CREATE TABLE Books (ID_book TEXT, URLCover TEXT);")
+        sqlx::query("CREATE TABLE Books (ID_book TEXT, URLCover TEXT, coverPlaceholder TEXT);")
             .execute(&db_pool)
             .await
             .unwrap();
 
-        sqlx::query("INSERT INTO Books (ID_book, URLCover) VALUES ('test_image.jpg', '');")
+        sqlx::query("INSERT INTO Books (ID_book, URLCover) VALUES ('test_image', '');")
             .execute(&db_pool)
             .await
             .unwrap();
@@ -48,5 +48,42 @@ mod tests {
 
         let webp_path = output_dir.path().join("test_image.jpg.webp");
         assert!(webp_path.exists());
+        assert!(output_dir.path().join("thumb/test_image.jpg.webp").exists());
+        assert!(
+            output_dir
+                .path()
+                .join("detail/test_image.jpg.webp")
+                .exists()
+        );
+
+        let (url_cover, placeholder): (String, String) =
+            sqlx::query_as("SELECT URLCover, coverPlaceholder FROM Books WHERE ID_book = ?;")
+                .bind("test_image")
+                .fetch_one(&db_pool)
+                .await
+                .unwrap();
+        assert_eq!(url_cover, webp_path.to_str().unwrap());
+        assert_eq!(placeholder, "#000000");
+    }
+
+    #[test]
+    fn test_get_cover_path_falls_back_to_full_size() {
+        let covers_dir = tempdir().unwrap();
+        let covers_path = covers_dir.path().to_str().unwrap();
+        std::fs::create_dir_all(covers_dir.path().join("thumb")).unwrap();
+        std::fs::write(covers_dir.path().join("thumb/a.webp"), b"thumb").unwrap();
+
+        assert_eq!(
+            get_cover_path(covers_path, "a.webp", Some("thumb")),
+            format!("{}/thumb/a.webp", covers_path)
+        );
+        assert_eq!(
+            get_cover_path(covers_path, "b.webp", Some("thumb")),
+            format!("{}/b.webp", covers_path)
+        );
+        assert_eq!(
+            get_cover_path(covers_path, "a.webp", Some("../../etc")),
+            format!("{}/a.webp", covers_path)
+        );
     }
 }
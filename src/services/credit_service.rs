@@ -0,0 +1,232 @@
+use crate::models::book_model::Book;
+use crate::models::series_model::Series;
+use crate::services::collection_service::ItemType;
+use serde::Serialize;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{FromRow, Row, SqlitePool};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CreditKind {
+    Creator,
+    Character,
+}
+
+impl CreditKind {
+    fn table(&self) -> &'static str {
+        match self {
+            CreditKind::Creator => "Creators",
+            CreditKind::Character => "Characters",
+        }
+    }
+
+    fn key(&self) -> &'static str {
+        match self {
+            CreditKind::Creator => "ID_CREATOR",
+            CreditKind::Character => "ID_CHAR",
+        }
+    }
+
+    fn links(&self) -> &'static str {
+        match self {
+            CreditKind::Creator => "CreatorLinks",
+            CreditKind::Character => "CharacterLinks",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Credit {
+    pub id: String,
+    pub name: String,
+    pub role: Option<String>,
+}
+
+impl Credit {
+    pub fn new(id: impl Into<String>, name: impl Into<String>, role: Option<&str>) -> Self {
+        Credit {
+            id: id.into(),
+            name: name.into(),
+            role: role.map(str::to_string),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct CreditedWork<T> {
+    #[serde(flatten)]
+    pub item: T,
+    pub roles: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Works {
+    pub books: Vec<CreditedWork<Book>>,
+    pub series: Vec<CreditedWork<Series>>,
+}
+
+pub fn normalize_roles(role: Option<&str>) -> Vec<String> {
+    let Some(role) = role.map(|role| role.trim().to_lowercase()) else {
+        return vec![String::new()];
+    };
+    if role.contains("cover") {
+        return vec!["cover".to_string()];
+    }
+    let mut roles: Vec<String> = Vec::new();
+    for part in role.split(['&', ',', '/']) {
+        let part = part.split('(').next().unwrap_or_default().trim();
+        let normalized = match part {
+            "story" | "script" | "writer" | "author" | "original creator" | "original story" => {
+                "writer"
+            }
+            "art" | "artist" | "penciller" | "penciler" | "illustration" | "illustrator" => {
+                "artist"
+            }
+            "colors" | "colorist" | "colourist" => "colorist",
+            "letterer" | "lettering" => "letterer",
+            part => part,
+        };
+        if !roles.iter().any(|existing| existing == normalized) {
+            roles.push(normalized.to_string());
+        }
+    }
+    if roles.is_empty() {
+        roles.push(String::new());
+    }
+    roles
+}
+
+pub async fn set_item_credits(
+    db_pool: &SqlitePool,
+    kind: CreditKind,
+    item_type: ItemType,
+    item_id: &str,
+    credits: &[Credit],
+) -> Result<(), sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    sqlx::query(&format!(
+        "DELETE FROM {} WHERE itemType = ? AND ID_item = ?;",
+        kind.links()
+    ))
+    .bind(item_type.as_str())
+    .bind(item_id)
+    .execute(&mut *tx)
+    .await?;
+    for credit in credits {
+        let name = credit.name.trim();
+        if credit.id.is_empty() || name.is_empty() {
+            continue;
+        }
+        sqlx::query(&format!(
+            "INSERT OR IGNORE INTO {} ({}, name) VALUES (?, ?);",
+            kind.table(),
+            kind.key()
+        ))
+        .bind(&credit.id)
+        .bind(name)
+        .execute(&mut *tx)
+        .await?;
+        for role in normalize_roles(credit.role.as_deref()) {
+            sqlx::query(&format!(
+                "INSERT OR IGNORE INTO {} ({}, itemType, ID_item, role) VALUES (?, ?, ?, ?);",
+                kind.links(),
+                kind.key()
+            ))
+            .bind(&credit.id)
+            .bind(item_type.as_str())
+            .bind(item_id)
+            .bind(role)
+            .execute(&mut *tx)
+            .await?;
+        }
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+fn split_roles(row: &SqliteRow) -> Result<Vec<String>, sqlx::Error> {
+    let roles: Option<String> = row.try_get("credit_roles")?;
+    let mut roles: Vec<String> = roles
+        .unwrap_or_default()
+        .split(',')
+        .filter(|role| !role.is_empty())
+        .map(str::to_string)
+        .collect();
+    roles.sort();
+    Ok(roles)
+}
+
+fn works_query(
+    kind: CreditKind,
+    table: &str,
+    key: &str,
+    item_type: ItemType,
+    order: &str,
+) -> String {
+    format!(
+        "SELECT {table}.*, group_concat(DISTINCT {links}.role) AS credit_roles FROM {links} \
+         JOIN {table} ON {table}.{key} = {links}.ID_item \
+         WHERE {links}.itemType = '{item_type}' AND {links}.{person} IN \
+         (SELECT {person} FROM {people} WHERE {person} = ?1 OR lower(trim(name)) = \
+         (SELECT lower(trim(name)) FROM {people} WHERE {person} = ?1)) \
+         GROUP BY {table}.{key} ORDER BY {order} COLLATE NOCASE;",
+        links = kind.links(),
+        person = kind.key(),
+        people = kind.table(),
+        item_type = item_type.as_str(),
+    )
+}
+
+pub async fn get_credited_works(
+    db_pool: &SqlitePool,
+    kind: CreditKind,
+    id: &str,
+) -> Result<Option<Works>, sqlx::Error> {
+    let exists: Option<String> = sqlx::query_scalar(&format!(
+        "SELECT {key} FROM {table} WHERE {key} = ?;",
+        key = kind.key(),
+        table = kind.table()
+    ))
+    .bind(id)
+    .fetch_optional(db_pool)
+    .await?;
+    if exists.is_none() {
+        return Ok(None);
+    }
+    let books = sqlx::query(&works_query(
+        kind,
+        "Books",
+        "ID_book",
+        ItemType::Book,
+        "Books.NOM",
+    ))
+    .bind(id)
+    .fetch_all(db_pool)
+    .await?
+    .iter()
+    .map(|row| {
+        Ok(CreditedWork {
+            item: Book::from_row(row)?,
+            roles: split_roles(row)?,
+        })
+    })
+    .collect::<Result<_, sqlx::Error>>()?;
+    let series = sqlx::query(&works_query(
+        kind,
+        "Series",
+        "ID_Series",
+        ItemType::Series,
+        "Series.title",
+    ))
+    .bind(id)
+    .fetch_all(db_pool)
+    .await?
+    .iter()
+    .map(|row| {
+        Ok(CreditedWork {
+            item: Series::from_row(row)?,
+            roles: split_roles(row)?,
+        })
+    })
+    .collect::<Result<_, sqlx::Error>>()?;
+    Ok(Some(Works { books, series }))
+}
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use crate::repositories::database_repo::make_test_db;
+    use crate::services::collection_service::ItemType;
+    use crate::services::credit_service::*;
+    use crate::services::resource_service::{Resource, create_resource, delete_resource};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use tempfile::TempDir;
+
+    async fn setup_db() -> (TempDir, SqlitePool) {
+        let (temp, pool) = make_test_db().await;
+        for (resource, payload) in [
+            (
+                Resource::Books,
+                json!({"id": "b1", "name": "Watchmen #1", "path": "/comics/w1.cbz"}),
+            ),
+            (
+                Resource::Books,
+                json!({"id": "b2", "name": "V for Vendetta", "path": "/comics/v.cbz"}),
+            ),
+            (
+                Resource::Series,
+                json!({"id": "s1", "title": "Watchmen", "path": "/comics"}),
+            ),
+        ] {
+            create_resource(&pool, resource, &payload).await.unwrap();
+        }
+        (temp, pool)
+    }
+
+    #[test]
+    fn test_normalize_roles() {
+        assert_eq!(
+            normalize_roles(Some("Story & Art")),
+            vec!["writer", "artist"]
+        );
+        assert_eq!(normalize_roles(Some("penciller (cover)")), vec!["cover"]);
+        assert_eq!(normalize_roles(Some(" Inker ")), vec!["inker"]);
+        assert_eq!(normalize_roles(Some("MAIN")), vec!["main"]);
+        assert_eq!(normalize_roles(None), vec![""]);
+    }
+
+    #[tokio::test]
+    async fn test_credited_works_are_merged_across_providers() {
+        let (_temp, pool) = setup_db().await;
+        let marvel = Credit::new("123_1", "Alan Moore", Some("writer"));
+        set_item_credits(&pool, CreditKind::Creator, ItemType::Book, "b1", &[marvel])
+            .await
+            .unwrap();
+        set_item_credits(
+            &pool,
+            CreditKind::Creator,
+            ItemType::Book,
+            "b2",
+            &[Credit::new("Alan Moore_4", "alan moore ", Some("author"))],
+        )
+        .await
+        .unwrap();
+        set_item_credits(
+            &pool,
+            CreditKind::Creator,
+            ItemType::Series,
+            "s1",
+            &[
+                Credit::new("123_1", "Alan Moore", Some("Story & Art")),
+                Credit::new("77_1", "Dave Gibbons", Some("artist")),
+            ],
+        )
+        .await
+        .unwrap();
+
+        let works = get_credited_works(&pool, CreditKind::Creator, "Alan Moore_4")
+            .await
+            .unwrap()
+            .unwrap();
+        let books: Vec<&str> = works.books.iter().map(|w| w.item.id.as_str()).collect();
+        assert_eq!(books, vec!["b2", "b1"]);
+        assert_eq!(works.books[0].roles, vec!["writer"]);
+        assert_eq!(works.series.len(), 1);
+        assert_eq!(works.series[0].roles, vec!["artist", "writer"]);
+        assert_eq!(
+            serde_json::to_value(&works.series[0]).unwrap()["id"],
+            json!("s1")
+        );
+
+        set_item_credits(&pool, CreditKind::Creator, ItemType::Series, "s1", &[])
+            .await
+            .unwrap();
+        delete_resource(&pool, Resource::Books, "b1").await.unwrap();
+        let works = get_credited_works(&pool, CreditKind::Creator, "123_1")
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(works.books.len(), 1);
+        assert!(works.series.is_empty());
+        assert!(
+            get_credited_works(&pool, CreditKind::Character, "123_1")
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,277 @@
+use crate::services::collection_service::ItemType;
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqlitePool};
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FieldType {
+    Text,
+    Number,
+    Date,
+    Enum,
+}
+
+impl FieldType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            FieldType::Text => "text",
+            FieldType::Number => "number",
+            FieldType::Date => "date",
+            FieldType::Enum => "enum",
+        }
+    }
+
+    fn from_db(value: &str) -> Option<Self> {
+        match value {
+            "text" => Some(FieldType::Text),
+            "number" => Some(FieldType::Number),
+            "date" => Some(FieldType::Date),
+            "enum" => Some(FieldType::Enum),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct CustomField {
+    pub id: i64,
+    pub name: String,
+    #[serde(rename = "type")]
+    pub field_type: FieldType,
+    pub target: ItemType,
+    pub options: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Text(String),
+    Number(f64),
+}
+
+fn row_to_field(row: &SqliteRow) -> Result<CustomField, sqlx::Error> {
+    let field_type: String = row.try_get("fieldType")?;
+    let target: String = row.try_get("target")?;
+    Ok(CustomField {
+        id: row.try_get("ID_FIELD")?,
+        name: row.try_get("NAME")?,
+        field_type: FieldType::from_db(&field_type).unwrap_or(FieldType::Text),
+        target: if target == "series" {
+            ItemType::Series
+        } else {
+            ItemType::Book
+        },
+        options: row
+            .try_get::<Option<String>, _>("options")?
+            .and_then(|options| serde_json::from_str(&options).ok())
+            .unwrap_or_default(),
+    })
+}
+
+pub fn validate_field_definition(field_type: FieldType, options: &[String]) -> Result<(), String> {
+    match field_type {
+        FieldType::Enum if options.is_empty() => {
+            Err("Enum fields need at least one option".to_string())
+        }
+        FieldType::Enum if options.iter().any(|option| option.trim().is_empty()) => {
+            Err("Enum options cannot be empty".to_string())
+        }
+        FieldType::Enum => Ok(()),
+        _ if !options.is_empty() => Err("Only enum fields can have options".to_string()),
+        _ => Ok(()),
+    }
+}
+
+pub fn validate_field_value(field: &CustomField, value: &Value) -> Result<FieldValue, String> {
+    let invalid = || format!("Invalid value for field: {}", field.name);
+    match field.field_type {
+        FieldType::Text => value
+            .as_str()
+            .map(|text| FieldValue::Text(text.to_string()))
+            .ok_or_else(invalid),
+        FieldType::Number => value.as_f64().map(FieldValue::Number).ok_or_else(invalid),
+        FieldType::Date => value
+            .as_str()
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            .map(|date| FieldValue::Text(date.format("%Y-%m-%d").to_string()))
+            .ok_or_else(invalid),
+        FieldType::Enum => value
+            .as_str()
+            .filter(|option| field.options.iter().any(|o| o == option))
+            .map(|option| FieldValue::Text(option.to_string()))
+            .ok_or_else(invalid),
+    }
+}
+
+pub async fn list_custom_fields(
+    db_pool: &SqlitePool,
+    target: Option<ItemType>,
+) -> Result<Vec<CustomField>, sqlx::Error> {
+    sqlx::query(
+        "SELECT ID_FIELD, NAME, fieldType, target, options FROM CustomFields \
+         WHERE ?1 IS NULL OR target = ?1 ORDER BY target, NAME COLLATE NOCASE;",
+    )
+    .bind(target.map(|target| target.as_str()))
+    .fetch_all(db_pool)
+    .await?
+    .iter()
+    .map(row_to_field)
+    .collect()
+}
+
+pub async fn get_custom_field(
+    db_pool: &SqlitePool,
+    field_id: i64,
+) -> Result<Option<CustomField>, sqlx::Error> {
+    sqlx::query(
+        "SELECT ID_FIELD, NAME, fieldType, target, options FROM CustomFields WHERE ID_FIELD = ?;",
+    )
+    .bind(field_id)
+    .fetch_optional(db_pool)
+    .await?
+    .as_ref()
+    .map(row_to_field)
+    .transpose()
+}
+
+pub async fn create_custom_field(
+    db_pool: &SqlitePool,
+    name: &str,
+    field_type: FieldType,
+    target: ItemType,
+    options: &[String],
+) -> Result<i64, sqlx::Error> {
+    let options =
+        (field_type == FieldType::Enum).then(|| serde_json::to_string(options).unwrap_or_default());
+    let result = sqlx::query(
+        "INSERT INTO CustomFields (NAME, fieldType, target, options) VALUES (?, ?, ?, ?);",
+    )
+    .bind(name)
+    .bind(field_type.as_str())
+    .bind(target.as_str())
+    .bind(options)
+    .execute(db_pool)
+    .await?;
+    Ok(result.last_insert_rowid())
+}
+
+pub async fn update_custom_field(
+    db_pool: &SqlitePool,
+    field: &CustomField,
+    name: &str,
+    options: &[String],
+) -> Result<bool, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    let stored_options = (field.field_type == FieldType::Enum)
+        .then(|| serde_json::to_string(options).unwrap_or_default());
+    let result = sqlx::query("UPDATE CustomFields SET NAME = ?, options = ? WHERE ID_FIELD = ?;")
+        .bind(name)
+        .bind(stored_options)
+        .bind(field.id)
+        .execute(&mut *tx)
+        .await?;
+    if field.field_type == FieldType::Enum {
+        let mut qb = sqlx::QueryBuilder::<sqlx::Sqlite>::new(
+            "DELETE FROM CustomFieldValues WHERE ID_FIELD = ",
+        );
+        qb.push_bind(field.id).push(" AND value NOT IN (");
+        let mut separated = qb.separated(", ");
+        for option in options {
+            separated.push_bind(option);
+        }
+        qb.push(")");
+        qb.build().execute(&mut *tx).await?;
+    }
+    tx.commit().await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn delete_custom_field(db_pool: &SqlitePool, field_id: i64) -> Result<bool, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    sqlx::query("DELETE FROM CustomFieldValues WHERE ID_FIELD = ?;")
+        .bind(field_id)
+        .execute(&mut *tx)
+        .await?;
+    let result = sqlx::query("DELETE FROM CustomFields WHERE ID_FIELD = ?;")
+        .bind(field_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_item_field_values(
+    db_pool: &SqlitePool,
+    item_type: ItemType,
+    item_id: &str,
+) -> Result<HashMap<String, Value>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT CustomFields.NAME, CustomFields.fieldType, CustomFieldValues.value \
+         FROM CustomFieldValues JOIN CustomFields ON CustomFields.ID_FIELD = CustomFieldValues.ID_FIELD \
+         WHERE CustomFields.target = ? AND CustomFieldValues.ID_item = ?;",
+    )
+    .bind(item_type.as_str())
+    .bind(item_id)
+    .fetch_all(db_pool)
+    .await?;
+    let mut values = HashMap::new();
+    for row in rows {
+        let field_type: String = row.try_get("fieldType")?;
+        let value = if field_type == FieldType::Number.as_str() {
+            Value::from(row.try_get::<f64, _>("value")?)
+        } else {
+            Value::from(row.try_get::<String, _>("value")?)
+        };
+        values.insert(row.try_get("NAME")?, value);
+    }
+    Ok(values)
+}
+
+pub async fn set_item_field_values(
+    db_pool: &SqlitePool,
+    item_type: ItemType,
+    item_id: &str,
+    values: &HashMap<String, Value>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let fields = list_custom_fields(db_pool, Some(item_type)).await?;
+    let mut changes = Vec::new();
+    for (name, value) in values {
+        let field = fields
+            .iter()
+            .find(|field| field.name.eq_ignore_ascii_case(name))
+            .ok_or_else(|| format!("Unknown field: {}", name))?;
+        let value = match value {
+            Value::Null => None,
+            value => Some(validate_field_value(field, value)?),
+        };
+        changes.push((field.id, value));
+    }
+
+    let mut tx = db_pool.begin().await?;
+    for (field_id, value) in changes {
+        let query = match value {
+            None => {
+                sqlx::query("DELETE FROM CustomFieldValues WHERE ID_FIELD = ? AND ID_item = ?;")
+                    .bind(field_id)
+                    .bind(item_id)
+            }
+            Some(value) => {
+                let query = sqlx::query(
+                    "INSERT OR REPLACE INTO CustomFieldValues (ID_FIELD, ID_item, value) VALUES (?, ?, ?);",
+                )
+                .bind(field_id)
+                .bind(item_id);
+                match value {
+                    FieldValue::Text(text) => query.bind(text),
+                    FieldValue::Number(number) => query.bind(number),
+                }
+            }
+        };
+        query.execute(&mut *tx).await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
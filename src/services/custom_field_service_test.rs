@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod tests {
+    use crate::repositories::database_repo::make_test_db;
+    use crate::services::collection_service::ItemType;
+    use crate::services::custom_field_service::*;
+    use serde_json::{Value, json};
+    use std::collections::HashMap;
+
+    fn values(value: Value) -> HashMap<String, Value> {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn condition_field() -> CustomField {
+        CustomField {
+            id: 1,
+            name: "Condition".to_string(),
+            field_type: FieldType::Enum,
+            target: ItemType::Book,
+            options: vec!["Mint".to_string(), "Good".to_string()],
+        }
+    }
+
+    #[test]
+    fn test_validate_field_definition_and_values() {
+        assert!(validate_field_definition(FieldType::Enum, &[]).is_err());
+        assert!(validate_field_definition(FieldType::Text, &["a".to_string()]).is_err());
+        assert!(validate_field_definition(FieldType::Date, &[]).is_ok());
+
+        let field = condition_field();
+        assert_eq!(
+            validate_field_value(&field, &json!("Mint")),
+            Ok(FieldValue::Text("Mint".to_string()))
+        );
+        assert!(validate_field_value(&field, &json!("Poor")).is_err());
+
+        let field = CustomField {
+            field_type: FieldType::Date,
+            options: Vec::new(),
+            ..condition_field()
+        };
+        assert_eq!(
+            validate_field_value(&field, &json!("2024-2-9")),
+            Ok(FieldValue::Text("2024-02-09".to_string()))
+        );
+        assert!(validate_field_value(&field, &json!("09/02/2024")).is_err());
+        assert!(validate_field_value(&field, &json!(20240209)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_set_and_clear_item_values() {
+        let (_temp, pool) = make_test_db().await;
+        create_custom_field(&pool, "Price", FieldType::Number, ItemType::Book, &[])
+            .await
+            .unwrap();
+        create_custom_field(&pool, "Bought", FieldType::Date, ItemType::Book, &[])
+            .await
+            .unwrap();
+        create_custom_field(&pool, "Shelf", FieldType::Text, ItemType::Series, &[])
+            .await
+            .unwrap();
+
+        set_item_field_values(
+            &pool,
+            ItemType::Book,
+            "b1",
+            &values(json!({ "price": 4.99, "Bought": "2024-03-01" })),
+        )
+        .await
+        .unwrap();
+        let stored = get_item_field_values(&pool, ItemType::Book, "b1")
+            .await
+            .unwrap();
+        assert_eq!(stored["Price"], json!(4.99));
+        assert_eq!(stored["Bought"], json!("2024-03-01"));
+
+        assert!(
+            set_item_field_values(
+                &pool,
+                ItemType::Book,
+                "b1",
+                &values(json!({ "Shelf": "A" }))
+            )
+            .await
+            .is_err()
+        );
+        assert!(
+            set_item_field_values(
+                &pool,
+                ItemType::Book,
+                "b1",
+                &values(json!({ "Price": "free" }))
+            )
+            .await
+            .is_err()
+        );
+
+        set_item_field_values(
+            &pool,
+            ItemType::Book,
+            "b1",
+            &values(json!({ "Price": null })),
+        )
+        .await
+        .unwrap();
+        let stored = get_item_field_values(&pool, ItemType::Book, "b1")
+            .await
+            .unwrap();
+        assert_eq!(stored.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_update_enum_options_drops_stale_values() {
+        let (_temp, pool) = make_test_db().await;
+        let field = condition_field();
+        let id = create_custom_field(
+            &pool,
+            &field.name,
+            field.field_type,
+            field.target,
+            &field.options,
+        )
+        .await
+        .unwrap();
+        assert!(
+            create_custom_field(&pool, "condition", FieldType::Text, ItemType::Book, &[])
+                .await
+                .is_err()
+        );
+        set_item_field_values(
+            &pool,
+            ItemType::Book,
+            "b1",
+            &values(json!({ "Condition": "Good" })),
+        )
+        .await
+        .unwrap();
+        set_item_field_values(
+            &pool,
+            ItemType::Book,
+            "b2",
+            &values(json!({ "Condition": "Mint" })),
+        )
+        .await
+        .unwrap();
+
+        let field = get_custom_field(&pool, id).await.unwrap().unwrap();
+        let options = vec!["Mint".to_string(), "Fine".to_string()];
+        assert!(
+            update_custom_field(&pool, &field, "Grade", &options)
+                .await
+                .unwrap()
+        );
+
+        let field = get_custom_field(&pool, id).await.unwrap().unwrap();
+        assert_eq!((field.name.as_str(), field.options), ("Grade", options));
+        assert!(
+            get_item_field_values(&pool, ItemType::Book, "b1")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        assert_eq!(
+            get_item_field_values(&pool, ItemType::Book, "b2")
+                .await
+                .unwrap()["Grade"],
+            json!("Mint")
+        );
+
+        assert!(delete_custom_field(&pool, id).await.unwrap());
+        assert!(list_custom_fields(&pool, None).await.unwrap().is_empty());
+    }
+}
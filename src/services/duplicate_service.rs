@@ -0,0 +1,280 @@
+use crate::AppGlobalVariables;
+use crate::services::archive_service::extract_first_image;
+use crate::utils::{generate_random_id, is_image_file, natural_cmp};
+use image::ImageReader;
+use image::imageops::FilterType;
+use serde::Serialize;
+use sqlx::{Row, SqlitePool};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, Read};
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{error, info};
+
+pub const SIMILAR_COVER_MAX_DISTANCE: u32 = 6;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DuplicateBook {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub size: u64,
+    pub format: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct DuplicateGroups {
+    pub exact: Vec<Vec<DuplicateBook>>,
+    pub similar: Vec<Vec<DuplicateBook>>,
+}
+
+fn list_folder_images(dir: &Path) -> io::Result<Vec<String>> {
+    let mut images: Vec<String> = fs::read_dir(dir)?
+        .flatten()
+        .filter(|entry| entry.path().is_file())
+        .filter_map(|entry| entry.file_name().to_str().map(|name| name.to_string()))
+        .filter(|name| is_image_file(name))
+        .collect();
+    images.sort_by(|a, b| natural_cmp(a, b));
+    Ok(images)
+}
+
+fn hash_file_into(context: &mut md5::Context, path: &Path) -> io::Result<()> {
+    let mut file = File::open(path)?;
+    let mut buffer = vec![0u8; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        context.consume(&buffer[..read]);
+    }
+    Ok(())
+}
+
+pub fn compute_content_hash(path: &Path) -> io::Result<String> {
+    let mut context = md5::Context::new();
+    if path.is_dir() {
+        for image in list_folder_images(path)? {
+            hash_file_into(&mut context, &path.join(image))?;
+        }
+    } else {
+        hash_file_into(&mut context, path)?;
+    }
+    Ok(format!("{:x}", context.finalize()))
+}
+
+pub fn compute_perceptual_hash(
+    image_path: &Path,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let img = ImageReader::open(image_path)?
+        .with_guessed_format()?
+        .decode()?;
+    let small_img = img.resize_exact(9, 8, FilterType::Triangle).to_luma8();
+    let mut hash: u64 = 0;
+    for y in 0..8 {
+        for x in 0..8 {
+            let left = small_img.get_pixel(x, y)[0];
+            let right = small_img.get_pixel(x + 1, y)[0];
+            hash = (hash << 1) | u64::from(left > right);
+        }
+    }
+    Ok(format!("{:016x}", hash))
+}
+
+pub fn parse_cover_hash(hash: &str) -> Option<u64> {
+    u64::from_str_radix(hash, 16).ok()
+}
+
+pub fn hamming_distance(a: u64, b: u64) -> u32 {
+    (a ^ b).count_ones()
+}
+
+pub fn get_book_format(path: &Path) -> String {
+    if path.is_dir() {
+        "folder".to_string()
+    } else {
+        path.extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_lowercase())
+            .unwrap_or_default()
+    }
+}
+
+async fn compute_first_page_hash(
+    book_path: &Path,
+    book_id: &str,
+    work_dir: &Path,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let format = get_book_format(book_path);
+    extract_first_image(
+        book_path.to_string_lossy().to_string(),
+        work_dir.to_string_lossy().to_string(),
+        &format,
+        book_id,
+    )
+    .await?;
+    let first_page = work_dir.join(format!("{}.jpg", book_id));
+    let page_path = first_page.clone();
+    let hash = tokio::task::spawn_blocking(move || compute_perceptual_hash(&page_path)).await?;
+    let _ = fs::remove_file(&first_page);
+    hash
+}
+
+pub async fn compute_missing_hashes(
+    db_pool: SqlitePool,
+    token: String,
+    progress_status: Arc<Mutex<AppGlobalVariables>>,
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let rows = sqlx::query(
+        "SELECT ID_book, PATH FROM Books WHERE contentHash IS NULL OR coverHash IS NULL;",
+    )
+    .fetch_all(&db_pool)
+    .await?;
+    let total = rows.len().max(1);
+    let work_dir =
+        std::env::temp_dir().join(format!("cosmic_comics_hash_{}", generate_random_id()));
+    fs::create_dir_all(&work_dir)?;
+
+    for (i, row) in rows.iter().enumerate() {
+        let book_id: String = row.try_get("ID_book")?;
+        let path: String = row.try_get("PATH")?;
+        progress_status.lock().await.set_progress_status(
+            token.clone(),
+            "duplicates".to_string(),
+            "loading".to_string(),
+            ((i * 100) / total).to_string(),
+            path.clone(),
+        );
+
+        let book_path = Path::new(&path);
+        if !book_path.exists() {
+            continue;
+        }
+        let hash_path = book_path.to_path_buf();
+        let content_hash = tokio::task::spawn_blocking(move || compute_content_hash(&hash_path))
+            .await
+            .unwrap_or_else(|e| Err(io::Error::other(e)));
+        let content_hash = match content_hash {
+            Ok(hash) => hash,
+            Err(e) => {
+                error!("Failed to hash {}: {}", path, e);
+                continue;
+            }
+        };
+        let cover_hash = match compute_first_page_hash(book_path, &book_id, &work_dir).await {
+            Ok(hash) => Some(hash),
+            Err(e) => {
+                error!("Failed to hash the first page of {}: {}", path, e);
+                None
+            }
+        };
+
+        sqlx::query("UPDATE Books SET contentHash = ?, coverHash = ? WHERE ID_book = ?;")
+            .bind(content_hash)
+            .bind(cover_hash)
+            .bind(&book_id)
+            .execute(&db_pool)
+            .await?;
+    }
+
+    let _ = fs::remove_dir_all(&work_dir);
+    progress_status.lock().await.set_progress_status(
+        token,
+        "duplicates".to_string(),
+        "done".to_string(),
+        "100".to_string(),
+        "All books hashed.".to_string(),
+    );
+    info!("Hashed {} books", rows.len());
+    Ok(())
+}
+
+fn find_root(parents: &mut [usize], i: usize) -> usize {
+    let mut root = i;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    parents[i] = root;
+    root
+}
+
+pub async fn find_duplicates(db_pool: &SqlitePool) -> Result<DuplicateGroups, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT ID_book, NOM, PATH, fileSize, contentHash, coverHash FROM Books \
+         WHERE contentHash IS NOT NULL AND NOT missing;",
+    )
+    .fetch_all(db_pool)
+    .await?;
+
+    let mut books = Vec::new();
+    let mut content_hashes = Vec::new();
+    let mut cover_hashes = Vec::new();
+    for row in rows {
+        let path: String = row.try_get("PATH")?;
+        let book_path = Path::new(&path);
+        books.push(DuplicateBook {
+            id: row.try_get("ID_book")?,
+            name: row.try_get("NOM")?,
+            size: row.try_get::<Option<i64>, _>("fileSize")?.unwrap_or(0) as u64,
+            format: get_book_format(book_path),
+            path,
+        });
+        content_hashes.push(row.try_get::<String, _>("contentHash")?);
+        cover_hashes.push(
+            row.try_get::<Option<String>, _>("coverHash")?
+                .and_then(|hash| parse_cover_hash(&hash)),
+        );
+    }
+
+    let mut exact_groups: HashMap<&str, Vec<usize>> = HashMap::new();
+    for (i, hash) in content_hashes.iter().enumerate() {
+        if !hash.is_empty() {
+            exact_groups.entry(hash.as_str()).or_default().push(i);
+        }
+    }
+
+    let mut parents: Vec<usize> = (0..books.len()).collect();
+    for i in 0..books.len() {
+        let Some(cover_i) = cover_hashes[i] else {
+            continue;
+        };
+        for j in (i + 1)..books.len() {
+            let Some(cover_j) = cover_hashes[j] else {
+                continue;
+            };
+            if content_hashes[i] == content_hashes[j] {
+                continue;
+            }
+            if hamming_distance(cover_i, cover_j) <= SIMILAR_COVER_MAX_DISTANCE {
+                let (root_i, root_j) = (find_root(&mut parents, i), find_root(&mut parents, j));
+                parents[root_j] = root_i;
+            }
+        }
+    }
+    let mut similar_groups: HashMap<usize, Vec<usize>> = HashMap::new();
+    for i in 0..books.len() {
+        let root = find_root(&mut parents, i);
+        similar_groups.entry(root).or_default().push(i);
+    }
+
+    let to_books = |indexes: Vec<usize>| -> Vec<DuplicateBook> {
+        indexes.into_iter().map(|i| books[i].clone()).collect()
+    };
+    let mut exact: Vec<Vec<DuplicateBook>> = exact_groups
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .map(to_books)
+        .collect();
+    let mut similar: Vec<Vec<DuplicateBook>> = similar_groups
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .map(to_books)
+        .collect();
+    exact.sort_by(|a, b| a[0].path.cmp(&b[0].path));
+    similar.sort_by(|a, b| a[0].path.cmp(&b[0].path));
+
+    Ok(DuplicateGroups { exact, similar })
+}
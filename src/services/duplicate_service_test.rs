@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use crate::AppGlobalVariables;
+    use crate::services::duplicate_service::*;
+    use sqlx::SqlitePool;
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::fs::{self, File};
+    use std::io::Write;
+    use std::path::Path;
+    use std::sync::Arc;
+    use tempfile::tempdir;
+    use tokio::sync::Mutex;
+    use zip::write::FileOptions;
+
+    fn create_test_image(path: &Path, reversed: bool) {
+        let img = image::RgbImage::from_fn(64, 64, |x, _| {
+            let shade = if reversed { 255 - x * 4 } else { x * 4 };
+            image::Rgb([shade as u8, shade as u8, 128])
+        });
+        img.save(path).unwrap();
+    }
+
+    fn create_test_cbz(path: &Path, image_path: &Path) {
+        let mut zip = zip::ZipWriter::new(File::create(path).unwrap());
+        let options: zip::write::FileOptions<()> =
+            FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        zip.start_file("page1.png", options).unwrap();
+        zip.write_all(&fs::read(image_path).unwrap()).unwrap();
+        zip.finish().unwrap();
+    }
+
+    async fn setup_books_table() -> SqlitePool {
+        let db = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::query(
+            "CREATE TABLE Books (ID_book TEXT, NOM TEXT, PATH TEXT, fileSize INTEGER, \
+             missing BOOLEAN DEFAULT false, contentHash TEXT, coverHash TEXT);",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        db
+    }
+
+    #[test]
+    fn test_compute_content_hash_matches_identical_files() {
+        let temp = tempdir().unwrap();
+        fs::write(temp.path().join("a.cbz"), b"same content").unwrap();
+        fs::write(temp.path().join("b.cbr"), b"same content").unwrap();
+        fs::write(temp.path().join("c.cbz"), b"other content").unwrap();
+
+        let a = compute_content_hash(&temp.path().join("a.cbz")).unwrap();
+        let b = compute_content_hash(&temp.path().join("b.cbr")).unwrap();
+        let c = compute_content_hash(&temp.path().join("c.cbz")).unwrap();
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_perceptual_hash_is_stable_across_formats() {
+        let temp = tempdir().unwrap();
+        create_test_image(&temp.path().join("cover.png"), false);
+        create_test_image(&temp.path().join("cover.jpg"), false);
+        create_test_image(&temp.path().join("other.png"), true);
+
+        let hash = |name: &str| {
+            parse_cover_hash(&compute_perceptual_hash(&temp.path().join(name)).unwrap()).unwrap()
+        };
+        let (png, jpg, other) = (hash("cover.png"), hash("cover.jpg"), hash("other.png"));
+
+        assert!(hamming_distance(png, jpg) <= SIMILAR_COVER_MAX_DISTANCE);
+        assert!(hamming_distance(png, other) > SIMILAR_COVER_MAX_DISTANCE);
+    }
+
+    #[tokio::test]
+    async fn test_find_duplicates_groups_exact_and_similar_books() {
+        let db = setup_books_table().await;
+        for (id, path, missing, content_hash, cover_hash) in [
+            ("1", "/lib1/Saga 001.cbz", false, "aaa", "ffff000000000000"),
+            ("2", "/lib2/Saga 001.cbz", false, "aaa", "ffff000000000000"),
+            ("3", "/lib2/Saga 001.cbr", false, "bbb", "ffff000000000001"),
+            ("4", "/lib1/Other.cbz", false, "ccc", "0000ffffffff0000"),
+            ("5", "/lib3/Saga 001.cbz", true, "aaa", "ffff000000000000"),
+        ] {
+            sqlx::query(
+                "INSERT INTO Books (ID_book, NOM, PATH, fileSize, missing, contentHash, coverHash) \
+                 VALUES (?, ?, ?, 1024, ?, ?, ?);",
+            )
+            .bind(id)
+            .bind(id)
+            .bind(path)
+            .bind(missing)
+            .bind(content_hash)
+            .bind(cover_hash)
+            .execute(&db)
+            .await
+            .unwrap();
+        }
+
+        let duplicates = find_duplicates(&db).await.unwrap();
+
+        assert_eq!(duplicates.exact.len(), 1);
+        assert_eq!(duplicates.exact[0].len(), 2);
+        assert_eq!(duplicates.exact[0][0].size, 1024);
+        assert_eq!(duplicates.similar.len(), 1);
+        let mut similar_ids: Vec<&str> = duplicates.similar[0]
+            .iter()
+            .map(|book| book.id.as_str())
+            .collect();
+        similar_ids.sort();
+        assert_eq!(similar_ids, vec!["1", "2", "3"]);
+        assert!(
+            duplicates.similar[0]
+                .iter()
+                .any(|book| book.format == "cbr")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compute_missing_hashes_fills_books() {
+        let temp = tempdir().unwrap();
+        let image_path = temp.path().join("page.png");
+        create_test_image(&image_path, false);
+        let first_copy = temp.path().join("first.cbz");
+        let second_copy = temp.path().join("second.cbz");
+        create_test_cbz(&first_copy, &image_path);
+        fs::copy(&first_copy, &second_copy).unwrap();
+
+        let db = setup_books_table().await;
+        for (id, path) in [("1", &first_copy), ("2", &second_copy)] {
+            sqlx::query("INSERT INTO Books (ID_book, NOM, PATH) VALUES (?, ?, ?);")
+                .bind(id)
+                .bind(id)
+                .bind(path.to_str().unwrap())
+                .execute(&db)
+                .await
+                .unwrap();
+        }
+
+        let progress = Arc::new(Mutex::new(AppGlobalVariables::default()));
+        compute_missing_hashes(db.clone(), "token".to_string(), progress.clone())
+            .await
+            .unwrap();
+
+        let hashes: Vec<(String, String)> =
+            sqlx::query_as("SELECT contentHash, coverHash FROM Books ORDER BY ID_book;")
+                .fetch_all(&db)
+                .await
+                .unwrap();
+        assert_eq!(hashes[0], hashes[1]);
+        assert_eq!(hashes[0].1.len(), 16);
+        assert_eq!(
+            progress.lock().await.progress_status["token"]["duplicates"]["status"],
+            "done"
+        );
+    }
+}
@@ -0,0 +1,329 @@
+use crate::services::smart_collection_service::{
+    SmartPage, SmartQuery, SmartRules, SmartTarget, field_expr, push_page, push_rules_filter,
+    row_to_smart_item, validate_rules,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::{QueryBuilder, Row, Sqlite, SqlitePool};
+use std::collections::{BTreeMap, HashMap};
+
+pub const MAX_FACET_VALUES: i64 = 50;
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct BrowseRequest {
+    #[serde(flatten)]
+    pub rules: SmartRules,
+    #[serde(default)]
+    pub facets: HashMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FacetValue {
+    pub value: String,
+    pub label: String,
+    pub count: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct BrowsePage {
+    #[serde(flatten)]
+    pub page: SmartPage,
+    pub facets: BTreeMap<&'static str, Vec<FacetValue>>,
+}
+
+struct FacetDef {
+    name: &'static str,
+    from: Option<&'static str>,
+    condition: &'static str,
+    value: &'static str,
+    label: Option<&'static str>,
+}
+
+const BOOK_FACETS: &[FacetDef] = &[
+    FacetDef {
+        name: "genres",
+        from: Some(
+            "json_each((SELECT CASE WHEN json_valid(Series.genres) AND json_type(Series.genres) = 'array' \
+             THEN Series.genres ELSE '[]' END FROM Series WHERE Series.ID_Series = Books.ID_Series)) AS facet",
+        ),
+        condition: "facet.type = 'text'",
+        value: "facet.value",
+        label: None,
+    },
+    FacetDef {
+        name: "publishers",
+        from: None,
+        condition: "1",
+        value: "NULLIF(Books.publisher, '')",
+        label: None,
+    },
+    FacetDef {
+        name: "creators",
+        from: Some(
+            "json_tree(CASE WHEN json_valid(Books.creators) THEN Books.creators ELSE json_array(Books.creators) END) AS facet",
+        ),
+        condition: "facet.type = 'text' AND (facet.key IN ('name', 'full') OR typeof(facet.key) = 'integer')",
+        value: "facet.value",
+        label: None,
+    },
+    FacetDef {
+        name: "years",
+        from: None,
+        condition: "1",
+        value: "CAST(NULLIF({year}, 0) / 10 * 10 AS TEXT)",
+        label: Some("CAST(NULLIF({year}, 0) / 10 * 10 AS TEXT) || 's'"),
+    },
+    FacetDef {
+        name: "status",
+        from: None,
+        condition: "1",
+        value: "(CASE WHEN Books.read IN (1, 'true') THEN 'read' \
+                WHEN Books.reading IN (1, 'true') THEN 'reading' ELSE 'unread' END)",
+        label: None,
+    },
+    FacetDef {
+        name: "formats",
+        from: None,
+        condition: "1",
+        value: "NULLIF(NULLIF(Books.format, ''), 'null')",
+        label: None,
+    },
+    FacetDef {
+        name: "providers",
+        from: None,
+        condition: "1",
+        value: "Books.API_ID",
+        label: Some("(SELECT API.NOM FROM API WHERE API.ID_API = Books.API_ID)"),
+    },
+    FacetDef {
+        name: "libraries",
+        from: Some("Libraries AS facet"),
+        condition: "substr(Books.PATH, 1, length(facet.PATH)) = facet.PATH",
+        value: "CAST(facet.ID_LIBRARY AS TEXT)",
+        label: Some("facet.NAME"),
+    },
+];
+
+const SERIES_FACETS: &[FacetDef] = &[
+    FacetDef {
+        name: "genres",
+        from: Some(
+            "json_each(CASE WHEN json_valid(Series.genres) AND json_type(Series.genres) = 'array' \
+             THEN Series.genres ELSE '[]' END) AS facet",
+        ),
+        condition: "facet.type = 'text'",
+        value: "facet.value",
+        label: None,
+    },
+    FacetDef {
+        name: "publishers",
+        from: Some("Books AS facet"),
+        condition: "facet.ID_Series = Series.ID_Series",
+        value: "NULLIF(facet.publisher, '')",
+        label: None,
+    },
+    FacetDef {
+        name: "creators",
+        from: Some(
+            "json_tree(CASE WHEN json_valid(Series.STAFF) THEN Series.STAFF ELSE json_array(Series.STAFF) END) AS facet",
+        ),
+        condition: "facet.type = 'text' AND (facet.key IN ('name', 'full') OR typeof(facet.key) = 'integer')",
+        value: "facet.value",
+        label: None,
+    },
+    FacetDef {
+        name: "years",
+        from: None,
+        condition: "1",
+        value: "CAST(NULLIF({year}, 0) / 10 * 10 AS TEXT)",
+        label: Some("CAST(NULLIF({year}, 0) / 10 * 10 AS TEXT) || 's'"),
+    },
+    FacetDef {
+        name: "status",
+        from: None,
+        condition: "1",
+        value: "(CASE WHEN NOT EXISTS (SELECT 1 FROM Books WHERE Books.ID_Series = Series.ID_Series) THEN NULL \
+                WHEN NOT EXISTS (SELECT 1 FROM Books WHERE Books.ID_Series = Series.ID_Series \
+                AND Books.read NOT IN (1, 'true')) THEN 'read' \
+                WHEN EXISTS (SELECT 1 FROM Books WHERE Books.ID_Series = Series.ID_Series \
+                AND (Books.read IN (1, 'true') OR Books.reading IN (1, 'true'))) THEN 'reading' \
+                ELSE 'unread' END)",
+        label: None,
+    },
+    FacetDef {
+        name: "providers",
+        from: Some("Books AS facet"),
+        condition: "facet.ID_Series = Series.ID_Series",
+        value: "facet.API_ID",
+        label: Some("(SELECT API.NOM FROM API WHERE API.ID_API = facet.API_ID)"),
+    },
+    FacetDef {
+        name: "libraries",
+        from: Some("Libraries AS facet"),
+        condition: "substr(Series.PATH, 1, length(facet.PATH)) = facet.PATH",
+        value: "CAST(facet.ID_LIBRARY AS TEXT)",
+        label: Some("facet.NAME"),
+    },
+];
+
+fn get_facets(target: SmartTarget) -> &'static [FacetDef] {
+    match target {
+        SmartTarget::Books => BOOK_FACETS,
+        SmartTarget::Series => SERIES_FACETS,
+    }
+}
+
+fn expand(expr: &str, target: SmartTarget) -> String {
+    expr.replace("{year}", field_expr(target, "year").unwrap_or("NULL"))
+}
+
+fn push_facet_filter(
+    qb: &mut QueryBuilder<'_, Sqlite>,
+    target: SmartTarget,
+    facet: &FacetDef,
+    values: &[String],
+) {
+    let value = expand(facet.value, target);
+    match facet.from {
+        Some(from) => {
+            qb.push(format!(
+                "EXISTS (SELECT 1 FROM {} WHERE {} AND {} IN (",
+                from, facet.condition, value
+            ));
+        }
+        None => {
+            qb.push(format!("{} IN (", value));
+        }
+    }
+    let mut separated = qb.separated(", ");
+    for value in values {
+        separated.push_bind(value.clone());
+    }
+    qb.push(if facet.from.is_some() { "))" } else { ")" });
+}
+
+fn push_filters(qb: &mut QueryBuilder<'_, Sqlite>, request: &BrowseRequest, skip: Option<&str>) {
+    let target = request.rules.target;
+    qb.push(" WHERE ");
+    push_rules_filter(qb, &request.rules);
+    for facet in get_facets(target) {
+        if skip == Some(facet.name) {
+            continue;
+        }
+        if let Some(values) = request
+            .facets
+            .get(facet.name)
+            .filter(|values| !values.is_empty())
+        {
+            qb.push(" AND ");
+            push_facet_filter(qb, target, facet, values);
+        }
+    }
+}
+
+pub fn validate_browse_request(request: &BrowseRequest) -> Result<(), String> {
+    validate_rules(&request.rules)?;
+    let facets = get_facets(request.rules.target);
+    for name in request.facets.keys() {
+        if !facets.iter().any(|facet| facet.name == name) {
+            return Err(format!("Unknown facet: {}", name));
+        }
+    }
+    Ok(())
+}
+
+async fn count_facet(
+    db_pool: &SqlitePool,
+    request: &BrowseRequest,
+    facet: &FacetDef,
+) -> Result<Vec<FacetValue>, sqlx::Error> {
+    let target = request.rules.target;
+    let table = target.table();
+    let value = expand(facet.value, target);
+    let label = facet
+        .label
+        .map(|label| expand(label, target))
+        .unwrap_or_else(|| value.clone());
+    let from = facet
+        .from
+        .map(|from| format!("{}, {}", table, from))
+        .unwrap_or_else(|| table.to_string());
+    let mut qb = QueryBuilder::<Sqlite>::new(format!(
+        "SELECT {} AS facet_value, {} AS facet_label, COUNT(DISTINCT {}) AS facet_count FROM {}",
+        value,
+        label,
+        target.key(),
+        from
+    ));
+    push_filters(&mut qb, request, Some(facet.name));
+    qb.push(format!(
+        " AND {} AND facet_value IS NOT NULL GROUP BY facet_value \
+         ORDER BY facet_count DESC, facet_value LIMIT ",
+        facet.condition
+    ))
+    .push_bind(MAX_FACET_VALUES);
+    qb.build()
+        .fetch_all(db_pool)
+        .await?
+        .iter()
+        .map(|row| {
+            let value: String = row.try_get("facet_value")?;
+            Ok(FacetValue {
+                label: row
+                    .try_get::<Option<String>, _>("facet_label")?
+                    .unwrap_or_else(|| value.clone()),
+                value,
+                count: row.try_get("facet_count")?,
+            })
+        })
+        .collect()
+}
+
+pub async fn count_facets(
+    db_pool: &SqlitePool,
+    request: &BrowseRequest,
+    names: Option<&[&str]>,
+) -> Result<BTreeMap<&'static str, Vec<FacetValue>>, sqlx::Error> {
+    let mut facets = BTreeMap::new();
+    for facet in get_facets(request.rules.target) {
+        if names.is_none_or(|names| names.contains(&facet.name)) {
+            facets.insert(facet.name, count_facet(db_pool, request, facet).await?);
+        }
+    }
+    Ok(facets)
+}
+
+pub async fn browse(
+    db_pool: &SqlitePool,
+    request: &BrowseRequest,
+    query: &SmartQuery,
+) -> Result<BrowsePage, Box<dyn std::error::Error + Send + Sync>> {
+    validate_browse_request(request)?;
+    let table = request.rules.target.table();
+    let mut qb = QueryBuilder::<Sqlite>::new(format!("SELECT {}.* FROM {}", table, table));
+    push_filters(&mut qb, request, None);
+    let (page, limit) = push_page(&mut qb, &request.rules, query)?;
+
+    let mut count_qb = QueryBuilder::<Sqlite>::new(format!("SELECT COUNT(*) FROM {}", table));
+    push_filters(&mut count_qb, request, None);
+    let (total,): (i64,) = count_qb.build_query_as().fetch_one(db_pool).await?;
+
+    let items = qb
+        .build()
+        .fetch_all(db_pool)
+        .await?
+        .iter()
+        .map(|row| row_to_smart_item(request.rules.target, row))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let facets = count_facets(db_pool, request, None).await?;
+
+    Ok(BrowsePage {
+        page: SmartPage {
+            total,
+            page,
+            limit,
+            items,
+        },
+        facets,
+    })
+}
@@ -0,0 +1,231 @@
+#[cfg(test)]
+mod tests {
+    use crate::repositories::database_repo::make_test_db;
+    use crate::services::facet_service::*;
+    use crate::services::smart_collection_service::SmartQuery;
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use tempfile::TempDir;
+
+    async fn setup_db() -> (TempDir, SqlitePool) {
+        let (temp, pool) = make_test_db().await;
+        sqlx::query(
+            r#"INSERT INTO Libraries (NAME, PATH, API_ID) VALUES ('Comics', '/comics', '1'), ('Manga', '/manga', '2');
+               INSERT INTO Series (ID_Series, title, genres, start_date, favorite, PATH) VALUES
+               ('10_1', 'Civil War', '["Action","Superhero"]', '2006', false, '/comics/civil-war'),
+               ('20_2', 'Berserk', '["Action","Horror"]', '{"year":1989,"month":8,"day":25}', false, '/manga/berserk');"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        for (id, api, series, read, reading, creators, format, publisher, dates, path) in [
+            (
+                "b1",
+                "1",
+                "10_1",
+                1,
+                0,
+                r#"{"available":2,"items":[{"name":"Mark Millar","role":"writer"},{"name":"Steve McNiven","role":"penciller"}]}"#,
+                "Comic",
+                "Marvel",
+                r#"[{"type":"onsaleDate","date":"2006-05-03T00:00:00-0400"}]"#,
+                "/comics/civil-war/01.cbz",
+            ),
+            (
+                "b2",
+                "1",
+                "10_1",
+                0,
+                1,
+                r#"{"available":1,"items":[{"name":"Mark Millar","role":"writer"}]}"#,
+                "Comic",
+                "Marvel",
+                r#"[{"type":"onsaleDate","date":"2006-06-07T00:00:00-0400"}]"#,
+                "/comics/civil-war/02.cbz",
+            ),
+            (
+                "b3",
+                "4",
+                "20_2",
+                0,
+                0,
+                r#"["Kentaro Miura"]"#,
+                "BOOK",
+                "Dark Horse",
+                "1990-01-01",
+                "/manga/berserk/01.cbz",
+            ),
+            (
+                "b4",
+                "0",
+                "",
+                0,
+                0,
+                "Unknown Artist",
+                "",
+                "",
+                "",
+                "/misc/oneshot.cbz",
+            ),
+        ] {
+            sqlx::query(
+                "INSERT INTO Books (ID_book, API_ID, NOM, read, reading, unread, favorite, last_page, folder, PATH, ID_Series, creators, format, publisher, dates) \
+                 VALUES (?, ?, ?, ?, ?, false, false, 0, false, ?, ?, ?, ?, ?, ?);",
+            )
+            .bind(id)
+            .bind(api)
+            .bind(format!("Book {}", id))
+            .bind(read)
+            .bind(reading)
+            .bind(path)
+            .bind(series)
+            .bind(creators)
+            .bind(format)
+            .bind(publisher)
+            .bind(dates)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        (temp, pool)
+    }
+
+    fn request(value: serde_json::Value) -> BrowseRequest {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn counts(page: &BrowsePage, facet: &str) -> Vec<(String, i64)> {
+        page.facets[facet]
+            .iter()
+            .map(|value| (value.label.clone(), value.count))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_browse_books_returns_expanded_facets() {
+        let (_temp, pool) = setup_db().await;
+
+        let page = browse(
+            &pool,
+            &request(json!({ "target": "books" })),
+            &SmartQuery::default(),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(page.page.total, 4);
+        assert_eq!(
+            counts(&page, "genres"),
+            vec![
+                ("Action".to_string(), 3),
+                ("Superhero".to_string(), 2),
+                ("Horror".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            counts(&page, "creators"),
+            vec![
+                ("Mark Millar".to_string(), 2),
+                ("Kentaro Miura".to_string(), 1),
+                ("Steve McNiven".to_string(), 1),
+                ("Unknown Artist".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            counts(&page, "years"),
+            vec![("2000s".to_string(), 2), ("1990s".to_string(), 1)]
+        );
+        assert_eq!(
+            counts(&page, "status"),
+            vec![
+                ("unread".to_string(), 2),
+                ("read".to_string(), 1),
+                ("reading".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            counts(&page, "publishers"),
+            vec![("Marvel".to_string(), 2), ("Dark Horse".to_string(), 1)]
+        );
+        assert_eq!(
+            counts(&page, "libraries"),
+            vec![("Comics".to_string(), 2), ("Manga".to_string(), 1)]
+        );
+        assert_eq!(
+            counts(&page, "providers"),
+            vec![
+                ("Marvel".to_string(), 2),
+                ("MANUAL".to_string(), 1),
+                ("Google Books API".to_string(), 1)
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_browse_facet_selection_keeps_own_counts() {
+        let (_temp, pool) = setup_db().await;
+        let request = request(json!({
+            "target": "books",
+            "conditions": [{ "field": "read", "op": "eq", "value": false }],
+            "facets": { "genres": ["Horror", "Superhero"], "formats": ["Comic", "BOOK"] }
+        }));
+        let query = SmartQuery {
+            sort: Some("name".to_string()),
+            ..Default::default()
+        };
+
+        let page = browse(&pool, &request, &query).await.unwrap();
+
+        let ids: Vec<&str> = page.page.items.iter().map(|item| item.id()).collect();
+        assert_eq!(ids, vec!["b2", "b3"]);
+        assert_eq!(
+            counts(&page, "genres"),
+            vec![
+                ("Action".to_string(), 2),
+                ("Horror".to_string(), 1),
+                ("Superhero".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            counts(&page, "formats"),
+            vec![("BOOK".to_string(), 1), ("Comic".to_string(), 1)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_browse_series_and_rejects_unknown_facets() {
+        let (_temp, pool) = setup_db().await;
+
+        let page = browse(
+            &pool,
+            &request(json!({ "target": "series", "facets": { "years": ["1980"] } })),
+            &SmartQuery::default(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(page.page.items[0].id(), "20_2");
+        assert_eq!(
+            counts(&page, "years"),
+            vec![("1980s".to_string(), 1), ("2000s".to_string(), 1)]
+        );
+        assert_eq!(counts(&page, "status"), vec![("unread".to_string(), 1)]);
+        assert_eq!(
+            counts(&page, "providers"),
+            vec![("Google Books API".to_string(), 1)]
+        );
+        assert_eq!(
+            counts(&page, "publishers"),
+            vec![("Dark Horse".to_string(), 1)]
+        );
+
+        assert!(
+            browse(
+                &pool,
+                &request(json!({ "target": "series", "facets": { "formats": ["Comic"] } })),
+                &SmartQuery::default(),
+            )
+            .await
+            .is_err()
+        );
+    }
+}
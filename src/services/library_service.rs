@@ -0,0 +1,644 @@
+use crate::AppGlobalVariables;
+use crate::repositories::database_repo::{insert_into_db, update_db};
+use crate::services::book_service::fill_blank_images;
+use crate::services::collectionner_service::refresh_item_metadata;
+use crate::services::parser_service::{
+    get_series_title, normalize_title, parse_book_name, parse_book_path,
+};
+use crate::utils::{
+    VALID_BOOK_EXTENSION, VALID_IMAGE_EXTENSION, generate_stable_id, is_image_file, natural_cmp,
+};
+use serde::Serialize;
+use sqlx::{Row, SqlitePool};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::os::unix::fs::MetadataExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tracing::{error, info};
+use walkdir::WalkDir;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LibraryEntry {
+    pub path: PathBuf,
+    pub folder: bool,
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct ScanReport {
+    pub books_added: usize,
+    pub books_updated: usize,
+    pub books_moved: usize,
+    pub books_unchanged: usize,
+    pub books_missing: usize,
+    pub series_added: usize,
+}
+
+fn is_hidden(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.starts_with('.'))
+}
+
+fn is_book_file(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| VALID_BOOK_EXTENSION.contains(&e.to_lowercase().as_str()))
+}
+
+fn is_image_folder(dir: &Path) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    let mut has_images = false;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if is_hidden(&path) {
+            continue;
+        }
+        if path.is_dir() || is_book_file(&path) {
+            return false;
+        }
+        if is_image_file(&entry.file_name().to_string_lossy()) {
+            has_images = true;
+        }
+    }
+    has_images
+}
+
+pub fn find_library_entries(library_path: &Path) -> Vec<LibraryEntry> {
+    let mut entries = Vec::new();
+    let mut walker = WalkDir::new(library_path)
+        .min_depth(1)
+        .sort_by(|a, b| {
+            natural_cmp(
+                &a.file_name().to_string_lossy(),
+                &b.file_name().to_string_lossy(),
+            )
+        })
+        .into_iter();
+    while let Some(entry) = walker.next() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                error!("Failed to read library entry: {}", e);
+                continue;
+            }
+        };
+        let is_dir = entry.file_type().is_dir();
+        if is_hidden(entry.path()) {
+            if is_dir {
+                walker.skip_current_dir();
+            }
+            continue;
+        }
+        if is_dir {
+            if is_image_folder(entry.path()) {
+                entries.push(LibraryEntry {
+                    path: entry.path().to_path_buf(),
+                    folder: true,
+                });
+                walker.skip_current_dir();
+            }
+        } else if is_book_file(entry.path()) {
+            entries.push(LibraryEntry {
+                path: entry.path().to_path_buf(),
+                folder: false,
+            });
+        }
+    }
+    entries
+}
+
+pub fn get_book_id(path: &Path, api_id: &str) -> String {
+    format!("{}_{}", generate_stable_id(&path.to_string_lossy()), api_id)
+}
+
+pub fn get_series_id(key: &str) -> String {
+    format!("{}U_0", generate_stable_id(key))
+}
+
+fn is_manual_series(id: &str) -> bool {
+    id.ends_with("U_0")
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FileStats {
+    pub size: i64,
+    pub modified: i64,
+    pub inode: i64,
+}
+
+struct KnownBook {
+    id: String,
+    stats: Option<FileStats>,
+    missing: bool,
+    series_id: Option<String>,
+}
+
+#[derive(Default)]
+struct KnownSeries {
+    by_path: HashMap<String, String>,
+    ids: HashSet<String>,
+}
+
+pub fn get_file_stats(path: &Path) -> Option<FileStats> {
+    let metadata = fs::metadata(path).ok()?;
+    Some(FileStats {
+        size: metadata.size() as i64,
+        modified: metadata.mtime(),
+        inode: metadata.ino() as i64,
+    })
+}
+
+async fn get_existing_paths(
+    db_pool: &SqlitePool,
+    table: &str,
+) -> Result<HashSet<String>, sqlx::Error> {
+    let rows = sqlx::query(&format!("SELECT PATH FROM {};", table))
+        .fetch_all(db_pool)
+        .await?;
+    rows.iter().map(|row| row.try_get("PATH")).collect()
+}
+
+async fn get_known_books(
+    db_pool: &SqlitePool,
+    library_path: &Path,
+) -> Result<HashMap<String, KnownBook>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT ID_book, PATH, fileSize, fileModified, fileInode, missing, ID_Series FROM Books;",
+    )
+    .fetch_all(db_pool)
+    .await?;
+    let mut books = HashMap::new();
+    for row in rows {
+        let path: String = row.try_get("PATH")?;
+        if !Path::new(&path).starts_with(library_path) {
+            continue;
+        }
+        let size: Option<i64> = row.try_get("fileSize")?;
+        let modified: Option<i64> = row.try_get("fileModified")?;
+        let inode: Option<i64> = row.try_get("fileInode")?;
+        let stats = match (size, modified, inode) {
+            (Some(size), Some(modified), Some(inode)) => Some(FileStats {
+                size,
+                modified,
+                inode,
+            }),
+            _ => None,
+        };
+        books.insert(
+            path,
+            KnownBook {
+                id: row.try_get("ID_book")?,
+                stats,
+                missing: row.try_get("missing")?,
+                series_id: row.try_get("ID_Series")?,
+            },
+        );
+    }
+    Ok(books)
+}
+
+async fn get_known_series(db_pool: &SqlitePool) -> Result<KnownSeries, sqlx::Error> {
+    let rows = sqlx::query("SELECT ID_Series, PATH FROM Series;")
+        .fetch_all(db_pool)
+        .await?;
+    let mut series = KnownSeries::default();
+    for row in rows {
+        let id: String = row.try_get("ID_Series")?;
+        let path: String = row.try_get("PATH")?;
+        let replace_manual = series
+            .by_path
+            .get(&path)
+            .is_none_or(|existing| is_manual_series(existing) && !is_manual_series(&id));
+        if replace_manual {
+            series.by_path.insert(path, id.clone());
+        }
+        series.ids.insert(id);
+    }
+    Ok(series)
+}
+
+async fn ensure_series(
+    db_pool: &SqlitePool,
+    library_path: &Path,
+    book_path: &Path,
+    known_series: &mut KnownSeries,
+    report: &mut ScanReport,
+) -> Result<Option<String>, sqlx::Error> {
+    let Some(parent) = book_path.parent() else {
+        return Ok(None);
+    };
+    let in_folder = parent != library_path;
+    let (series_id, title, series_path) = if in_folder {
+        let folder = parent.to_string_lossy().to_string();
+        if let Some(id) = known_series.by_path.get(&folder) {
+            return Ok(Some(id.clone()));
+        }
+        let title = parent
+            .file_name()
+            .map(|name| get_series_title(&name.to_string_lossy()))
+            .unwrap_or_default();
+        (get_series_id(&folder), title, folder)
+    } else {
+        let file_name = book_path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let title = parse_book_name(&file_name).series;
+        if title.is_empty() {
+            return Ok(None);
+        }
+        let root = library_path.to_string_lossy().to_string();
+        let key = format!("{}#{}", root, normalize_title(&title));
+        (get_series_id(&key), title, root)
+    };
+
+    if known_series.ids.insert(series_id.clone()) {
+        insert_into_db(
+            db_pool,
+            "Series",
+            Some(vec![
+                "ID_Series".to_string(),
+                "title".to_string(),
+                "favorite".to_string(),
+                "PATH".to_string(),
+            ]),
+            vec![
+                series_id.clone(),
+                title,
+                "0".to_string(),
+                series_path.clone(),
+            ],
+        )
+        .await?;
+        report.series_added += 1;
+        if in_folder {
+            known_series.by_path.insert(series_path, series_id.clone());
+        }
+    }
+    Ok(Some(series_id))
+}
+
+pub async fn adopt_manual_series(
+    db_pool: &SqlitePool,
+    series_path: &str,
+    series_id: &str,
+) -> Result<u64, sqlx::Error> {
+    let rows = sqlx::query("SELECT ID_Series FROM Series WHERE PATH = ? AND ID_Series != ?;")
+        .bind(series_path)
+        .bind(series_id)
+        .fetch_all(db_pool)
+        .await?;
+    let mut tx = db_pool.begin().await?;
+    let mut relinked = 0;
+    for row in rows {
+        let manual_id: String = row.try_get("ID_Series")?;
+        if !is_manual_series(&manual_id) {
+            continue;
+        }
+        relinked += sqlx::query("UPDATE Books SET ID_Series = ? WHERE ID_Series = ?;")
+            .bind(series_id)
+            .bind(&manual_id)
+            .execute(&mut *tx)
+            .await?
+            .rows_affected();
+        sqlx::query("DELETE FROM Series WHERE ID_Series = ?;")
+            .bind(&manual_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(relinked)
+}
+
+async fn update_book_file(
+    db_pool: &SqlitePool,
+    book_id: &str,
+    path: &str,
+    stats: Option<FileStats>,
+    content_changed: bool,
+) -> Result<(), sqlx::Error> {
+    let mut query =
+        "UPDATE Books SET PATH = ?, fileSize = ?, fileModified = ?, fileInode = ?, missing = false"
+            .to_string();
+    if content_changed {
+        query.push_str(
+            ", contentHash = NULL, coverHash = NULL, coverPlaceholder = NULL, \
+             URLCover = CASE WHEN URLCover LIKE 'http%' THEN URLCover ELSE NULL END",
+        );
+    }
+    query.push_str(" WHERE ID_book = ?;");
+    sqlx::query(&query)
+        .bind(path)
+        .bind(stats.map(|s| s.size))
+        .bind(stats.map(|s| s.modified))
+        .bind(stats.map(|s| s.inode))
+        .bind(book_id)
+        .execute(db_pool)
+        .await?;
+    Ok(())
+}
+
+pub async fn scan_library(
+    db_pool: &SqlitePool,
+    library_id: i64,
+    token: &str,
+    progress_status: &Arc<Mutex<AppGlobalVariables>>,
+) -> Result<ScanReport, Box<dyn std::error::Error + Send + Sync>> {
+    let library = sqlx::query("SELECT PATH, API_ID FROM Libraries WHERE ID_LIBRARY = ?;")
+        .bind(library_id)
+        .fetch_optional(db_pool)
+        .await?
+        .ok_or_else(|| format!("Library {} not found", library_id))?;
+    let library_path: String = library.try_get("PATH")?;
+    let api_id: String = library.try_get("API_ID")?;
+    let library_path = Path::new(&library_path);
+    if !library_path.is_dir() {
+        return Err(format!("Library path {} is not a directory", library_path.display()).into());
+    }
+
+    let entries = find_library_entries(library_path);
+    let known_books = get_known_books(db_pool, library_path).await?;
+    let known_inodes: HashMap<i64, &str> = known_books
+        .iter()
+        .filter_map(|(path, book)| book.stats.map(|stats| (stats.inode, path.as_str())))
+        .collect();
+    let mut other_books = get_existing_paths(db_pool, "Books").await?;
+    let mut known_series = get_known_series(db_pool).await?;
+    let mut seen_books = HashSet::new();
+    let mut report = ScanReport::default();
+    let total = entries.len().max(1);
+
+    for (i, entry) in entries.iter().enumerate() {
+        let path = entry.path.to_string_lossy().to_string();
+        progress_status.lock().await.set_progress_status(
+            token.to_string(),
+            "scan".to_string(),
+            "loading".to_string(),
+            ((i * 100) / total).to_string(),
+            path.clone(),
+        );
+
+        let series_id = ensure_series(
+            db_pool,
+            library_path,
+            &entry.path,
+            &mut known_series,
+            &mut report,
+        )
+        .await?;
+
+        let stats = get_file_stats(&entry.path);
+        if let Some(known) = known_books.get(&path) {
+            seen_books.insert(path.clone());
+            if known.series_id.is_none()
+                && let Some(series_id) = &series_id
+            {
+                update_db(
+                    db_pool,
+                    "edit",
+                    vec!["ID_Series".to_string()],
+                    vec![series_id.clone()],
+                    "Books",
+                    "ID_book",
+                    &known.id,
+                )
+                .await?;
+            }
+            if known.stats.is_some() && known.stats == stats && !known.missing {
+                report.books_unchanged += 1;
+                continue;
+            }
+            let content_changed = known.stats.is_some_and(|old| {
+                Some(old.size) != stats.map(|s| s.size)
+                    || Some(old.modified) != stats.map(|s| s.modified)
+            });
+            update_book_file(db_pool, &known.id, &path, stats, content_changed).await?;
+            report.books_updated += 1;
+            continue;
+        }
+
+        let moved_from = stats.and_then(|stats| {
+            known_inodes
+                .get(&stats.inode)
+                .filter(|old_path| {
+                    !seen_books.contains(**old_path) && !Path::new(old_path).exists()
+                })
+                .filter(|old_path| {
+                    known_books[**old_path].stats.map(|s| s.size) == Some(stats.size)
+                })
+        });
+        if let Some(old_path) = moved_from {
+            seen_books.insert(old_path.to_string());
+            update_book_file(db_pool, &known_books[*old_path].id, &path, stats, false).await?;
+            report.books_moved += 1;
+            continue;
+        }
+
+        if !other_books.insert(path.clone()) {
+            report.books_unchanged += 1;
+            continue;
+        }
+        let name = if entry.folder {
+            entry.path.file_name()
+        } else {
+            entry.path.file_stem()
+        }
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+        let mut columns = vec![
+            "ID_book".to_string(),
+            "API_ID".to_string(),
+            "NOM".to_string(),
+            "read".to_string(),
+            "reading".to_string(),
+            "unread".to_string(),
+            "favorite".to_string(),
+            "last_page".to_string(),
+            "folder".to_string(),
+            "PATH".to_string(),
+        ];
+        let mut values = vec![
+            get_book_id(&entry.path, &api_id),
+            api_id.clone(),
+            name,
+            "0".to_string(),
+            "0".to_string(),
+            "1".to_string(),
+            "0".to_string(),
+            "0".to_string(),
+            if entry.folder { "1" } else { "0" }.to_string(),
+            path,
+        ];
+        if let Some(series_id) = series_id {
+            columns.push("ID_Series".to_string());
+            values.push(series_id);
+        }
+        if let Some(issue) = parse_book_path(&entry.path).issue {
+            columns.push("issueNumber".to_string());
+            values.push(issue.to_string());
+        }
+        if let Some(stats) = stats {
+            columns.extend([
+                "fileSize".to_string(),
+                "fileModified".to_string(),
+                "fileInode".to_string(),
+            ]);
+            values.extend([
+                stats.size.to_string(),
+                stats.modified.to_string(),
+                stats.inode.to_string(),
+            ]);
+        }
+        insert_into_db(db_pool, "Books", Some(columns), values).await?;
+        report.books_added += 1;
+    }
+
+    for (path, book) in &known_books {
+        if book.missing || seen_books.contains(path) {
+            continue;
+        }
+        update_db(
+            db_pool,
+            "edit",
+            vec!["missing".to_string()],
+            vec!["1".to_string()],
+            "Books",
+            "ID_book",
+            &book.id,
+        )
+        .await?;
+        report.books_missing += 1;
+    }
+
+    progress_status.lock().await.set_progress_status(
+        token.to_string(),
+        "scan".to_string(),
+        "done".to_string(),
+        "100".to_string(),
+        format!(
+            "{} books added, {} updated, {} missing",
+            report.books_added,
+            report.books_updated + report.books_moved,
+            report.books_missing
+        ),
+    );
+    info!(
+        "Scanned library {}: {} added, {} updated, {} moved, {} unchanged, {} missing, {} series added",
+        library_id,
+        report.books_added,
+        report.books_updated,
+        report.books_moved,
+        report.books_unchanged,
+        report.books_missing,
+        report.series_added
+    );
+    Ok(report)
+}
+
+pub async fn run_library_scan(
+    db_pool: SqlitePool,
+    library_id: i64,
+    token: String,
+    progress_status: Arc<Mutex<AppGlobalVariables>>,
+    covers_dir: String,
+) -> Result<ScanReport, Box<dyn std::error::Error + Send + Sync>> {
+    match scan_library(&db_pool, library_id, &token, &progress_status).await {
+        Ok(report) => {
+            if report.books_added + report.books_updated > 0
+                && let Err(e) =
+                    fill_blank_images(db_pool, VALID_IMAGE_EXTENSION, Some(covers_dir)).await
+            {
+                error!("Error extracting covers after scan: {}", e);
+            }
+            Ok(report)
+        }
+        Err(e) => {
+            error!("Error scanning library {}: {}", library_id, e);
+            progress_status.lock().await.set_progress_status(
+                token,
+                "scan".to_string(),
+                "error".to_string(),
+                "0".to_string(),
+                e.to_string(),
+            );
+            Err(e)
+        }
+    }
+}
+
+fn get_provider(id: &str) -> Option<i32> {
+    id.rsplit('_').next()?.parse().ok()
+}
+
+pub async fn refresh_library_metadata(
+    db_pool: &SqlitePool,
+    library_id: i64,
+    marvel_priv_key: String,
+    marvel_pub_key: String,
+) -> Result<(usize, usize), Box<dyn std::error::Error + Send + Sync>> {
+    let library_path: String = sqlx::query("SELECT PATH FROM Libraries WHERE ID_LIBRARY = ?;")
+        .bind(library_id)
+        .fetch_optional(db_pool)
+        .await?
+        .ok_or_else(|| format!("Library {} not found", library_id))?
+        .try_get("PATH")?;
+    let library_path = Path::new(&library_path);
+
+    let mut items = Vec::new();
+    for row in
+        sqlx::query("SELECT ID_book, PATH FROM Books WHERE lock = false AND missing = false;")
+            .fetch_all(db_pool)
+            .await?
+    {
+        let id: String = row.try_get("ID_book")?;
+        let path: String = row.try_get("PATH")?;
+        let path = Path::new(&path);
+        let provider = get_provider(&id);
+        let matched = provider.is_some_and(|p| get_book_id(path, &p.to_string()) != id);
+        if path.starts_with(library_path) && matched {
+            items.push((id, "book", provider.unwrap_or_default()));
+        }
+    }
+    for row in sqlx::query("SELECT ID_Series, PATH FROM Series WHERE lock = false;")
+        .fetch_all(db_pool)
+        .await?
+    {
+        let id: String = row.try_get("ID_Series")?;
+        let path: String = row.try_get("PATH")?;
+        if let Some(provider) = get_provider(&id)
+            && Path::new(&path).starts_with(library_path)
+            && !id.contains("U_")
+        {
+            items.push((id, "series", provider));
+        }
+    }
+
+    let (mut refreshed, mut failed) = (0, 0);
+    for (id, item_type, provider) in items {
+        match refresh_item_metadata(
+            db_pool,
+            &id,
+            item_type,
+            provider,
+            marvel_priv_key.clone(),
+            marvel_pub_key.clone(),
+        )
+        .await
+        {
+            Ok(_) => refreshed += 1,
+            Err(e) => {
+                error!("Error refreshing metadata of {}: {}", id, e);
+                failed += 1;
+            }
+        }
+    }
+    info!(
+        "Refreshed metadata of library {}: {} refreshed, {} failed",
+        library_id, refreshed, failed
+    );
+    Ok((refreshed, failed))
+}
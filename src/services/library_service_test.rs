@@ -0,0 +1,266 @@
+#[cfg(test)]
+mod tests {
+    use crate::AppGlobalVariables;
+    use crate::repositories::database_repo::make_test_db;
+    use crate::services::library_service::*;
+    use sqlx::SqlitePool;
+    use std::fs;
+    use std::path::Path;
+    use std::sync::Arc;
+    use tempfile::{TempDir, tempdir};
+    use tokio::sync::Mutex;
+
+    fn create_library_tree(root: &Path) {
+        fs::create_dir_all(root.join("Saga/Volume 1")).unwrap();
+        fs::create_dir_all(root.join(".hidden")).unwrap();
+        fs::write(root.join("Saga/Saga 10.cbz"), b"book").unwrap();
+        fs::write(root.join("Saga/Saga 2.CBR"), b"book").unwrap();
+        fs::write(root.join("Saga/notes.txt"), b"text").unwrap();
+        fs::write(root.join("Saga/Volume 1/001.jpg"), b"page").unwrap();
+        fs::write(root.join("Saga/Volume 1/002.jpg"), b"page").unwrap();
+        fs::write(root.join(".hidden/Secret.cbz"), b"book").unwrap();
+        fs::write(root.join("Standalone.pdf"), b"book").unwrap();
+    }
+
+    async fn setup_library(library_path: &Path) -> (TempDir, SqlitePool) {
+        let (temp, pool) = make_test_db().await;
+        sqlx::query("INSERT INTO Libraries (NAME, PATH, API_ID) VALUES ('Comics', ?, '1');")
+            .bind(library_path.to_str().unwrap())
+            .execute(&pool)
+            .await
+            .unwrap();
+        (temp, pool)
+    }
+
+    #[test]
+    fn test_find_library_entries_finds_books_and_image_folders() {
+        let temp = tempdir().unwrap();
+        create_library_tree(temp.path());
+
+        let entries = find_library_entries(temp.path());
+
+        assert_eq!(
+            entries,
+            vec![
+                LibraryEntry {
+                    path: temp.path().join("Saga/Saga 2.CBR"),
+                    folder: false,
+                },
+                LibraryEntry {
+                    path: temp.path().join("Saga/Saga 10.cbz"),
+                    folder: false,
+                },
+                LibraryEntry {
+                    path: temp.path().join("Saga/Volume 1"),
+                    folder: true,
+                },
+                LibraryEntry {
+                    path: temp.path().join("Standalone.pdf"),
+                    folder: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_book_ids_are_stable() {
+        let path = Path::new("/comics/Saga/Saga 1.cbz");
+        assert_eq!(get_book_id(path, "1"), get_book_id(path, "1"));
+        assert_ne!(
+            get_book_id(path, "1"),
+            get_book_id(Path::new("/comics/Saga/Saga 2.cbz"), "1")
+        );
+        assert_eq!(get_series_id("/comics/Saga"), get_series_id("/comics/Saga"));
+        assert!(get_series_id("/comics/Saga").ends_with("U_0"));
+    }
+
+    #[tokio::test]
+    async fn test_scan_library_creates_books_and_series_once() {
+        let temp = tempdir().unwrap();
+        let library_path = temp.path().join("library");
+        create_library_tree(&library_path);
+        let (_db, pool) = setup_library(&library_path).await;
+        let progress = Arc::new(Mutex::new(AppGlobalVariables::default()));
+
+        let report = scan_library(&pool, 1, "token", &progress).await.unwrap();
+        assert_eq!(report.books_added, 4);
+        assert_eq!(report.series_added, 2);
+
+        let books: Vec<(String, String, bool)> =
+            sqlx::query_as("SELECT ID_book, NOM, folder FROM Books ORDER BY NOM;")
+                .fetch_all(&pool)
+                .await
+                .unwrap();
+        assert_eq!(books.len(), 4);
+        assert_eq!(
+            books[0].0,
+            get_book_id(&library_path.join("Saga/Saga 10.cbz"), "1")
+        );
+        assert_eq!(
+            books[3],
+            (
+                get_book_id(&library_path.join("Saga/Volume 1"), "1"),
+                "Volume 1".to_string(),
+                true
+            )
+        );
+        assert_eq!(
+            progress.lock().await.progress_status["token"]["scan"]["status"],
+            "done"
+        );
+
+        let (issue,): (Option<i64>,) =
+            sqlx::query_as("SELECT issueNumber FROM Books WHERE NOM = 'Saga 10';")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(issue, Some(10));
+
+        let rescan = scan_library(&pool, 1, "token", &progress).await.unwrap();
+        assert_eq!(rescan.books_added, 0);
+        assert_eq!(rescan.books_unchanged, 4);
+        assert_eq!(rescan.series_added, 0);
+    }
+
+    #[tokio::test]
+    async fn test_rescan_detects_changed_moved_and_missing_books() {
+        let temp = tempdir().unwrap();
+        let library_path = temp.path().join("library");
+        create_library_tree(&library_path);
+        let (_db, pool) = setup_library(&library_path).await;
+        let progress = Arc::new(Mutex::new(AppGlobalVariables::default()));
+        scan_library(&pool, 1, "token", &progress).await.unwrap();
+
+        let moved_id = get_book_id(&library_path.join("Saga/Saga 2.CBR"), "1");
+        sqlx::query("UPDATE Books SET read = 1, contentHash = 'abc' WHERE ID_book = ?;")
+            .bind(&moved_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+        fs::rename(
+            library_path.join("Saga/Saga 2.CBR"),
+            library_path.join("Saga/Saga 02.cbr"),
+        )
+        .unwrap();
+        fs::write(library_path.join("Saga/Saga 10.cbz"), b"a longer book").unwrap();
+        fs::remove_file(library_path.join("Standalone.pdf")).unwrap();
+
+        let report = scan_library(&pool, 1, "token", &progress).await.unwrap();
+        assert_eq!(report.books_added, 0);
+        assert_eq!(report.books_moved, 1);
+        assert_eq!(report.books_updated, 1);
+        assert_eq!(report.books_missing, 1);
+        assert_eq!(report.books_unchanged, 1);
+
+        let (path, read, content_hash): (String, bool, Option<String>) =
+            sqlx::query_as("SELECT PATH, read, contentHash FROM Books WHERE ID_book = ?;")
+                .bind(&moved_id)
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert!(path.ends_with("Saga 02.cbr"));
+        assert!(read);
+        assert_eq!(content_hash.as_deref(), Some("abc"));
+
+        let missing: Vec<(String,)> = sqlx::query_as("SELECT NOM FROM Books WHERE missing = 1;")
+            .fetch_all(&pool)
+            .await
+            .unwrap();
+        assert_eq!(missing, vec![("Standalone".to_string(),)]);
+
+        fs::write(library_path.join("Standalone.pdf"), b"book").unwrap();
+        let report = scan_library(&pool, 1, "token", &progress).await.unwrap();
+        assert_eq!(report.books_missing, 0);
+        let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM Books WHERE missing = 1;")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    #[tokio::test]
+    async fn test_scan_library_links_books_to_series() {
+        let temp = tempdir().unwrap();
+        let library_path = temp.path().join("library");
+        create_library_tree(&library_path);
+        fs::write(library_path.join("Blacksad 01.cbz"), b"book").unwrap();
+        fs::write(library_path.join("Blacksad 02.cbz"), b"book").unwrap();
+        let (_db, pool) = setup_library(&library_path).await;
+        let progress = Arc::new(Mutex::new(AppGlobalVariables::default()));
+
+        scan_library(&pool, 1, "token", &progress).await.unwrap();
+
+        let links: Vec<(String, String)> = sqlx::query_as(
+            "SELECT Books.NOM, Series.title FROM Books JOIN Series ON Books.ID_Series = Series.ID_Series ORDER BY Books.NOM;",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            links,
+            vec![
+                ("Blacksad 01".to_string(), "Blacksad".to_string()),
+                ("Blacksad 02".to_string(), "Blacksad".to_string()),
+                ("Saga 10".to_string(), "Saga".to_string()),
+                ("Saga 2".to_string(), "Saga".to_string()),
+                ("Standalone".to_string(), "Standalone".to_string()),
+                ("Volume 1".to_string(), "Saga".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_adopt_manual_series_keeps_book_links() {
+        let temp = tempdir().unwrap();
+        let library_path = temp.path().join("library");
+        create_library_tree(&library_path);
+        let (_db, pool) = setup_library(&library_path).await;
+        let progress = Arc::new(Mutex::new(AppGlobalVariables::default()));
+        scan_library(&pool, 1, "token", &progress).await.unwrap();
+
+        let saga_path = library_path.join("Saga").to_string_lossy().to_string();
+        sqlx::query(
+            "INSERT INTO Series (ID_Series, title, favorite, PATH) VALUES ('42_1', 'Saga', 0, ?);",
+        )
+        .bind(&saga_path)
+        .execute(&pool)
+        .await
+        .unwrap();
+        let relinked = adopt_manual_series(&pool, &saga_path, "42_1")
+            .await
+            .unwrap();
+        assert_eq!(relinked, 3);
+
+        let (linked,): (i64,) =
+            sqlx::query_as("SELECT COUNT(*) FROM Books WHERE ID_Series = '42_1';")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(linked, 3);
+        let (manual,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM Series WHERE PATH = ?;")
+            .bind(&saga_path)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(manual, 1);
+
+        fs::write(library_path.join("Saga/Saga 11.cbz"), b"book").unwrap();
+        let report = scan_library(&pool, 1, "token", &progress).await.unwrap();
+        assert_eq!(report.series_added, 0);
+        let (series_id,): (String,) =
+            sqlx::query_as("SELECT ID_Series FROM Books WHERE NOM = 'Saga 11';")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(series_id, "42_1");
+    }
+
+    #[tokio::test]
+    async fn test_scan_library_rejects_unknown_library() {
+        let temp = tempdir().unwrap();
+        let (_db, pool) = setup_library(temp.path()).await;
+        let progress = Arc::new(Mutex::new(AppGlobalVariables::default()));
+
+        assert!(scan_library(&pool, 42, "token", &progress).await.is_err());
+    }
+}
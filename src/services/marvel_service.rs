@@ -1,3 +1,4 @@
+use crate::services::parser_service::{get_series_title, parse_book_name};
 use anyhow::{Result, anyhow};
 use chrono::Utc;
 use md5;
@@ -60,6 +61,14 @@ pub struct SeriesSummary {
     pub name: String,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreatorSummary {
+    #[serde(rename = "resourceURI")]
+    pub resource_uri: String,
+    pub name: String,
+    pub role: Option<String>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct EventSummary {
     #[serde(rename = "resourceURI")]
@@ -118,7 +127,7 @@ pub struct Comic {
     pub prices: Vec<ComicPrice>,
     pub thumbnail: Image,
     pub images: Vec<Image>,
-    pub creators: ResourceList<SeriesSummary>,
+    pub creators: ResourceList<CreatorSummary>,
     pub characters: ResourceList<SeriesSummary>,
     pub stories: ResourceList<SeriesSummary>,
     pub events: ResourceList<SeriesSummary>,
@@ -163,7 +172,7 @@ pub struct Event {
     pub stories: ResourceList<ComicSummary>,
     pub series: ResourceList<SeriesSummary>,
     pub characters: ResourceList<SeriesSummary>,
-    pub creators: ResourceList<SeriesSummary>,
+    pub creators: ResourceList<CreatorSummary>,
     pub next: Option<EventSummary>,
     pub previous: Option<EventSummary>,
 }
@@ -187,7 +196,7 @@ pub struct Series {
     pub stories: ResourceList<ComicSummary>,
     pub events: ResourceList<EventSummary>,
     pub characters: ResourceList<SeriesSummary>,
-    pub creators: ResourceList<SeriesSummary>,
+    pub creators: ResourceList<CreatorSummary>,
     pub next: Option<SeriesSummary>,
     pub previous: Option<SeriesSummary>,
 }
@@ -261,19 +270,11 @@ pub async fn api_marvel_get(
         return Err(anyhow!("Name is empty"));
     }
 
-    let mut date = String::new();
-    let mut date_nb = 0;
-
-    let date_from_name = name.replace(|c: char| !c.is_numeric(), "#");
-    for element in date_from_name.split('#') {
-        if date_nb == 0 && element.len() == 4 && element.chars().all(|c| c.is_numeric()) {
-            date_nb += 1;
-            date = element.to_string();
-        }
-    }
-
-    let mut cleaned_name = name.replace(&['(', ')'][..], "");
-    cleaned_name = cleaned_name.trim_end().to_string();
+    let date = parse_book_name(name)
+        .year
+        .map(|year| year.to_string())
+        .unwrap_or_default();
+    let cleaned_name = get_series_title(name);
     let encoded_name = encode(&cleaned_name);
 
     let base_url = "https://gateway.marvel.com:443/v1/public/series";
@@ -0,0 +1,184 @@
+use crate::models::book_model::Book;
+use crate::services::parser_service::{ParsedName, parse_book_path};
+use crate::utils::natural_cmp;
+use serde::Serialize;
+use sqlx::{FromRow, Row, SqlitePool};
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+#[derive(Debug, Serialize)]
+pub struct OnDeck {
+    pub in_progress: Vec<Book>,
+    pub next_up: Vec<Book>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AdjacentBooks {
+    pub previous: Option<Book>,
+    pub next: Option<Book>,
+}
+
+fn get_series_id(book: &Book) -> Option<&str> {
+    book.series_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|id| !id.is_empty() && *id != "null")
+}
+
+fn get_folder(book: &Book) -> String {
+    Path::new(&book.path)
+        .parent()
+        .map(|parent| parent.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+pub fn get_series_key(book: &Book) -> String {
+    match get_series_id(book) {
+        Some(id) => id.to_string(),
+        None => get_folder(book),
+    }
+}
+
+fn get_number(book: &Book, parsed: &ParsedName) -> Option<f64> {
+    book.issue
+        .0
+        .or(parsed.issue.map(f64::from))
+        .or(parsed.chapter.map(f64::from))
+}
+
+fn compare_books(a: &(Book, ParsedName), b: &(Book, ParsedName)) -> Ordering {
+    let (volume_a, volume_b) = (a.1.volume.unwrap_or(0.0), b.1.volume.unwrap_or(0.0));
+    volume_a
+        .total_cmp(&volume_b)
+        .then_with(|| match (get_number(&a.0, &a.1), get_number(&b.0, &b.1)) {
+            (Some(number_a), Some(number_b)) => number_a.total_cmp(&number_b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        })
+        .then_with(|| natural_cmp(&a.0.path.to_lowercase(), &b.0.path.to_lowercase()))
+}
+
+pub fn sort_reading_order(books: Vec<Book>) -> Vec<Book> {
+    let mut books: Vec<(Book, ParsedName)> = books
+        .into_iter()
+        .map(|book| {
+            let parsed = parse_book_path(Path::new(&book.path));
+            (book, parsed)
+        })
+        .collect();
+    books.sort_by(compare_books);
+    books.into_iter().map(|(book, _)| book).collect()
+}
+
+fn is_in_progress(book: &Book) -> bool {
+    !book.read.0 && (book.reading.0 || book.last_page.0.is_some_and(|page| page > 0.0))
+}
+
+pub async fn get_on_deck(db_pool: &SqlitePool, limit: usize) -> Result<OnDeck, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT Books.*, (SELECT MAX(ended) FROM ReadingSessions \
+         WHERE ReadingSessions.BOOK_ID = Books.ID_book) AS last_read \
+         FROM Books WHERE missing NOT IN (1, 'true');",
+    )
+    .fetch_all(db_pool)
+    .await?;
+    let mut last_read = HashMap::new();
+    let mut groups: HashMap<String, Vec<Book>> = HashMap::new();
+    for row in &rows {
+        let book = Book::from_row(row)?;
+        if let Some(ended) = row.try_get::<Option<i64>, _>("last_read")? {
+            last_read.insert(book.id.clone(), ended);
+        }
+        groups.entry(get_series_key(&book)).or_default().push(book);
+    }
+    let activity = |books: &[&Book]| {
+        books
+            .iter()
+            .filter_map(|book| last_read.get(&book.id).copied())
+            .max()
+    };
+
+    let mut in_progress: Vec<&Book> = groups
+        .values()
+        .flatten()
+        .filter(|b| is_in_progress(b))
+        .collect();
+    in_progress.sort_by(|a, b| {
+        activity(&[b])
+            .cmp(&activity(&[a]))
+            .then_with(|| natural_cmp(&a.name, &b.name))
+    });
+    let in_progress_ids: HashSet<&str> = in_progress.iter().map(|book| book.id.as_str()).collect();
+
+    let mut next_up = Vec::new();
+    for (key, books) in &groups {
+        let books = sort_reading_order(books.clone());
+        let Some(last_finished) = books.iter().rposition(|book| book.read.0) else {
+            continue;
+        };
+        let next = books[last_finished + 1..].iter().find(|book| !book.read.0);
+        if let Some(next) = next.filter(|next| !in_progress_ids.contains(next.id.as_str())) {
+            let series: Vec<&Book> = groups[key].iter().collect();
+            next_up.push((activity(&series), key, next.clone()));
+        }
+    }
+    next_up.sort_by(|a, b| b.0.cmp(&a.0).then_with(|| natural_cmp(a.1, b.1)));
+
+    Ok(OnDeck {
+        in_progress: in_progress.into_iter().take(limit).cloned().collect(),
+        next_up: next_up
+            .into_iter()
+            .take(limit)
+            .map(|(_, _, book)| book)
+            .collect(),
+    })
+}
+
+pub async fn get_adjacent_books(
+    db_pool: &SqlitePool,
+    book_id: &str,
+) -> Result<Option<AdjacentBooks>, sqlx::Error> {
+    let book: Option<Book> = sqlx::query_as("SELECT * FROM Books WHERE ID_book = ?;")
+        .bind(book_id)
+        .fetch_optional(db_pool)
+        .await?;
+    let Some(book) = book else {
+        return Ok(None);
+    };
+    let siblings: Vec<Book> = match get_series_id(&book) {
+        Some(series_id) => {
+            sqlx::query_as("SELECT * FROM Books WHERE ID_Series = ?;")
+                .bind(series_id)
+                .fetch_all(db_pool)
+                .await?
+        }
+        None => {
+            let folder = format!("{}/", get_folder(&book));
+            sqlx::query_as("SELECT * FROM Books WHERE substr(PATH, 1, length(?1)) = ?1;")
+                .bind(&folder)
+                .fetch_all(db_pool)
+                .await?
+        }
+    };
+    let key = get_series_key(&book);
+    let siblings = sort_reading_order(
+        siblings
+            .into_iter()
+            .filter(|sibling| {
+                sibling.id == book.id || (!sibling.missing.0 && get_series_key(sibling) == key)
+            })
+            .collect(),
+    );
+    let Some(index) = siblings.iter().position(|sibling| sibling.id == book.id) else {
+        return Ok(None);
+    };
+    Ok(Some(AdjacentBooks {
+        previous: index
+            .checked_sub(1)
+            .and_then(|index| siblings.get(index))
+            .cloned(),
+        next: siblings.get(index + 1).cloned(),
+    }))
+}
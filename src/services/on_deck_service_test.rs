@@ -0,0 +1,86 @@
+#[cfg(test)]
+mod tests {
+    use crate::repositories::database_repo::make_test_db;
+    use crate::services::on_deck_service::*;
+    use crate::services::reading_history_service::{PageView, record_page_view};
+    use sqlx::SqlitePool;
+    use tempfile::TempDir;
+
+    async fn setup_db() -> (TempDir, SqlitePool) {
+        let (temp, pool) = make_test_db().await;
+        for (id, series, path, read, reading, last_page, missing) in [
+            ("s1", "10_1", "/comics/saga/Saga 010.cbz", 0, 0, 0, 0),
+            ("s2", "10_1", "/comics/saga/Saga Annual.cbz", 0, 0, 0, 0),
+            ("s3", "10_1", "/comics/saga/Saga 002.cbz", 1, 0, 0, 0),
+            ("s4", "10_1", "/comics/saga/Saga 001.cbz", 0, 0, 0, 0),
+            ("s5", "10_1", "/comics/saga/Saga 003.cbz", 0, 0, 0, 1),
+            ("v2", "20_2", "/manga/berserk/Berserk v02.cbz", 0, 1, 0, 0),
+            ("v1", "20_2", "/manga/berserk/Berserk v01.cbz", 1, 0, 0, 0),
+            ("v3", "20_2", "/manga/berserk/Berserk v03.cbz", 0, 0, 0, 0),
+            ("m1", "", "/misc/oneshots/a.cbz", 1, 0, 0, 0),
+            ("m2", "", "/misc/oneshots/b.cbz", 0, 0, 0, 0),
+            ("m3", "", "/misc/oneshots/c.cbz", 0, 0, 3, 0),
+        ] {
+            sqlx::query(
+                "INSERT INTO Books (ID_book, NOM, read, reading, unread, favorite, last_page, folder, PATH, ID_Series, missing) \
+                 VALUES (?, ?, ?, ?, false, false, ?, false, ?, ?, ?);",
+            )
+            .bind(id)
+            .bind(format!("Book {}", id))
+            .bind(read)
+            .bind(reading)
+            .bind(last_page)
+            .bind(path)
+            .bind(series)
+            .bind(missing)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        for (book_id, timestamp) in [("s3", 1_700_000_000), ("v2", 1_700_100_000)] {
+            let view = PageView {
+                book_id: book_id.to_string(),
+                page: 0,
+                page_count: None,
+                user_agent: None,
+            };
+            record_page_view(&pool, &view, timestamp).await.unwrap();
+        }
+        (temp, pool)
+    }
+
+    fn ids(books: &[crate::models::book_model::Book]) -> Vec<&str> {
+        books.iter().map(|book| book.id.as_str()).collect()
+    }
+
+    #[tokio::test]
+    async fn test_on_deck_lists_in_progress_and_next_books() {
+        let (_temp, pool) = setup_db().await;
+        let on_deck = get_on_deck(&pool, 10).await.unwrap();
+        assert_eq!(ids(&on_deck.in_progress), vec!["v2", "m3"]);
+        assert_eq!(ids(&on_deck.next_up), vec!["s1", "m2"]);
+
+        let on_deck = get_on_deck(&pool, 1).await.unwrap();
+        assert_eq!(ids(&on_deck.in_progress), vec!["v2"]);
+        assert_eq!(ids(&on_deck.next_up), vec!["s1"]);
+    }
+
+    #[tokio::test]
+    async fn test_adjacent_books_follow_parsed_order() {
+        let (_temp, pool) = setup_db().await;
+        for (book_id, previous, next) in [
+            ("s3", Some("s4"), Some("s1")),
+            ("s2", Some("s1"), None),
+            ("v1", None, Some("v2")),
+            ("m2", Some("m1"), Some("m3")),
+        ] {
+            let adjacent = get_adjacent_books(&pool, book_id).await.unwrap().unwrap();
+            assert_eq!(
+                adjacent.previous.as_ref().map(|book| book.id.as_str()),
+                previous
+            );
+            assert_eq!(adjacent.next.as_ref().map(|book| book.id.as_str()), next);
+        }
+        assert!(get_adjacent_books(&pool, "nope").await.unwrap().is_none());
+    }
+}
@@ -25,6 +25,7 @@ pub struct BookDetails {
     pub publish_date: Option<String>,
     pub info_url: Option<String>,
     pub authors: Option<Vec<AuthorRef>>,
+    pub publishers: Option<Vec<String>>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -0,0 +1,157 @@
+use crate::utils::natural_cmp;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::cmp::Ordering;
+
+pub const DEFAULT_PAGE_SIZE: u32 = 50;
+pub const MAX_PAGE_SIZE: u32 = 200;
+pub const LIST_PARAMS: &[&str] = &["page", "limit", "sort", "order", "fields"];
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ListQuery {
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+    pub fields: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Page<T> {
+    pub total: i64,
+    pub page: u32,
+    pub limit: u32,
+    pub items: Vec<T>,
+}
+
+impl ListQuery {
+    pub fn is_requested(&self) -> bool {
+        self.page.is_some()
+            || self.limit.is_some()
+            || self.sort.is_some()
+            || self.order.is_some()
+            || self.fields.is_some()
+    }
+
+    pub fn page_and_limit(&self) -> (u32, u32) {
+        (
+            self.page.unwrap_or(1).max(1),
+            self.limit
+                .unwrap_or(DEFAULT_PAGE_SIZE)
+                .clamp(1, MAX_PAGE_SIZE),
+        )
+    }
+
+    pub fn offset(&self) -> i64 {
+        let (page, limit) = self.page_and_limit();
+        (page as i64 - 1) * limit as i64
+    }
+}
+
+pub fn sort_order(order: Option<&str>) -> Result<&'static str, String> {
+    match order {
+        None | Some("asc") => Ok("ASC"),
+        Some("desc") => Ok("DESC"),
+        Some(order) => Err(format!("Unknown sort order: {}", order)),
+    }
+}
+
+pub fn get_sort_key<'a>(query: &ListQuery, allowed: &[&'a str]) -> Result<Option<&'a str>, String> {
+    query
+        .sort
+        .as_deref()
+        .map(|sort| {
+            allowed
+                .iter()
+                .find(|key| **key == sort)
+                .copied()
+                .ok_or_else(|| format!("Unknown sort field: {}", sort))
+        })
+        .transpose()
+}
+
+pub fn get_fields(query: &ListQuery, allowed: &[&str]) -> Result<Option<Vec<String>>, String> {
+    let Some(fields) = query.fields.as_deref() else {
+        return Ok(None);
+    };
+    let mut selected = Vec::new();
+    for field in fields.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+        if !allowed.contains(&field) {
+            return Err(format!("Unknown field: {}", field));
+        }
+        selected.push(field.to_string());
+    }
+    Ok(Some(selected))
+}
+
+pub fn select_fields(item: &mut Map<String, Value>, fields: Option<&[String]>) {
+    if let Some(fields) = fields {
+        item.retain(|key, _| key == "id" || fields.contains(key));
+    }
+}
+
+pub fn to_items<T: Serialize>(values: &[T]) -> Vec<Map<String, Value>> {
+    values
+        .iter()
+        .filter_map(|value| match serde_json::to_value(value) {
+            Ok(Value::Object(item)) => Some(item),
+            _ => None,
+        })
+        .collect()
+}
+
+fn compare_values(a: &Value, b: &Value) -> Ordering {
+    match (a, b) {
+        (Value::Number(a), Value::Number(b)) => a
+            .as_f64()
+            .partial_cmp(&b.as_f64())
+            .unwrap_or(Ordering::Equal),
+        (Value::String(a), Value::String(b)) => natural_cmp(&a.to_lowercase(), &b.to_lowercase()),
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::Null, Value::Null) => Ordering::Equal,
+        (Value::Null, _) => Ordering::Less,
+        (_, Value::Null) => Ordering::Greater,
+        _ => a.to_string().cmp(&b.to_string()),
+    }
+}
+
+pub fn paginate_items(
+    mut items: Vec<Map<String, Value>>,
+    query: &ListQuery,
+    sort_keys: &[&str],
+    field_names: &[&str],
+) -> Result<Page<Map<String, Value>>, String> {
+    let sort = get_sort_key(query, sort_keys)?.or(sort_keys.first().copied());
+    let descending = sort_order(query.order.as_deref())? == "DESC";
+    let fields = get_fields(query, field_names)?;
+    if let Some(sort) = sort {
+        items.sort_by(|a, b| {
+            let ordering = compare_values(
+                a.get(sort).unwrap_or(&Value::Null),
+                b.get(sort).unwrap_or(&Value::Null),
+            );
+            if descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+    }
+    let (page, limit) = query.page_and_limit();
+    let total = items.len() as i64;
+    let items = items
+        .into_iter()
+        .skip(query.offset() as usize)
+        .take(limit as usize)
+        .map(|mut item| {
+            select_fields(&mut item, fields.as_deref());
+            item
+        })
+        .collect();
+    Ok(Page {
+        total,
+        page,
+        limit,
+        items,
+    })
+}
@@ -0,0 +1,104 @@
+#[cfg(test)]
+mod tests {
+    use crate::services::pagination_service::*;
+    use serde_json::{Map, Value, json};
+
+    fn items(values: Value) -> Vec<Map<String, Value>> {
+        values
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|value| value.as_object().unwrap().clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_page_and_limit_are_clamped() {
+        let query = ListQuery {
+            page: Some(0),
+            limit: Some(10_000),
+            ..ListQuery::default()
+        };
+        assert_eq!(query.page_and_limit(), (1, MAX_PAGE_SIZE));
+        assert_eq!(query.offset(), 0);
+        assert!(query.is_requested());
+        assert!(!ListQuery::default().is_requested());
+        assert_eq!(
+            ListQuery::default().page_and_limit(),
+            (1, DEFAULT_PAGE_SIZE)
+        );
+    }
+
+    #[test]
+    fn test_paginate_items_sorts_naturally_and_selects_fields() {
+        let profiles = items(json!([
+            {"name": "user10", "image": "a", "passcode": true},
+            {"name": "User2", "image": "b", "passcode": false},
+            {"name": "user1", "image": "c", "passcode": false},
+        ]));
+        let query = ListQuery {
+            limit: Some(2),
+            fields: Some("name, passcode".to_string()),
+            ..ListQuery::default()
+        };
+        let page =
+            paginate_items(profiles.clone(), &query, &["name"], &["name", "passcode"]).unwrap();
+        assert_eq!(page.total, 3);
+        assert_eq!(
+            Value::Array(page.items.into_iter().map(Value::Object).collect()),
+            json!([
+                {"name": "user1", "passcode": false},
+                {"name": "User2", "passcode": false},
+            ])
+        );
+
+        let query = ListQuery {
+            page: Some(2),
+            limit: Some(2),
+            order: Some("desc".to_string()),
+            ..ListQuery::default()
+        };
+        let page = paginate_items(profiles, &query, &["name"], &["name"]).unwrap();
+        assert_eq!(page.items[0]["name"], json!("user1"));
+    }
+
+    #[test]
+    fn test_unknown_sort_order_and_fields_are_rejected() {
+        let query = |sort: &str, order: &str, fields: &str| ListQuery {
+            sort: Some(sort.to_string()),
+            order: Some(order.to_string()),
+            fields: Some(fields.to_string()),
+            ..ListQuery::default()
+        };
+        assert_eq!(
+            paginate_items(
+                Vec::new(),
+                &query("image", "asc", "name"),
+                &["name"],
+                &["name"]
+            )
+            .unwrap_err(),
+            "Unknown sort field: image"
+        );
+        assert_eq!(
+            paginate_items(
+                Vec::new(),
+                &query("name", "up", "name"),
+                &["name"],
+                &["name"]
+            )
+            .unwrap_err(),
+            "Unknown sort order: up"
+        );
+        assert_eq!(
+            paginate_items(
+                Vec::new(),
+                &query("name", "asc", "name,x"),
+                &["name"],
+                &["name"]
+            )
+            .unwrap_err(),
+            "Unknown field: x"
+        );
+    }
+}
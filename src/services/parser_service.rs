@@ -0,0 +1,160 @@
+use crate::utils::VALID_BOOK_EXTENSION;
+use regex::Regex;
+use serde::Serialize;
+use std::path::Path;
+use std::sync::LazyLock;
+
+static BRACKETED: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[\(\[\{]([^\)\]\}]*)[\)\]\}]").unwrap());
+static YEAR: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^(?:19|20)\d{2}$").unwrap());
+static VOLUME: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:^|\s)(?:v|vol\.?|volume|tome|t)\s*(\d+(?:\.\d+)?)(?:\s|$)").unwrap()
+});
+static CHAPTER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?i)(?:^|\s)(?:c|ch\.?|chap\.?|chapter)\s*(\d+(?:\.\d+)?)(?:\s*-\s*(\d+(?:\.\d+)?))?(?:\s|$)",
+    )
+    .unwrap()
+});
+static ISSUE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)(?:^|\s)(\d{1,4}(?:\.\d+)?)(?:\s*(?:of|/)\s*\d+)?\s*(?:$|-\s)").unwrap()
+});
+static HASH_ISSUE: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"#\s*(\d+(?:\.\d+)?)").unwrap());
+static SPACES: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\s+").unwrap());
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct ParsedName {
+    pub series: String,
+    pub volume: Option<f32>,
+    pub chapter: Option<f32>,
+    pub chapter_end: Option<f32>,
+    pub issue: Option<f32>,
+    pub year: Option<u16>,
+    pub tags: Vec<String>,
+}
+
+fn strip_book_extension(name: &str) -> &str {
+    match name.rsplit_once('.') {
+        Some((stem, ext)) if VALID_BOOK_EXTENSION.contains(&ext.to_lowercase().as_str()) => stem,
+        _ => name,
+    }
+}
+
+fn replace_separators(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    chars
+        .iter()
+        .enumerate()
+        .map(|(i, c)| match c {
+            '_' => ' ',
+            '.' if !(i > 0
+                && chars[i - 1].is_ascii_digit()
+                && chars.get(i + 1).is_some_and(|next| next.is_ascii_digit())) =>
+            {
+                ' '
+            }
+            c => *c,
+        })
+        .collect()
+}
+
+fn parse_number(value: Option<regex::Match>) -> Option<f32> {
+    value.and_then(|m| m.as_str().parse().ok())
+}
+
+pub fn parse_book_name(name: &str) -> ParsedName {
+    let name = strip_book_extension(name.trim());
+    let mut parsed = ParsedName::default();
+
+    for caps in BRACKETED.captures_iter(name) {
+        let content = caps[1].trim();
+        if content.is_empty() {
+            continue;
+        }
+        if parsed.year.is_none() && YEAR.is_match(content) {
+            parsed.year = content.parse().ok();
+        } else {
+            parsed.tags.push(content.to_string());
+        }
+    }
+
+    let cleaned = replace_separators(&BRACKETED.replace_all(name, " "));
+    let cleaned = SPACES.replace_all(&cleaned, " ").trim().to_string();
+    let mut series_end = cleaned.len();
+
+    let mut remaining = cleaned.clone();
+    if let Some(caps) = VOLUME.captures(&cleaned) {
+        let whole = caps.get(0).unwrap();
+        parsed.volume = parse_number(caps.get(1));
+        series_end = series_end.min(whole.start());
+        remaining.replace_range(whole.range(), &" ".repeat(whole.len()));
+    }
+    if let Some(caps) = CHAPTER.captures(&cleaned) {
+        let whole = caps.get(0).unwrap();
+        parsed.chapter = parse_number(caps.get(1));
+        parsed.chapter_end = parse_number(caps.get(2));
+        series_end = series_end.min(whole.start());
+        remaining.replace_range(whole.range(), &" ".repeat(whole.len()));
+    }
+    let issue = HASH_ISSUE
+        .captures(&remaining)
+        .or_else(|| ISSUE.captures(&remaining));
+    if let Some(caps) = issue {
+        let whole = caps.get(0).unwrap();
+        parsed.issue = parse_number(caps.get(1));
+        series_end = series_end.min(whole.start());
+    }
+
+    parsed.series = cleaned[..series_end]
+        .trim_matches(|c: char| c.is_whitespace() || matches!(c, '-' | ',' | '#' | ':'))
+        .to_string();
+    parsed
+}
+
+pub fn parse_book_path(path: &Path) -> ParsedName {
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut parsed = parse_book_name(&name);
+    if parsed.series.is_empty()
+        && let Some(parent) = path.parent().and_then(|parent| parent.file_name())
+    {
+        parsed.series = parse_book_name(&parent.to_string_lossy()).series;
+    }
+    parsed
+}
+
+pub fn get_series_title(name: &str) -> String {
+    let parsed = parse_book_name(name);
+    if parsed.series.is_empty() {
+        name.trim().to_string()
+    } else {
+        parsed.series
+    }
+}
+
+pub fn get_search_title(name: &str) -> String {
+    let parsed = parse_book_name(name);
+    if parsed.series.is_empty() {
+        return name.trim().to_string();
+    }
+    match parsed.issue.or(parsed.volume) {
+        Some(number) => format!("{} {}", parsed.series, number),
+        None => parsed.series,
+    }
+}
+
+pub fn normalize_title(title: &str) -> String {
+    title
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn series_matches(name: &str, title: &str) -> bool {
+    let series = normalize_title(&parse_book_name(name).series);
+    !series.is_empty() && series == normalize_title(title)
+}
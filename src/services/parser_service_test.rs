@@ -0,0 +1,209 @@
+#[cfg(test)]
+mod tests {
+    use crate::services::parser_service::*;
+    use std::path::Path;
+
+    struct Case {
+        name: &'static str,
+        series: &'static str,
+        volume: Option<f32>,
+        chapter: Option<f32>,
+        chapter_end: Option<f32>,
+        issue: Option<f32>,
+        year: Option<u16>,
+        tags: &'static [&'static str],
+    }
+
+    const CASES: &[Case] = &[
+        Case {
+            name: "Saga 054 (2018) (Digital).cbz",
+            series: "Saga",
+            volume: None,
+            chapter: None,
+            chapter_end: None,
+            issue: Some(54.0),
+            year: Some(2018),
+            tags: &["Digital"],
+        },
+        Case {
+            name: "One Piece v01 c001-008.cbz",
+            series: "One Piece",
+            volume: Some(1.0),
+            chapter: Some(1.0),
+            chapter_end: Some(8.0),
+            issue: None,
+            year: None,
+            tags: &[],
+        },
+        Case {
+            name: "Saga (2012)",
+            series: "Saga",
+            volume: None,
+            chapter: None,
+            chapter_end: None,
+            issue: None,
+            year: Some(2012),
+            tags: &[],
+        },
+        Case {
+            name: "Batman #12.cbr",
+            series: "Batman",
+            volume: None,
+            chapter: None,
+            chapter_end: None,
+            issue: Some(12.0),
+            year: None,
+            tags: &[],
+        },
+        Case {
+            name: "[Group] Berserk Vol. 3.cbz",
+            series: "Berserk",
+            volume: Some(3.0),
+            chapter: None,
+            chapter_end: None,
+            issue: None,
+            year: None,
+            tags: &["Group"],
+        },
+        Case {
+            name: "The Walking Dead - Volume 02.pdf",
+            series: "The Walking Dead",
+            volume: Some(2.0),
+            chapter: None,
+            chapter_end: None,
+            issue: None,
+            year: None,
+            tags: &[],
+        },
+        Case {
+            name: "X-Men_v2_012.cbz",
+            series: "X-Men",
+            volume: Some(2.0),
+            chapter: None,
+            chapter_end: None,
+            issue: Some(12.0),
+            year: None,
+            tags: &[],
+        },
+        Case {
+            name: "Spider-Man 2099 001 (1992) (c2c).cbz",
+            series: "Spider-Man 2099",
+            volume: None,
+            chapter: None,
+            chapter_end: None,
+            issue: Some(1.0),
+            year: Some(1992),
+            tags: &["c2c"],
+        },
+        Case {
+            name: "2000 AD 1234.cbr",
+            series: "2000 AD",
+            volume: None,
+            chapter: None,
+            chapter_end: None,
+            issue: Some(1234.0),
+            year: None,
+            tags: &[],
+        },
+        Case {
+            name: "Asterix 12 - Asterix et les Normands.cbz",
+            series: "Asterix",
+            volume: None,
+            chapter: None,
+            chapter_end: None,
+            issue: Some(12.0),
+            year: None,
+            tags: &[],
+        },
+        Case {
+            name: "Invincible 003 (of 6).cbz",
+            series: "Invincible",
+            volume: None,
+            chapter: None,
+            chapter_end: None,
+            issue: Some(3.0),
+            year: None,
+            tags: &["of 6"],
+        },
+        Case {
+            name: "Naruto Ch.700.5.epub",
+            series: "Naruto",
+            volume: None,
+            chapter: Some(700.5),
+            chapter_end: None,
+            issue: None,
+            year: None,
+            tags: &[],
+        },
+        Case {
+            name: "Blacksad.cbz",
+            series: "Blacksad",
+            volume: None,
+            chapter: None,
+            chapter_end: None,
+            issue: None,
+            year: None,
+            tags: &[],
+        },
+        Case {
+            name: "001.cbz",
+            series: "",
+            volume: None,
+            chapter: None,
+            chapter_end: None,
+            issue: Some(1.0),
+            year: None,
+            tags: &[],
+        },
+    ];
+
+    #[test]
+    fn test_parse_book_name_cases() {
+        for case in CASES {
+            let parsed = parse_book_name(case.name);
+            assert_eq!(
+                parsed,
+                ParsedName {
+                    series: case.series.to_string(),
+                    volume: case.volume,
+                    chapter: case.chapter,
+                    chapter_end: case.chapter_end,
+                    issue: case.issue,
+                    year: case.year,
+                    tags: case.tags.iter().map(|tag| tag.to_string()).collect(),
+                },
+                "failed to parse {}",
+                case.name
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_book_path_falls_back_to_folder_series() {
+        let parsed = parse_book_path(Path::new(
+            "/comics/Saga (2012)/Saga 054 (2018) (Digital).cbz",
+        ));
+        assert_eq!(parsed.series, "Saga");
+        assert_eq!(parsed.year, Some(2018));
+
+        let parsed = parse_book_path(Path::new("/comics/Saga (2012)/001.cbz"));
+        assert_eq!(parsed.series, "Saga");
+        assert_eq!(parsed.issue, Some(1.0));
+    }
+
+    #[test]
+    fn test_search_title_and_series_matching() {
+        assert_eq!(get_search_title("Saga (2012)"), "Saga");
+        assert_eq!(get_search_title("Saga 054 (2018) (Digital)"), "Saga 54");
+        assert_eq!(get_search_title("(2012)"), "(2012)");
+        assert_eq!(get_series_title("One Piece v01 (2003)"), "One Piece");
+
+        assert!(series_matches("One Piece v01 c001-008", "ONE PIECE"));
+        assert!(series_matches(
+            "Shingeki_no_Kyojin (2009)",
+            "Shingeki no Kyojin"
+        ));
+        assert!(!series_matches("One Punch Man", "One Piece"));
+        assert!(!series_matches("(2012)", ""));
+    }
+}
@@ -4,6 +4,8 @@ use std::{fs, path::PathBuf};
 use serde::Deserialize;
 use serde_json::Value;
 use serde_json::json;
+use sqlx::SqlitePool;
+use sqlx::sqlite::SqliteConnectOptions;
 use tracing::error;
 
 use crate::repositories::database_repo;
@@ -301,6 +303,37 @@ pub async fn discover_profiles_service(
     Ok(profiles)
 }
 
+pub async fn schema_version_service(name: &str, base_path: &str) -> Result<Value, String> {
+    let db_path = Path::new(base_path)
+        .join("profiles")
+        .join(name)
+        .join("CosmicComics.db");
+    if !db_path.is_file() {
+        return Err(format!("Database of {} not found", name));
+    }
+    let options = SqliteConnectOptions::new()
+        .filename(&db_path)
+        .read_only(true);
+    let pool = SqlitePool::connect_with(options)
+        .await
+        .map_err(|e| format!("Failed to open database of {}: {}", name, e))?;
+    let version = database_repo::get_schema_version(&pool)
+        .await
+        .map_err(|e| format!("Failed to read schema version of {}: {}", name, e))?;
+    pool.close().await;
+    let pending = database_repo::MIGRATIONS
+        .iter()
+        .filter(|migration| migration.version > version)
+        .count();
+
+    Ok(json!({
+        "name": name,
+        "version": version,
+        "latest": database_repo::latest_schema_version(),
+        "pending": pending
+    }))
+}
+
 pub async fn logout_service(token: &str, base_path: &str) -> Result<(), String> {
     let config_path = format!("{}/serverconfig.json", base_path);
     let config_content = fs::read_to_string(&config_path)
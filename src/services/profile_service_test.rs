@@ -5,7 +5,7 @@ mod tests {
         services::profile_service::{
             CreateUserPayload, create_user_service, delete_account_service,
             discover_profiles_service, generate_token, login_check_service, login_service,
-            logout_service, modify_profile_service, resolve_token,
+            logout_service, modify_profile_service, resolve_token, schema_version_service,
         },
     };
     use serde_json::Value;
@@ -414,4 +414,23 @@ mod tests {
         assert!(!std::path::Path::new(&user_dir).exists());
         assert!(!global.opened_db.contains_key(token));
     }
+
+    #[tokio::test]
+    async fn test_schema_version_service_reports_the_profile() {
+        use crate::repositories::database_repo::{latest_schema_version, make_db};
+        use tempfile::tempdir;
+
+        let dir = tempdir().unwrap();
+        let base_path = dir.path().to_str().unwrap();
+        make_db("reader", base_path).await.unwrap();
+        make_db("other", base_path).await.unwrap();
+        std::fs::create_dir_all(dir.path().join("profiles/empty")).unwrap();
+
+        let profile = schema_version_service("reader", base_path).await.unwrap();
+
+        assert_eq!(profile["name"], "reader");
+        assert_eq!(profile["version"], latest_schema_version());
+        assert_eq!(profile["pending"], 0);
+        assert!(schema_version_service("empty", base_path).await.is_err());
+    }
 }
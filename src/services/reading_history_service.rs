@@ -0,0 +1,190 @@
+use crate::models::book_model::Book;
+use crate::models::reading_session_model::ReadingSession;
+use chrono::Utc;
+use serde::Serialize;
+use sqlx::{FromRow, Row, SqlitePool};
+use std::fs;
+use std::path::Path;
+
+pub const SESSION_TIMEOUT: i64 = 30 * 60;
+
+#[derive(Debug, Clone)]
+pub struct PageView {
+    pub book_id: String,
+    pub page: i64,
+    pub page_count: Option<i64>,
+    pub user_agent: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RecentBook {
+    #[serde(flatten)]
+    pub book: Book,
+    pub last_read: i64,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct BookReadingSummary {
+    pub book_id: String,
+    pub sessions: i64,
+    pub time_spent: i64,
+    pub pages_read: i64,
+    pub reads: i64,
+    pub rereads: i64,
+    pub first_read: Option<i64>,
+    pub last_read: Option<i64>,
+}
+
+pub async fn record_page_view(
+    db_pool: &SqlitePool,
+    view: &PageView,
+    timestamp: i64,
+) -> Result<ReadingSession, sqlx::Error> {
+    let completed = view.page_count.is_some_and(|count| view.page >= count - 1);
+    let mut tx = db_pool.begin_with("BEGIN IMMEDIATE").await?;
+    let active: Option<i64> = sqlx::query_scalar(
+        "SELECT ID_SESSION FROM ReadingSessions WHERE BOOK_ID = ? AND user_agent IS ? \
+         AND ended >= ? AND NOT (completed AND ? = 0) \
+         ORDER BY ended DESC, ID_SESSION DESC LIMIT 1;",
+    )
+    .bind(&view.book_id)
+    .bind(&view.user_agent)
+    .bind(timestamp - SESSION_TIMEOUT)
+    .bind(view.page)
+    .fetch_optional(&mut *tx)
+    .await?;
+    let id = match active {
+        Some(id) => id,
+        None => sqlx::query(
+            "INSERT INTO ReadingSessions (BOOK_ID, started, ended, first_page, last_page, user_agent) \
+             VALUES (?1, ?2, ?2, ?3, ?3, ?4);",
+        )
+        .bind(&view.book_id)
+        .bind(timestamp)
+        .bind(view.page)
+        .bind(&view.user_agent)
+        .execute(&mut *tx)
+        .await?
+        .last_insert_rowid(),
+    };
+    sqlx::query(
+        "INSERT OR IGNORE INTO ReadingSessionPages (ID_SESSION, page, viewed) VALUES (?, ?, ?);",
+    )
+    .bind(id)
+    .bind(view.page)
+    .bind(timestamp)
+    .execute(&mut *tx)
+    .await?;
+    sqlx::query(
+        "UPDATE ReadingSessions SET ended = MAX(ended, ?1), last_page = ?2, \
+         pages_read = (SELECT COUNT(*) FROM ReadingSessionPages WHERE ID_SESSION = ?5), \
+         page_count = COALESCE(?3, page_count), completed = (completed OR ?4) \
+         WHERE ID_SESSION = ?5;",
+    )
+    .bind(timestamp)
+    .bind(view.page)
+    .bind(view.page_count)
+    .bind(completed)
+    .bind(id)
+    .execute(&mut *tx)
+    .await?;
+    let session = sqlx::query_as("SELECT * FROM ReadingSessions WHERE ID_SESSION = ?;")
+        .bind(id)
+        .fetch_one(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(session)
+}
+
+pub async fn record_current_page(
+    db_pool: &SqlitePool,
+    current_book: &Path,
+    page: usize,
+    page_count: usize,
+    user_agent: Option<String>,
+) -> Result<Option<ReadingSession>, sqlx::Error> {
+    let Ok(path) = fs::read_to_string(current_book.join("path.txt")) else {
+        return Ok(None);
+    };
+    let book_id: Option<String> = sqlx::query_scalar("SELECT ID_book FROM Books WHERE PATH = ?;")
+        .bind(path.trim())
+        .fetch_optional(db_pool)
+        .await?;
+    let Some(book_id) = book_id else {
+        return Ok(None);
+    };
+    let view = PageView {
+        book_id,
+        page: page as i64,
+        page_count: Some(page_count as i64),
+        user_agent,
+    };
+    record_page_view(db_pool, &view, Utc::now().timestamp())
+        .await
+        .map(Some)
+}
+
+pub async fn list_reading_history(
+    db_pool: &SqlitePool,
+    book_id: Option<&str>,
+    limit: i64,
+) -> Result<Vec<ReadingSession>, sqlx::Error> {
+    sqlx::query_as(
+        "SELECT * FROM ReadingSessions WHERE (?1 IS NULL OR BOOK_ID = ?1) \
+         ORDER BY ended DESC, ID_SESSION DESC LIMIT ?2;",
+    )
+    .bind(book_id)
+    .bind(limit)
+    .fetch_all(db_pool)
+    .await
+}
+
+pub async fn get_recently_read(
+    db_pool: &SqlitePool,
+    limit: i64,
+) -> Result<Vec<RecentBook>, sqlx::Error> {
+    sqlx::query(
+        "SELECT Books.*, MAX(ReadingSessions.ended) AS last_read FROM ReadingSessions \
+         JOIN Books ON Books.ID_book = ReadingSessions.BOOK_ID \
+         GROUP BY Books.ID_book ORDER BY last_read DESC LIMIT ?;",
+    )
+    .bind(limit)
+    .fetch_all(db_pool)
+    .await?
+    .iter()
+    .map(|row| {
+        Ok(RecentBook {
+            book: Book::from_row(row)?,
+            last_read: row.try_get("last_read")?,
+        })
+    })
+    .collect()
+}
+
+pub async fn get_book_reading_summary(
+    db_pool: &SqlitePool,
+    book_id: &str,
+) -> Result<BookReadingSummary, sqlx::Error> {
+    let row = sqlx::query(
+        "SELECT COUNT(*) AS sessions, COALESCE(SUM(ended - started), 0) AS time_spent, \
+         COALESCE(SUM(completed), 0) AS reads, MIN(started) AS first_read, \
+         MAX(ended) AS last_read, \
+         (SELECT COUNT(DISTINCT page) FROM ReadingSessionPages JOIN ReadingSessions \
+          USING (ID_SESSION) WHERE BOOK_ID = ?1) AS pages_read \
+         FROM ReadingSessions WHERE BOOK_ID = ?1;",
+    )
+    .bind(book_id)
+    .fetch_one(db_pool)
+    .await?;
+    let reads: i64 = row.try_get("reads")?;
+    Ok(BookReadingSummary {
+        book_id: book_id.to_string(),
+        sessions: row.try_get("sessions")?,
+        time_spent: row.try_get("time_spent")?,
+        pages_read: row.try_get("pages_read")?,
+        reads,
+        rereads: (reads - 1).max(0),
+        first_read: row.try_get("first_read")?,
+        last_read: row.try_get("last_read")?,
+    })
+}
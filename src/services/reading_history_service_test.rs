@@ -0,0 +1,125 @@
+#[cfg(test)]
+mod tests {
+    use crate::repositories::database_repo::make_test_db;
+    use crate::services::reading_history_service::*;
+    use crate::services::resource_service::{Resource, create_resource, delete_resource};
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use std::fs;
+    use tempfile::TempDir;
+
+    async fn setup_db() -> (TempDir, SqlitePool) {
+        let (temp, pool) = make_test_db().await;
+        for (id, name) in [("b1", "Saga #1"), ("b2", "Saga #2")] {
+            create_resource(
+                &pool,
+                Resource::Books,
+                &json!({"id": id, "name": name, "path": format!("/comics/{}.cbz", id)}),
+            )
+            .await
+            .unwrap();
+        }
+        (temp, pool)
+    }
+
+    fn view(book_id: &str, page: i64, user_agent: &str) -> PageView {
+        PageView {
+            book_id: book_id.to_string(),
+            page,
+            page_count: Some(3),
+            user_agent: Some(user_agent.to_string()),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_page_views_are_grouped_into_sessions() {
+        let (_temp, pool) = setup_db().await;
+        let start = 1_700_000_000;
+        let first = record_page_view(&pool, &view("b1", 0, "tablet"), start)
+            .await
+            .unwrap();
+        for (page, offset) in [(1, 60), (1, 90), (2, 120)] {
+            let session = record_page_view(&pool, &view("b1", page, "tablet"), start + offset)
+                .await
+                .unwrap();
+            assert_eq!(session.id, first.id);
+        }
+        let other_device = record_page_view(&pool, &view("b1", 1, "phone"), start + 130)
+            .await
+            .unwrap();
+        assert_ne!(other_device.id, first.id);
+
+        let sessions = list_reading_history(&pool, Some("b1"), 10).await.unwrap();
+        assert_eq!(sessions.len(), 2);
+        let tablet = &sessions[1];
+        assert_eq!((tablet.started, tablet.ended), (start, start + 120));
+        assert_eq!((tablet.first_page, tablet.last_page), (0, 2));
+        assert_eq!(tablet.pages_read, 3);
+        assert!(tablet.completed);
+        assert!(!sessions[0].completed);
+
+        let reread = record_page_view(&pool, &view("b1", 0, "tablet"), start + 200)
+            .await
+            .unwrap();
+        assert_ne!(reread.id, first.id);
+        record_page_view(&pool, &view("b1", 2, "tablet"), start + 260)
+            .await
+            .unwrap();
+        let later = start + SESSION_TIMEOUT * 4;
+        record_page_view(&pool, &view("b2", 0, "tablet"), later)
+            .await
+            .unwrap();
+
+        let summary = get_book_reading_summary(&pool, "b1").await.unwrap();
+        assert_eq!(summary.sessions, 3);
+        assert_eq!(summary.time_spent, 180);
+        assert_eq!(summary.pages_read, 3);
+        assert_eq!((summary.reads, summary.rereads), (2, 1));
+        assert_eq!(summary.last_read, Some(start + 260));
+        assert_eq!(
+            get_book_reading_summary(&pool, "nope").await.unwrap(),
+            BookReadingSummary {
+                book_id: "nope".to_string(),
+                ..Default::default()
+            }
+        );
+
+        let recent: Vec<String> = get_recently_read(&pool, 10)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|recent| recent.book.id)
+            .collect();
+        assert_eq!(recent, vec!["b2", "b1"]);
+        assert_eq!(list_reading_history(&pool, None, 2).await.unwrap().len(), 2);
+
+        delete_resource(&pool, Resource::Books, "b1").await.unwrap();
+        assert!(
+            list_reading_history(&pool, Some("b1"), 10)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_record_current_page_resolves_opened_book() {
+        let (temp, pool) = setup_db().await;
+        let current_book = temp.path().join("current_book");
+        assert!(
+            record_current_page(&pool, &current_book, 0, 2, None)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        fs::create_dir_all(&current_book).unwrap();
+        fs::write(current_book.join("path.txt"), "/comics/b2.cbz\n").unwrap();
+        let session = record_current_page(&pool, &current_book, 1, 2, None)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(session.book_id, "b2");
+        assert_eq!(session.page_count, Some(2));
+        assert!(session.completed);
+    }
+}
@@ -0,0 +1,366 @@
+use serde::{Deserialize, Serialize};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, SqlitePool};
+use std::collections::HashSet;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadingOrder {
+    pub id: i64,
+    pub name: String,
+    pub description: Option<String>,
+    pub total: i64,
+    pub read: i64,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CblReference {
+    pub series: String,
+    pub number: Option<String>,
+    pub volume: Option<String>,
+    pub year: Option<u16>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ReadingOrderEntry {
+    pub id: i64,
+    pub position: i64,
+    pub book_id: Option<String>,
+    pub name: Option<String>,
+    pub cover: Option<String>,
+    pub series: Option<String>,
+    pub read: bool,
+    pub reading: bool,
+    pub reference: Option<CblReference>,
+    pub confidence: Option<f64>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadingOrderDetail {
+    #[serde(flatten)]
+    pub order: ReadingOrder,
+    pub current: Option<i64>,
+    pub entries: Vec<ReadingOrderEntry>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct NextInReadingOrder {
+    pub order_id: i64,
+    pub order_name: String,
+    pub entry: ReadingOrderEntry,
+}
+
+const ORDER_QUERY: &str = "SELECT ReadingOrders.ID_READING_ORDER, ReadingOrders.NAME, ReadingOrders.description, \
+     COUNT(ReadingOrderBooks.ID_ENTRY) AS total, \
+     COALESCE(SUM(CASE WHEN Books.read THEN 1 ELSE 0 END), 0) AS read_count \
+     FROM ReadingOrders \
+     LEFT JOIN ReadingOrderBooks ON ReadingOrderBooks.ID_READING_ORDER = ReadingOrders.ID_READING_ORDER \
+     LEFT JOIN Books ON Books.ID_book = ReadingOrderBooks.ID_book";
+
+pub(crate) const ENTRY_QUERY: &str = "SELECT ReadingOrderBooks.ID_ENTRY, ReadingOrderBooks.position, ReadingOrderBooks.ID_book, \
+     Books.NOM, Books.URLCover, Books.read, Books.reading, Series.title, \
+     ReadingOrderBooks.cblSeries, ReadingOrderBooks.cblNumber, ReadingOrderBooks.cblVolume, \
+     ReadingOrderBooks.cblYear, ReadingOrderBooks.confidence \
+     FROM ReadingOrderBooks \
+     LEFT JOIN Books ON Books.ID_book = ReadingOrderBooks.ID_book \
+     LEFT JOIN Series ON Series.ID_Series = Books.ID_Series";
+
+fn row_to_order(row: &SqliteRow) -> Result<ReadingOrder, sqlx::Error> {
+    Ok(ReadingOrder {
+        id: row.try_get("ID_READING_ORDER")?,
+        name: row.try_get("NAME")?,
+        description: row.try_get("description")?,
+        total: row.try_get("total")?,
+        read: row.try_get("read_count")?,
+    })
+}
+
+pub(crate) fn row_to_entry(row: &SqliteRow) -> Result<ReadingOrderEntry, sqlx::Error> {
+    Ok(ReadingOrderEntry {
+        id: row.try_get("ID_ENTRY")?,
+        position: row.try_get("position")?,
+        book_id: row.try_get("ID_book")?,
+        name: row.try_get("NOM")?,
+        cover: row.try_get("URLCover")?,
+        series: row.try_get("title")?,
+        read: row.try_get::<Option<bool>, _>("read")?.unwrap_or(false),
+        reading: row.try_get::<Option<bool>, _>("reading")?.unwrap_or(false),
+        reference: row
+            .try_get::<Option<String>, _>("cblSeries")?
+            .map(|series| -> Result<CblReference, sqlx::Error> {
+                Ok(CblReference {
+                    series,
+                    number: row.try_get("cblNumber")?,
+                    volume: row.try_get("cblVolume")?,
+                    year: row.try_get("cblYear")?,
+                })
+            })
+            .transpose()?,
+        confidence: row.try_get("confidence")?,
+    })
+}
+
+pub async fn list_reading_orders(db_pool: &SqlitePool) -> Result<Vec<ReadingOrder>, sqlx::Error> {
+    let rows = sqlx::query(&format!(
+        "{} GROUP BY ReadingOrders.ID_READING_ORDER ORDER BY ReadingOrders.NAME;",
+        ORDER_QUERY
+    ))
+    .fetch_all(db_pool)
+    .await?;
+    rows.iter().map(row_to_order).collect()
+}
+
+pub async fn create_reading_order(
+    db_pool: &SqlitePool,
+    name: &str,
+    description: Option<&str>,
+) -> Result<i64, sqlx::Error> {
+    let result = sqlx::query("INSERT INTO ReadingOrders (NAME, description) VALUES (?, ?);")
+        .bind(name)
+        .bind(description)
+        .execute(db_pool)
+        .await?;
+    Ok(result.last_insert_rowid())
+}
+
+pub async fn update_reading_order(
+    db_pool: &SqlitePool,
+    order_id: i64,
+    name: &str,
+    description: Option<&str>,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE ReadingOrders SET NAME = ?, description = ? WHERE ID_READING_ORDER = ?;",
+    )
+    .bind(name)
+    .bind(description)
+    .bind(order_id)
+    .execute(db_pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn delete_reading_order(
+    db_pool: &SqlitePool,
+    order_id: i64,
+) -> Result<bool, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    sqlx::query("DELETE FROM ReadingOrderBooks WHERE ID_READING_ORDER = ?;")
+        .bind(order_id)
+        .execute(&mut *tx)
+        .await?;
+    let result = sqlx::query("DELETE FROM ReadingOrders WHERE ID_READING_ORDER = ?;")
+        .bind(order_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn reading_order_exists(
+    db_pool: &SqlitePool,
+    order_id: i64,
+) -> Result<bool, sqlx::Error> {
+    let (exists,): (bool,) =
+        sqlx::query_as("SELECT EXISTS(SELECT 1 FROM ReadingOrders WHERE ID_READING_ORDER = ?);")
+            .bind(order_id)
+            .fetch_one(db_pool)
+            .await?;
+    Ok(exists)
+}
+
+pub async fn get_unknown_books(
+    db_pool: &SqlitePool,
+    book_ids: &[String],
+) -> Result<Vec<String>, sqlx::Error> {
+    let mut unknown = Vec::new();
+    for book_id in book_ids {
+        let (exists,): (bool,) =
+            sqlx::query_as("SELECT EXISTS(SELECT 1 FROM Books WHERE ID_book = ?);")
+                .bind(book_id)
+                .fetch_one(db_pool)
+                .await?;
+        if !exists && !unknown.contains(book_id) {
+            unknown.push(book_id.clone());
+        }
+    }
+    Ok(unknown)
+}
+
+pub async fn get_reading_order(
+    db_pool: &SqlitePool,
+    order_id: i64,
+) -> Result<Option<ReadingOrderDetail>, sqlx::Error> {
+    let Some(row) = sqlx::query(&format!(
+        "{} WHERE ReadingOrders.ID_READING_ORDER = ? GROUP BY ReadingOrders.ID_READING_ORDER;",
+        ORDER_QUERY
+    ))
+    .bind(order_id)
+    .fetch_optional(db_pool)
+    .await?
+    else {
+        return Ok(None);
+    };
+    let order = row_to_order(&row)?;
+    let entries = sqlx::query(&format!(
+        "{} WHERE ReadingOrderBooks.ID_READING_ORDER = ? ORDER BY ReadingOrderBooks.position;",
+        ENTRY_QUERY
+    ))
+    .bind(order_id)
+    .fetch_all(db_pool)
+    .await?
+    .iter()
+    .map(row_to_entry)
+    .collect::<Result<Vec<_>, _>>()?;
+    let current = entries
+        .iter()
+        .find(|entry| entry.book_id.is_some() && !entry.read)
+        .map(|entry| entry.id);
+    Ok(Some(ReadingOrderDetail {
+        order,
+        current,
+        entries,
+    }))
+}
+
+pub async fn add_books_to_reading_order(
+    db_pool: &SqlitePool,
+    order_id: i64,
+    book_ids: &[String],
+    position: Option<i64>,
+) -> Result<(), sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    let (count,): (i64,) =
+        sqlx::query_as("SELECT COUNT(*) FROM ReadingOrderBooks WHERE ID_READING_ORDER = ?;")
+            .bind(order_id)
+            .fetch_one(&mut *tx)
+            .await?;
+    let start = position.unwrap_or(count).clamp(0, count);
+    sqlx::query(
+        "UPDATE ReadingOrderBooks SET position = position + ? WHERE ID_READING_ORDER = ? AND position >= ?;",
+    )
+    .bind(book_ids.len() as i64)
+    .bind(order_id)
+    .bind(start)
+    .execute(&mut *tx)
+    .await?;
+    for (i, book_id) in book_ids.iter().enumerate() {
+        sqlx::query(
+            "INSERT INTO ReadingOrderBooks (ID_READING_ORDER, ID_book, position) VALUES (?, ?, ?);",
+        )
+        .bind(order_id)
+        .bind(book_id)
+        .bind(start + i as i64)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await
+}
+
+async fn compact_positions(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    order_id: i64,
+    entry_ids: &[i64],
+) -> Result<(), sqlx::Error> {
+    for (position, entry_id) in entry_ids.iter().enumerate() {
+        sqlx::query(
+            "UPDATE ReadingOrderBooks SET position = ? WHERE ID_ENTRY = ? AND ID_READING_ORDER = ?;",
+        )
+        .bind(position as i64)
+        .bind(entry_id)
+        .bind(order_id)
+        .execute(&mut **tx)
+        .await?;
+    }
+    Ok(())
+}
+
+async fn get_entry_ids(
+    tx: &mut sqlx::Transaction<'_, sqlx::Sqlite>,
+    order_id: i64,
+) -> Result<Vec<i64>, sqlx::Error> {
+    let rows: Vec<(i64,)> = sqlx::query_as(
+        "SELECT ID_ENTRY FROM ReadingOrderBooks WHERE ID_READING_ORDER = ? ORDER BY position;",
+    )
+    .bind(order_id)
+    .fetch_all(&mut **tx)
+    .await?;
+    Ok(rows.into_iter().map(|(id,)| id).collect())
+}
+
+pub async fn remove_entry_from_reading_order(
+    db_pool: &SqlitePool,
+    order_id: i64,
+    entry_id: i64,
+) -> Result<bool, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    let result =
+        sqlx::query("DELETE FROM ReadingOrderBooks WHERE ID_ENTRY = ? AND ID_READING_ORDER = ?;")
+            .bind(entry_id)
+            .bind(order_id)
+            .execute(&mut *tx)
+            .await?;
+    let remaining = get_entry_ids(&mut tx, order_id).await?;
+    compact_positions(&mut tx, order_id, &remaining).await?;
+    tx.commit().await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn reorder_reading_order(
+    db_pool: &SqlitePool,
+    order_id: i64,
+    entry_ids: &[i64],
+) -> Result<bool, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    let existing: HashSet<i64> = get_entry_ids(&mut tx, order_id)
+        .await?
+        .into_iter()
+        .collect();
+    let requested: HashSet<i64> = entry_ids.iter().copied().collect();
+    if existing != requested || requested.len() != entry_ids.len() {
+        return Ok(false);
+    }
+    compact_positions(&mut tx, order_id, entry_ids).await?;
+    tx.commit().await?;
+    Ok(true)
+}
+
+pub async fn get_next_in_reading_orders(
+    db_pool: &SqlitePool,
+    book_id: &str,
+) -> Result<Vec<NextInReadingOrder>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT ReadingOrders.ID_READING_ORDER, ReadingOrders.NAME, ReadingOrderBooks.position \
+         FROM ReadingOrderBooks \
+         JOIN ReadingOrders ON ReadingOrders.ID_READING_ORDER = ReadingOrderBooks.ID_READING_ORDER \
+         WHERE ReadingOrderBooks.ID_book = ? ORDER BY ReadingOrders.NAME;",
+    )
+    .bind(book_id)
+    .fetch_all(db_pool)
+    .await?;
+
+    let mut suggestions = Vec::new();
+    for row in rows {
+        let order_id: i64 = row.try_get("ID_READING_ORDER")?;
+        let position: i64 = row.try_get("position")?;
+        let next = sqlx::query(&format!(
+            "{} WHERE ReadingOrderBooks.ID_READING_ORDER = ? AND ReadingOrderBooks.position > ? \
+             AND ReadingOrderBooks.ID_book IS NOT NULL AND ReadingOrderBooks.ID_book != ? \
+             AND NOT COALESCE(Books.read, false) \
+             ORDER BY ReadingOrderBooks.position LIMIT 1;",
+            ENTRY_QUERY
+        ))
+        .bind(order_id)
+        .bind(position)
+        .bind(book_id)
+        .fetch_optional(db_pool)
+        .await?;
+        if let Some(next) = next {
+            suggestions.push(NextInReadingOrder {
+                order_id,
+                order_name: row.try_get("NAME")?,
+                entry: row_to_entry(&next)?,
+            });
+        }
+    }
+    Ok(suggestions)
+}
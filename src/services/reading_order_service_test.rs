@@ -0,0 +1,154 @@
+#[cfg(test)]
+mod tests {
+    use crate::repositories::database_repo::make_test_db;
+    use crate::services::reading_order_service::*;
+    use sqlx::SqlitePool;
+    use tempfile::TempDir;
+
+    async fn setup_db() -> (TempDir, SqlitePool) {
+        let (temp, pool) = make_test_db().await;
+        sqlx::query(
+            "INSERT INTO Series (ID_Series, title, favorite, PATH) VALUES ('s1', 'Saga', false, '/saga'), ('s2', 'Paper Girls', false, '/paper');",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        for (id, series) in [("a", "s1"), ("b", "s2"), ("c", "s1"), ("d", "s2")] {
+            sqlx::query(
+                "INSERT INTO Books (ID_book, NOM, read, reading, unread, favorite, last_page, folder, PATH, ID_Series) \
+                 VALUES (?, ?, false, false, true, false, 0, false, ?, ?);",
+            )
+            .bind(id)
+            .bind(format!("Book {}", id))
+            .bind(format!("/books/{}.cbz", id))
+            .bind(series)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        (temp, pool)
+    }
+
+    fn book_ids(detail: &ReadingOrderDetail) -> Vec<&str> {
+        detail
+            .entries
+            .iter()
+            .map(|entry| entry.book_id.as_deref().unwrap())
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_add_books_keeps_order_and_inserts_at_position() {
+        let (_temp, pool) = setup_db().await;
+        let id = create_reading_order(&pool, "Crossover", Some("Event"))
+            .await
+            .unwrap();
+
+        add_books_to_reading_order(&pool, id, &["a".to_string(), "b".to_string()], None)
+            .await
+            .unwrap();
+        add_books_to_reading_order(&pool, id, &["c".to_string()], Some(1))
+            .await
+            .unwrap();
+
+        let detail = get_reading_order(&pool, id).await.unwrap().unwrap();
+        assert_eq!(book_ids(&detail), vec!["a", "c", "b"]);
+        assert_eq!(
+            detail
+                .entries
+                .iter()
+                .map(|e| e.position)
+                .collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert_eq!(detail.entries[1].series.as_deref(), Some("Saga"));
+        assert_eq!(detail.order.total, 3);
+
+        assert!(reading_order_exists(&pool, id).await.unwrap());
+        assert!(!reading_order_exists(&pool, id + 1).await.unwrap());
+        let requested = ["a", "x", "y", "x"].map(String::from);
+        assert_eq!(
+            get_unknown_books(&pool, &requested).await.unwrap(),
+            vec!["x", "y"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reorder_and_remove_entries() {
+        let (_temp, pool) = setup_db().await;
+        let id = create_reading_order(&pool, "Crossover", None)
+            .await
+            .unwrap();
+        let books = ["a", "b", "c"].map(String::from);
+        add_books_to_reading_order(&pool, id, &books, None)
+            .await
+            .unwrap();
+        let detail = get_reading_order(&pool, id).await.unwrap().unwrap();
+        let entries: Vec<i64> = detail.entries.iter().map(|e| e.id).collect();
+
+        assert!(
+            !reorder_reading_order(&pool, id, &entries[..2])
+                .await
+                .unwrap()
+        );
+        assert!(
+            reorder_reading_order(&pool, id, &[entries[2], entries[0], entries[1]])
+                .await
+                .unwrap()
+        );
+        let detail = get_reading_order(&pool, id).await.unwrap().unwrap();
+        assert_eq!(book_ids(&detail), vec!["c", "a", "b"]);
+
+        assert!(
+            remove_entry_from_reading_order(&pool, id, entries[0])
+                .await
+                .unwrap()
+        );
+        let detail = get_reading_order(&pool, id).await.unwrap().unwrap();
+        assert_eq!(book_ids(&detail), vec!["c", "b"]);
+        assert_eq!(
+            detail
+                .entries
+                .iter()
+                .map(|e| e.position)
+                .collect::<Vec<_>>(),
+            vec![0, 1]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_progress_and_next_in_arc() {
+        let (_temp, pool) = setup_db().await;
+        let id = create_reading_order(&pool, "Crossover", None)
+            .await
+            .unwrap();
+        let books = ["a", "b", "c", "d"].map(String::from);
+        add_books_to_reading_order(&pool, id, &books, None)
+            .await
+            .unwrap();
+        sqlx::query("UPDATE Books SET read = true WHERE ID_book IN ('a', 'c');")
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let detail = get_reading_order(&pool, id).await.unwrap().unwrap();
+        assert_eq!(detail.order.read, 2);
+        assert_eq!(detail.current, Some(detail.entries[1].id));
+
+        let next = get_next_in_reading_orders(&pool, "b").await.unwrap();
+        assert_eq!(next.len(), 1);
+        assert_eq!(next[0].order_name, "Crossover");
+        assert_eq!(next[0].entry.book_id.as_deref(), Some("d"));
+
+        assert!(
+            get_next_in_reading_orders(&pool, "d")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+
+        assert!(delete_reading_order(&pool, id).await.unwrap());
+        assert!(get_reading_order(&pool, id).await.unwrap().is_none());
+        assert!(list_reading_orders(&pool).await.unwrap().is_empty());
+    }
+}
@@ -0,0 +1,661 @@
+use crate::models::book_model::Book;
+use crate::models::bookmark_model::Bookmark;
+use crate::models::character_model::Character;
+use crate::models::creator_model::Creator;
+use crate::models::library_model::Library;
+use crate::models::series_model::Series;
+use crate::services::collection_service::ItemType;
+use crate::services::library_service::{get_book_id, get_series_id};
+use crate::services::pagination_service::{
+    LIST_PARAMS, ListQuery, Page, get_fields, get_sort_key, select_fields, sort_order,
+};
+use crate::services::scheduler_service::is_valid_schedule;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::sqlite::SqliteRow;
+use sqlx::{FromRow, QueryBuilder, Sqlite, SqlitePool};
+use std::collections::HashMap;
+use std::path::Path;
+
+pub type ResourceItem = Map<String, Value>;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Resource {
+    Books,
+    Series,
+    Libraries,
+    Bookmarks,
+    Creators,
+    Characters,
+}
+
+impl Resource {
+    pub const ALL: [Resource; 6] = [
+        Resource::Books,
+        Resource::Series,
+        Resource::Libraries,
+        Resource::Bookmarks,
+        Resource::Creators,
+        Resource::Characters,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Resource::Books => "books",
+            Resource::Series => "series",
+            Resource::Libraries => "libraries",
+            Resource::Bookmarks => "bookmarks",
+            Resource::Creators => "creators",
+            Resource::Characters => "characters",
+        }
+    }
+
+    fn def(&self) -> &'static ResourceDef {
+        match self {
+            Resource::Books => &BOOKS,
+            Resource::Series => &SERIES,
+            Resource::Libraries => &LIBRARIES,
+            Resource::Bookmarks => &BOOKMARKS,
+            Resource::Creators => &CREATORS,
+            Resource::Characters => &CHARACTERS,
+        }
+    }
+
+    fn item_type(&self) -> Option<ItemType> {
+        match self {
+            Resource::Books => Some(ItemType::Book),
+            Resource::Series => Some(ItemType::Series),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FieldKind {
+    Text,
+    Integer,
+    Bool,
+    Json,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum KeyKind {
+    Auto,
+    Derived,
+    Provided,
+}
+
+struct ResourceField {
+    name: &'static str,
+    column: &'static str,
+    kind: FieldKind,
+    required: bool,
+    writable: bool,
+    default: Option<i64>,
+}
+
+struct ResourceDef {
+    table: &'static str,
+    key: &'static str,
+    key_kind: KeyKind,
+    fields: &'static [ResourceField],
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Bound {
+    Null,
+    Text(String),
+    Integer(i64),
+    Bool(bool),
+}
+
+const fn field(name: &'static str, column: &'static str, kind: FieldKind) -> ResourceField {
+    ResourceField {
+        name,
+        column,
+        kind,
+        required: false,
+        writable: true,
+        default: None,
+    }
+}
+
+const fn required(name: &'static str, column: &'static str, kind: FieldKind) -> ResourceField {
+    ResourceField {
+        required: true,
+        ..field(name, column, kind)
+    }
+}
+
+const fn with_default(
+    name: &'static str,
+    column: &'static str,
+    kind: FieldKind,
+    default: i64,
+) -> ResourceField {
+    ResourceField {
+        default: Some(default),
+        ..field(name, column, kind)
+    }
+}
+
+const fn read_only(name: &'static str, column: &'static str, kind: FieldKind) -> ResourceField {
+    ResourceField {
+        writable: false,
+        ..field(name, column, kind)
+    }
+}
+
+static BOOKS: ResourceDef = ResourceDef {
+    table: "Books",
+    key: "ID_book",
+    key_kind: KeyKind::Derived,
+    fields: &[
+        required("name", "NOM", FieldKind::Text),
+        required("path", "PATH", FieldKind::Text),
+        field("provider", "API_ID", FieldKind::Text),
+        field("series_id", "ID_Series", FieldKind::Text),
+        field("rating", "note", FieldKind::Integer),
+        with_default("read", "read", FieldKind::Bool, 0),
+        with_default("reading", "reading", FieldKind::Bool, 0),
+        with_default("unread", "unread", FieldKind::Bool, 1),
+        with_default("favorite", "favorite", FieldKind::Bool, 0),
+        with_default("last_page", "last_page", FieldKind::Integer, 0),
+        with_default("folder", "folder", FieldKind::Bool, 0),
+        with_default("lock", "lock", FieldKind::Bool, 0),
+        field("cover", "URLCover", FieldKind::Text),
+        field("issue", "issueNumber", FieldKind::Integer),
+        field("description", "description", FieldKind::Text),
+        field("format", "format", FieldKind::Text),
+        field("publisher", "publisher", FieldKind::Text),
+        field("page_count", "pageCount", FieldKind::Integer),
+        field("urls", "URLs", FieldKind::Json),
+        field("series", "series", FieldKind::Json),
+        field("creators", "creators", FieldKind::Json),
+        field("characters", "characters", FieldKind::Json),
+        field("prices", "prices", FieldKind::Json),
+        field("dates", "dates", FieldKind::Json),
+        field("collected_issues", "collectedIssues", FieldKind::Json),
+        field("collections", "collections", FieldKind::Json),
+        field("variants", "variants", FieldKind::Json),
+        read_only("cover_placeholder", "coverPlaceholder", FieldKind::Text),
+        read_only("content_hash", "contentHash", FieldKind::Text),
+        read_only("cover_hash", "coverHash", FieldKind::Text),
+        read_only("file_size", "fileSize", FieldKind::Integer),
+        read_only("file_modified", "fileModified", FieldKind::Integer),
+        read_only("file_inode", "fileInode", FieldKind::Integer),
+        read_only("missing", "missing", FieldKind::Bool),
+    ],
+};
+
+static SERIES: ResourceDef = ResourceDef {
+    table: "Series",
+    key: "ID_Series",
+    key_kind: KeyKind::Derived,
+    fields: &[
+        required("title", "title", FieldKind::Json),
+        required("path", "PATH", FieldKind::Text),
+        field("rating", "note", FieldKind::Integer),
+        field("status", "statut", FieldKind::Text),
+        field("start_date", "start_date", FieldKind::Json),
+        field("end_date", "end_date", FieldKind::Json),
+        field("description", "description", FieldKind::Text),
+        field("score", "Score", FieldKind::Integer),
+        field("genres", "genres", FieldKind::Json),
+        field("cover", "cover", FieldKind::Text),
+        field("background", "BG", FieldKind::Text),
+        field("characters", "CHARACTERS", FieldKind::Json),
+        field("trending", "TRENDING", FieldKind::Integer),
+        field("staff", "STAFF", FieldKind::Json),
+        field("source", "SOURCE", FieldKind::Text),
+        field("volumes", "volumes", FieldKind::Integer),
+        field("chapters", "chapters", FieldKind::Integer),
+        with_default("favorite", "favorite", FieldKind::Bool, 0),
+        with_default("lock", "lock", FieldKind::Bool, 0),
+    ],
+};
+
+static LIBRARIES: ResourceDef = ResourceDef {
+    table: "Libraries",
+    key: "ID_LIBRARY",
+    key_kind: KeyKind::Auto,
+    fields: &[
+        required("name", "NAME", FieldKind::Text),
+        required("path", "PATH", FieldKind::Text),
+        required("provider", "API_ID", FieldKind::Text),
+        field("rescan_schedule", "rescanSchedule", FieldKind::Text),
+        field("refresh_schedule", "refreshSchedule", FieldKind::Text),
+        with_default("schedule_enabled", "scheduleEnabled", FieldKind::Bool, 0),
+    ],
+};
+
+static BOOKMARKS: ResourceDef = ResourceDef {
+    table: "Bookmarks",
+    key: "ID_BOOKMARK",
+    key_kind: KeyKind::Auto,
+    fields: &[
+        required("book_id", "BOOK_ID", FieldKind::Text),
+        required("page", "page", FieldKind::Integer),
+        field("path", "PATH", FieldKind::Text),
+        field("name", "name", FieldKind::Text),
+        field("note", "note", FieldKind::Text),
+        read_only("thumbnail", "thumbnail", FieldKind::Text),
+        read_only("created", "created", FieldKind::Integer),
+    ],
+};
+
+static CREATORS: ResourceDef = ResourceDef {
+    table: "Creators",
+    key: "ID_CREATOR",
+    key_kind: KeyKind::Provided,
+    fields: &[
+        required("name", "name", FieldKind::Text),
+        field("image", "image", FieldKind::Json),
+        field("description", "description", FieldKind::Text),
+        field("url", "url", FieldKind::Json),
+    ],
+};
+
+static CHARACTERS: ResourceDef = ResourceDef {
+    table: "Characters",
+    key: "ID_CHAR",
+    key_kind: KeyKind::Provided,
+    fields: &[
+        required("name", "name", FieldKind::Text),
+        field("image", "image", FieldKind::Json),
+        field("description", "description", FieldKind::Text),
+        field("url", "url", FieldKind::Json),
+    ],
+};
+
+fn find_field(def: &ResourceDef, name: &str) -> Result<&'static ResourceField, String> {
+    def.fields
+        .iter()
+        .find(|field| field.name == name)
+        .ok_or_else(|| format!("Unknown field: {}", name))
+}
+
+fn bind_value(field: &ResourceField, value: &Value) -> Result<Bound, String> {
+    let invalid = || format!("Invalid value for field: {}", field.name);
+    match (field.kind, value) {
+        (_, Value::Null) if field.required => Err(format!("Field is required: {}", field.name)),
+        (_, Value::Null) => Ok(match field.default {
+            Some(default) if field.kind == FieldKind::Bool => Bound::Bool(default != 0),
+            Some(default) => Bound::Integer(default),
+            None => Bound::Null,
+        }),
+        (FieldKind::Text, Value::String(text)) if field.required && text.trim().is_empty() => {
+            Err(format!("Field is required: {}", field.name))
+        }
+        (FieldKind::Text, Value::String(text)) => Ok(Bound::Text(text.clone())),
+        (FieldKind::Integer, value) => value.as_i64().map(Bound::Integer).ok_or_else(invalid),
+        (FieldKind::Bool, Value::Bool(flag)) => Ok(Bound::Bool(*flag)),
+        (FieldKind::Json, Value::String(text)) => Ok(Bound::Text(text.clone())),
+        (FieldKind::Json, value) => Ok(Bound::Text(value.to_string())),
+        _ => Err(invalid()),
+    }
+}
+
+fn bind_filter(field: &ResourceField, value: &str) -> Result<Bound, String> {
+    let invalid = || format!("Invalid filter for field: {}", field.name);
+    match field.kind {
+        FieldKind::Text => Ok(Bound::Text(value.to_string())),
+        FieldKind::Integer => value.parse().map(Bound::Integer).map_err(|_| invalid()),
+        FieldKind::Bool => value.parse().map(Bound::Bool).map_err(|_| invalid()),
+        FieldKind::Json => Err(format!("Cannot filter on field: {}", field.name)),
+    }
+}
+
+fn push_bound(qb: &mut QueryBuilder<'_, Sqlite>, value: Bound) {
+    match value {
+        Bound::Null => qb.push("NULL"),
+        Bound::Text(text) => qb.push_bind(text),
+        Bound::Integer(number) => qb.push_bind(number),
+        Bound::Bool(flag) => qb.push_bind(flag),
+    };
+}
+
+fn push_key(qb: &mut QueryBuilder<'_, Sqlite>, def: &ResourceDef, id: &str) {
+    qb.push(format!("{} = ", def.key));
+    match def.key_kind {
+        KeyKind::Auto => qb.push_bind(id.parse::<i64>().unwrap_or(-1)),
+        _ => qb.push_bind(id.to_string()),
+    };
+}
+
+fn validate_payload(
+    resource: Resource,
+    payload: &Value,
+    creating: bool,
+) -> Result<Vec<(&'static str, Bound)>, String> {
+    let def = resource.def();
+    let payload = payload
+        .as_object()
+        .ok_or_else(|| "Expected a JSON object".to_string())?;
+    let mut values = Vec::new();
+    for (name, value) in payload {
+        if name == "id" {
+            if creating && def.key_kind != KeyKind::Auto {
+                continue;
+            }
+            return Err("Field is read-only: id".to_string());
+        }
+        let field = find_field(def, name)?;
+        if !field.writable {
+            return Err(format!("Field is read-only: {}", name));
+        }
+        if let (Some(schedule), true) = (value.as_str(), name.ends_with("_schedule"))
+            && !schedule.trim().is_empty()
+            && !is_valid_schedule(schedule)
+        {
+            return Err(format!("Invalid schedule: {}", name));
+        }
+        values.push((field.column, bind_value(field, value)?));
+    }
+    if creating {
+        for field in def.fields.iter().filter(|field| field.writable) {
+            if !payload.contains_key(field.name) {
+                values.push((field.column, bind_value(field, &Value::Null)?));
+            }
+        }
+    } else if values.is_empty() {
+        return Err("No fields to update".to_string());
+    }
+    Ok(values)
+}
+
+fn payload_text<'a>(payload: &'a Value, name: &str) -> Option<&'a str> {
+    payload[name]
+        .as_str()
+        .filter(|text| !text.trim().is_empty())
+}
+
+fn get_new_id(resource: Resource, payload: &Value) -> Result<Option<String>, String> {
+    if let Some(id) = payload_text(payload, "id") {
+        return Ok(Some(id.to_string()));
+    }
+    match resource {
+        Resource::Books => Ok(Some(get_book_id(
+            Path::new(payload_text(payload, "path").unwrap_or_default()),
+            payload_text(payload, "provider").unwrap_or("0"),
+        ))),
+        Resource::Series => Ok(Some(get_series_id(
+            payload_text(payload, "path").unwrap_or_default(),
+        ))),
+        _ if resource.def().key_kind == KeyKind::Provided => {
+            Err("Field is required: id".to_string())
+        }
+        _ => Ok(None),
+    }
+}
+
+async fn check_references(
+    db_pool: &SqlitePool,
+    resource: Resource,
+    payload: &Value,
+    values: &mut [(&'static str, Bound)],
+) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    if let Some(provider) = payload_text(payload, "provider") {
+        let exists: Option<String> = sqlx::query_scalar("SELECT ID_API FROM API WHERE ID_API = ?;")
+            .bind(provider)
+            .fetch_optional(db_pool)
+            .await?;
+        if exists.is_none() {
+            return Err(format!("Unknown provider: {}", provider).into());
+        }
+    }
+    if resource == Resource::Bookmarks
+        && let Some(book_id) = payload_text(payload, "book_id")
+    {
+        let path: Option<String> = sqlx::query_scalar("SELECT PATH FROM Books WHERE ID_book = ?;")
+            .bind(book_id)
+            .fetch_optional(db_pool)
+            .await?;
+        let path = path.ok_or_else(|| format!("Unknown book: {}", book_id))?;
+        for (column, value) in values.iter_mut() {
+            if *column == "PATH" && *value == Bound::Null {
+                *value = Bound::Text(path.clone());
+            }
+        }
+    }
+    Ok(())
+}
+
+fn to_item<T: Serialize>(model: T) -> Result<ResourceItem, sqlx::Error> {
+    match serde_json::to_value(model) {
+        Ok(Value::Object(item)) => Ok(item),
+        Ok(_) => Err(sqlx::Error::Decode("Expected a JSON object".into())),
+        Err(e) => Err(sqlx::Error::Decode(Box::new(e))),
+    }
+}
+
+fn row_to_item(row: &SqliteRow, resource: Resource) -> Result<ResourceItem, sqlx::Error> {
+    match resource {
+        Resource::Books => to_item(Book::from_row(row)?),
+        Resource::Series => to_item(Series::from_row(row)?),
+        Resource::Libraries => to_item(Library::from_row(row)?),
+        Resource::Bookmarks => to_item(Bookmark::from_row(row)?),
+        Resource::Creators => to_item(Creator::from_row(row)?),
+        Resource::Characters => to_item(Character::from_row(row)?),
+    }
+}
+
+fn select_query(def: &ResourceDef) -> QueryBuilder<'static, Sqlite> {
+    QueryBuilder::new(format!("SELECT * FROM {}", def.table))
+}
+
+fn push_filters(
+    qb: &mut QueryBuilder<'_, Sqlite>,
+    def: &ResourceDef,
+    filters: &HashMap<String, String>,
+) -> Result<(), String> {
+    qb.push(" WHERE 1");
+    for (name, value) in filters {
+        if LIST_PARAMS.contains(&name.as_str()) {
+            continue;
+        }
+        let field = find_field(def, name)?;
+        match bind_filter(field, value)? {
+            Bound::Bool(flag) => {
+                qb.push(format!(
+                    " AND {} {} (1, 'true')",
+                    field.column,
+                    if flag { "IN" } else { "NOT IN" }
+                ));
+            }
+            value => {
+                qb.push(format!(" AND {} = ", field.column));
+                push_bound(qb, value);
+            }
+        }
+    }
+    Ok(())
+}
+
+pub fn sort_keys(resource: Resource) -> Vec<&'static str> {
+    std::iter::once("id")
+        .chain(
+            resource
+                .def()
+                .fields
+                .iter()
+                .filter(|field| field.kind != FieldKind::Json)
+                .map(|field| field.name),
+        )
+        .collect()
+}
+
+pub fn field_names(resource: Resource) -> Vec<&'static str> {
+    std::iter::once("id")
+        .chain(resource.def().fields.iter().map(|field| field.name))
+        .collect()
+}
+
+pub async fn list_resources(
+    db_pool: &SqlitePool,
+    resource: Resource,
+    filters: &HashMap<String, String>,
+    query: &ListQuery,
+) -> Result<Page<ResourceItem>, Box<dyn std::error::Error + Send + Sync>> {
+    let def = resource.def();
+    let sort = get_sort_key(query, &sort_keys(resource))?
+        .and_then(|sort| find_field(def, sort).ok())
+        .map_or(def.key, |field| field.column);
+    let order = sort_order(query.order.as_deref())?;
+    let fields = get_fields(query, &field_names(resource))?;
+    let (page, limit) = query.page_and_limit();
+
+    let mut qb = select_query(def);
+    push_filters(&mut qb, def, filters)?;
+    qb.push(format!(" ORDER BY {} {}, {} LIMIT ", sort, order, def.key))
+        .push_bind(limit as i64)
+        .push(" OFFSET ")
+        .push_bind(query.offset());
+
+    let mut count_qb = QueryBuilder::<Sqlite>::new(format!("SELECT COUNT(*) FROM {}", def.table));
+    push_filters(&mut count_qb, def, filters)?;
+    let (total,): (i64,) = count_qb.build_query_as().fetch_one(db_pool).await?;
+
+    let items = qb
+        .build()
+        .fetch_all(db_pool)
+        .await?
+        .iter()
+        .map(|row| {
+            let mut item = row_to_item(row, resource)?;
+            select_fields(&mut item, fields.as_deref());
+            Ok(item)
+        })
+        .collect::<Result<_, sqlx::Error>>()?;
+    Ok(Page {
+        total,
+        page,
+        limit,
+        items,
+    })
+}
+
+pub async fn get_resource(
+    db_pool: &SqlitePool,
+    resource: Resource,
+    id: &str,
+) -> Result<Option<ResourceItem>, sqlx::Error> {
+    let def = resource.def();
+    let mut qb = select_query(def);
+    qb.push(" WHERE ");
+    push_key(&mut qb, def, id);
+    qb.build()
+        .fetch_optional(db_pool)
+        .await?
+        .as_ref()
+        .map(|row| row_to_item(row, resource))
+        .transpose()
+}
+
+pub async fn create_resource(
+    db_pool: &SqlitePool,
+    resource: Resource,
+    payload: &Value,
+) -> Result<String, Box<dyn std::error::Error + Send + Sync>> {
+    let def = resource.def();
+    let mut values = validate_payload(resource, payload, true)?;
+    let id = get_new_id(resource, payload)?;
+    check_references(db_pool, resource, payload, &mut values).await?;
+
+    let mut columns: Vec<&str> = values.iter().map(|(column, _)| *column).collect();
+    if id.is_some() {
+        columns.insert(0, def.key);
+    }
+    let mut qb = QueryBuilder::<Sqlite>::new(format!(
+        "INSERT INTO {} ({}) VALUES (",
+        def.table,
+        columns.join(", ")
+    ));
+    if let Some(id) = &id {
+        qb.push_bind(id.clone()).push(", ");
+    }
+    for (i, (_, value)) in values.into_iter().enumerate() {
+        if i > 0 {
+            qb.push(", ");
+        }
+        push_bound(&mut qb, value);
+    }
+    qb.push(")");
+    let result = qb.build().execute(db_pool).await?;
+    Ok(id.unwrap_or_else(|| result.last_insert_rowid().to_string()))
+}
+
+pub async fn update_resource(
+    db_pool: &SqlitePool,
+    resource: Resource,
+    id: &str,
+    payload: &Value,
+) -> Result<bool, Box<dyn std::error::Error + Send + Sync>> {
+    let def = resource.def();
+    let mut values = validate_payload(resource, payload, false)?;
+    check_references(db_pool, resource, payload, &mut values).await?;
+
+    let mut qb = QueryBuilder::<Sqlite>::new(format!("UPDATE {} SET ", def.table));
+    for (i, (column, value)) in values.into_iter().enumerate() {
+        if i > 0 {
+            qb.push(", ");
+        }
+        qb.push(format!("{} = ", column));
+        push_bound(&mut qb, value);
+    }
+    qb.push(" WHERE ");
+    push_key(&mut qb, def, id);
+    let result = qb.build().execute(db_pool).await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn delete_resource(
+    db_pool: &SqlitePool,
+    resource: Resource,
+    id: &str,
+) -> Result<bool, sqlx::Error> {
+    let def = resource.def();
+    let mut tx = db_pool.begin().await?;
+    if resource == Resource::Books {
+        for statement in [
+            "DELETE FROM Bookmarks WHERE BOOK_ID = ?;",
+            "DELETE FROM Annotations WHERE BOOK_ID = ?;",
+            "DELETE FROM ReadingSessionPages WHERE ID_SESSION IN \
+             (SELECT ID_SESSION FROM ReadingSessions WHERE BOOK_ID = ?);",
+            "DELETE FROM ReadingSessions WHERE BOOK_ID = ?;",
+            "UPDATE ReadingOrderBooks SET ID_book = NULL WHERE ID_book = ?;",
+        ] {
+            sqlx::query(statement).bind(id).execute(&mut *tx).await?;
+        }
+    }
+    if let Some(item_type) = resource.item_type() {
+        for statement in [
+            "DELETE FROM TagLinks WHERE itemType = ?1 AND ID_item = ?2;",
+            "DELETE FROM CollectionItems WHERE itemType = ?1 AND ID_item = ?2;",
+            "DELETE FROM CreatorLinks WHERE itemType = ?1 AND ID_item = ?2;",
+            "DELETE FROM CharacterLinks WHERE itemType = ?1 AND ID_item = ?2;",
+            "DELETE FROM CustomFieldValues WHERE ID_item = ?2 AND ID_FIELD IN \
+             (SELECT ID_FIELD FROM CustomFields WHERE target = ?1);",
+        ] {
+            sqlx::query(statement)
+                .bind(item_type.as_str())
+                .bind(id)
+                .execute(&mut *tx)
+                .await?;
+        }
+    }
+    let links = match resource {
+        Resource::Creators => Some("DELETE FROM CreatorLinks WHERE ID_CREATOR = ?;"),
+        Resource::Characters => Some("DELETE FROM CharacterLinks WHERE ID_CHAR = ?;"),
+        _ => None,
+    };
+    if let Some(statement) = links {
+        sqlx::query(statement).bind(id).execute(&mut *tx).await?;
+    }
+    let mut qb = QueryBuilder::<Sqlite>::new(format!("DELETE FROM {} WHERE ", def.table));
+    push_key(&mut qb, def, id);
+    let result = qb.build().execute(&mut *tx).await?;
+    tx.commit().await?;
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,258 @@
+#[cfg(test)]
+mod tests {
+    use crate::repositories::database_repo::make_test_db;
+    use crate::services::collection_service::ItemType;
+    use crate::services::pagination_service::ListQuery;
+    use crate::services::resource_service::*;
+    use crate::services::tag_service::{get_item_tags, set_item_tags};
+    use serde_json::json;
+    use std::collections::HashMap;
+
+    #[tokio::test]
+    async fn test_create_and_update_book_with_typed_values() {
+        let (_temp, pool) = make_test_db().await;
+        let id = create_resource(
+            &pool,
+            Resource::Books,
+            &json!({"name": "Saga #1", "path": "/comics/saga1.cbz", "provider": "0",
+                    "issue": 1, "creators": [{"name": "Brian K. Vaughan"}]}),
+        )
+        .await
+        .unwrap();
+
+        let book = get_resource(&pool, Resource::Books, &id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(book["id"], json!(id));
+        assert_eq!(book["issue"], json!(1));
+        assert_eq!(book["unread"], json!(true));
+        assert_eq!(book["read"], json!(false));
+        assert_eq!(book["creators"][0]["name"], json!("Brian K. Vaughan"));
+
+        assert!(
+            update_resource(
+                &pool,
+                Resource::Books,
+                &id,
+                &json!({"read": true, "unread": false})
+            )
+            .await
+            .unwrap()
+        );
+        let filters = HashMap::from([("read".to_string(), "true".to_string())]);
+        let books = list_resources(&pool, Resource::Books, &filters, &ListQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(books.total, 1);
+        assert_eq!(books.items[0]["unread"], json!(false));
+        assert!(
+            !update_resource(&pool, Resource::Books, "missing", &json!({"read": true}))
+                .await
+                .unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_payload_validation_rejects_unknown_and_mistyped_fields() {
+        let (_temp, pool) = make_test_db().await;
+        let cases = [
+            (json!({"name": "x"}), "Field is required: path"),
+            (
+                json!({"name": "x", "path": "/x", "NOM": "x"}),
+                "Unknown field: NOM",
+            ),
+            (
+                json!({"name": "x", "path": "/x", "issue": "1; DROP TABLE Books"}),
+                "Invalid value for field: issue",
+            ),
+            (
+                json!({"name": "x", "path": "/x", "missing": true}),
+                "Field is read-only: missing",
+            ),
+            (
+                json!({"name": "x", "path": "/x", "provider": "9"}),
+                "Unknown provider: 9",
+            ),
+        ];
+        for (payload, message) in cases {
+            let error = create_resource(&pool, Resource::Books, &payload)
+                .await
+                .unwrap_err();
+            assert_eq!(error.to_string(), message);
+        }
+
+        let error = create_resource(
+            &pool,
+            Resource::Libraries,
+            &json!({"name": "Comics", "path": "/comics", "provider": "0",
+                    "rescan_schedule": "every day"}),
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(error.to_string(), "Invalid schedule: rescan_schedule");
+        assert!(
+            create_resource(&pool, Resource::Creators, &json!({"name": "Moebius"}))
+                .await
+                .is_err()
+        );
+        let filters = HashMap::from([("issue".to_string(), "one".to_string())]);
+        assert!(
+            list_resources(&pool, Resource::Books, &filters, &ListQuery::default())
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bookmarks_and_delete_cleanup() {
+        let (_temp, pool) = make_test_db().await;
+        let book_id = create_resource(
+            &pool,
+            Resource::Books,
+            &json!({"id": "b1", "name": "Saga #1", "path": "/comics/saga1.cbz"}),
+        )
+        .await
+        .unwrap();
+        assert!(
+            create_resource(
+                &pool,
+                Resource::Bookmarks,
+                &json!({"book_id": "nope", "page": 3})
+            )
+            .await
+            .is_err()
+        );
+        let bookmark_id = create_resource(
+            &pool,
+            Resource::Bookmarks,
+            &json!({"book_id": book_id, "page": 3}),
+        )
+        .await
+        .unwrap();
+        let bookmark = get_resource(&pool, Resource::Bookmarks, &bookmark_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(bookmark["id"], json!(bookmark_id.parse::<i64>().unwrap()));
+        assert_eq!(bookmark["path"], json!("/comics/saga1.cbz"));
+        set_item_tags(&pool, ItemType::Book, &book_id, &["Signed".to_string()])
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO ReadingOrders (ID_READING_ORDER, NAME) VALUES (1, 'Saga'); \
+             INSERT INTO ReadingOrderBooks (ID_READING_ORDER, ID_book, position) \
+             VALUES (1, 'b1', 0);",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        assert!(
+            delete_resource(&pool, Resource::Books, &book_id)
+                .await
+                .unwrap()
+        );
+        assert!(
+            !delete_resource(&pool, Resource::Books, &book_id)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            get_resource(&pool, Resource::Bookmarks, &bookmark_id)
+                .await
+                .unwrap(),
+            None
+        );
+        assert!(
+            get_item_tags(&pool, ItemType::Book, &book_id)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        let entry: Option<String> =
+            sqlx::query_scalar("SELECT ID_book FROM ReadingOrderBooks WHERE ID_READING_ORDER = 1;")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(entry, None);
+    }
+
+    #[tokio::test]
+    async fn test_list_resources_pages_sorts_and_selects_fields() {
+        let (_temp, pool) = make_test_db().await;
+        for (id, name) in [
+            ("c1", "Moebius"),
+            ("c2", "Alan Moore"),
+            ("c3", "Jack Kirby"),
+        ] {
+            create_resource(&pool, Resource::Creators, &json!({"id": id, "name": name}))
+                .await
+                .unwrap();
+        }
+        let query = ListQuery {
+            page: Some(2),
+            limit: Some(2),
+            sort: Some("name".to_string()),
+            order: Some("desc".to_string()),
+            fields: Some("name".to_string()),
+        };
+        let page = list_resources(&pool, Resource::Creators, &HashMap::new(), &query)
+            .await
+            .unwrap();
+        assert_eq!((page.total, page.page, page.limit), (3, 2, 2));
+        assert_eq!(page.items.len(), 1);
+        assert_eq!(
+            serde_json::Value::Object(page.items[0].clone()),
+            json!({"id": "c2", "name": "Alan Moore"})
+        );
+
+        for query in [
+            ListQuery {
+                sort: Some("NOM".to_string()),
+                ..ListQuery::default()
+            },
+            ListQuery {
+                fields: Some("name,secret".to_string()),
+                ..ListQuery::default()
+            },
+        ] {
+            assert!(
+                list_resources(&pool, Resource::Creators, &HashMap::new(), &query)
+                    .await
+                    .is_err()
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_field_names_match_model_fields() {
+        let (_temp, pool) = make_test_db().await;
+        let payloads = [
+            (
+                Resource::Books,
+                json!({"id": "b1", "name": "Saga #1", "path": "/comics/saga1.cbz"}),
+            ),
+            (
+                Resource::Series,
+                json!({"title": {"english": "Saga"}, "path": "/comics"}),
+            ),
+            (
+                Resource::Libraries,
+                json!({"name": "Comics", "path": "/comics", "provider": "0"}),
+            ),
+            (Resource::Bookmarks, json!({"book_id": "b1", "page": 2})),
+            (Resource::Creators, json!({"id": "c1", "name": "Moebius"})),
+            (Resource::Characters, json!({"id": "h1", "name": "Arzach"})),
+        ];
+        for (resource, payload) in payloads {
+            let id = create_resource(&pool, resource, &payload).await.unwrap();
+            let item = get_resource(&pool, resource, &id).await.unwrap().unwrap();
+            let mut keys: Vec<&str> = item.keys().map(String::as_str).collect();
+            let mut names = field_names(resource);
+            keys.sort();
+            names.sort();
+            assert_eq!(keys, names, "{}", resource.as_str());
+        }
+    }
+}
@@ -0,0 +1,316 @@
+use crate::repositories::database_repo::get_db;
+use crate::services::library_service::{refresh_library_metadata, run_library_scan};
+use crate::{ApiTokens, AppGlobalVariables};
+use chrono::Utc;
+use serde::Serialize;
+use sqlx::{Row, SqlitePool};
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+use tokio_cron_scheduler::{Job, JobScheduler, JobSchedulerError};
+use tracing::{error, info};
+
+pub const MAX_RECORDED_RUNS: usize = 100;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct LibrarySchedule {
+    pub library_id: i64,
+    pub rescan: Option<String>,
+    pub refresh: Option<String>,
+    pub enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpcomingRun {
+    pub library_id: i64,
+    pub kind: String,
+    pub schedule: String,
+    pub next_run: Option<String>,
+    pub progress_token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ScheduledRun {
+    #[serde(skip)]
+    pub profile: String,
+    pub library_id: i64,
+    pub kind: String,
+    pub started_at: String,
+    pub finished_at: String,
+    pub success: bool,
+    pub message: String,
+}
+
+struct ScheduledJob {
+    kind: String,
+    schedule: String,
+    job: Job,
+}
+
+pub struct LibraryScheduler {
+    scheduler: JobScheduler,
+    jobs: HashMap<(String, i64), Vec<ScheduledJob>>,
+    recent_runs: Arc<std::sync::Mutex<VecDeque<ScheduledRun>>>,
+    base_path: String,
+    global_vars: Arc<Mutex<AppGlobalVariables>>,
+    creds: Arc<Mutex<ApiTokens>>,
+}
+
+pub fn is_valid_schedule(schedule: &str) -> bool {
+    Job::new_async(schedule, |_uuid, _l| Box::pin(async {})).is_ok()
+}
+
+pub fn scan_progress_token(library_id: i64) -> String {
+    format!("schedule-{}", library_id)
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|v| !v.trim().is_empty())
+}
+
+pub async fn get_library_schedules(
+    db_pool: &SqlitePool,
+) -> Result<Vec<LibrarySchedule>, sqlx::Error> {
+    let rows = sqlx::query(
+        "SELECT ID_LIBRARY, rescanSchedule, refreshSchedule, scheduleEnabled FROM Libraries;",
+    )
+    .fetch_all(db_pool)
+    .await?;
+    rows.iter()
+        .map(|row| {
+            Ok(LibrarySchedule {
+                library_id: row.try_get("ID_LIBRARY")?,
+                rescan: non_empty(row.try_get("rescanSchedule")?),
+                refresh: non_empty(row.try_get("refreshSchedule")?),
+                enabled: row.try_get("scheduleEnabled")?,
+            })
+        })
+        .collect()
+}
+
+fn record_run(runs: &std::sync::Mutex<VecDeque<ScheduledRun>>, run: ScheduledRun) {
+    let mut runs = runs.lock().unwrap();
+    runs.push_front(run);
+    runs.truncate(MAX_RECORDED_RUNS);
+}
+
+impl LibraryScheduler {
+    pub fn new(
+        scheduler: JobScheduler,
+        base_path: String,
+        global_vars: Arc<Mutex<AppGlobalVariables>>,
+        creds: Arc<Mutex<ApiTokens>>,
+    ) -> Self {
+        LibraryScheduler {
+            scheduler,
+            jobs: HashMap::new(),
+            recent_runs: Arc::new(std::sync::Mutex::new(VecDeque::new())),
+            base_path,
+            global_vars,
+            creds,
+        }
+    }
+
+    fn make_job(
+        &self,
+        profile: &str,
+        library_id: i64,
+        kind: &str,
+        schedule: &str,
+    ) -> Result<Job, JobSchedulerError> {
+        let profile = profile.to_string();
+        let kind = kind.to_string();
+        let base_path = self.base_path.clone();
+        let global_vars = self.global_vars.clone();
+        let creds = self.creds.clone();
+        let recent_runs = self.recent_runs.clone();
+        Job::new_async(schedule, move |_uuid, _l| {
+            let profile = profile.clone();
+            let kind = kind.clone();
+            let base_path = base_path.clone();
+            let global_vars = global_vars.clone();
+            let creds = creds.clone();
+            let recent_runs = recent_runs.clone();
+            Box::pin(async move {
+                info!("Running scheduled {} of library {}", kind, library_id);
+                let started_at = Utc::now().to_rfc3339();
+                let opened_db = global_vars.lock().await.opened_db.clone();
+                let result = match get_db(&profile, &base_path, opened_db).await {
+                    Ok(pool) if kind == "rescan" => run_library_scan(
+                        pool,
+                        library_id,
+                        scan_progress_token(library_id),
+                        global_vars,
+                        format!("{}/public/FirstImagesOfAll", base_path),
+                    )
+                    .await
+                    .map(|report| {
+                        format!(
+                            "{} books added, {} updated, {} missing",
+                            report.books_added,
+                            report.books_updated + report.books_moved,
+                            report.books_missing
+                        )
+                    }),
+                    Ok(pool) => {
+                        let (marvel_priv_key, marvel_pub_key) = {
+                            let creds = creds.lock().await;
+                            (
+                                creds.marvel_private_key.clone(),
+                                creds.marvel_public_key.clone(),
+                            )
+                        };
+                        refresh_library_metadata(&pool, library_id, marvel_priv_key, marvel_pub_key)
+                            .await
+                            .map(|(refreshed, failed)| {
+                                format!("{} items refreshed, {} failed", refreshed, failed)
+                            })
+                    }
+                    Err(e) => Err(e.into()),
+                };
+                let (success, message) = match result {
+                    Ok(message) => (true, message),
+                    Err(e) => {
+                        error!("Scheduled {} of library {} failed: {}", kind, library_id, e);
+                        (false, e.to_string())
+                    }
+                };
+                record_run(
+                    &recent_runs,
+                    ScheduledRun {
+                        profile,
+                        library_id,
+                        kind,
+                        started_at,
+                        finished_at: Utc::now().to_rfc3339(),
+                        success,
+                        message,
+                    },
+                );
+            })
+        })
+    }
+
+    pub async fn unschedule_library(
+        &mut self,
+        profile: &str,
+        library_id: i64,
+    ) -> Result<(), JobSchedulerError> {
+        if let Some(jobs) = self.jobs.remove(&(profile.to_string(), library_id)) {
+            for scheduled in jobs {
+                self.scheduler.remove(&scheduled.job.guid()).await?;
+            }
+        }
+        Ok(())
+    }
+
+    pub async fn schedule_library(
+        &mut self,
+        profile: &str,
+        schedule: &LibrarySchedule,
+    ) -> Result<(), JobSchedulerError> {
+        self.unschedule_library(profile, schedule.library_id)
+            .await?;
+        if !schedule.enabled {
+            return Ok(());
+        }
+        let mut jobs = Vec::new();
+        for (kind, cron) in [("rescan", &schedule.rescan), ("refresh", &schedule.refresh)] {
+            let Some(cron) = cron else {
+                continue;
+            };
+            let job = self.make_job(profile, schedule.library_id, kind, cron)?;
+            self.scheduler.add(job.clone()).await?;
+            jobs.push(ScheduledJob {
+                kind: kind.to_string(),
+                schedule: cron.clone(),
+                job,
+            });
+        }
+        info!(
+            "Scheduled {} jobs for library {} of {}",
+            jobs.len(),
+            schedule.library_id,
+            profile
+        );
+        self.jobs
+            .insert((profile.to_string(), schedule.library_id), jobs);
+        Ok(())
+    }
+
+    pub async fn schedule_profile(
+        &mut self,
+        profile: &str,
+        db_pool: &SqlitePool,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        for schedule in get_library_schedules(db_pool).await? {
+            self.schedule_library(profile, &schedule).await?;
+        }
+        Ok(())
+    }
+
+    pub async fn schedule_all_profiles(&mut self) {
+        let profiles_dir = format!("{}/profiles", self.base_path);
+        let Ok(entries) = fs::read_dir(&profiles_dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let profile = entry.file_name().to_string_lossy().to_string();
+            let db_path = entry.path().join("CosmicComics.db");
+            if !Path::new(&db_path).exists() {
+                continue;
+            }
+            let opened_db = self.global_vars.lock().await.opened_db.clone();
+            let result = match get_db(&profile, &self.base_path, opened_db).await {
+                Ok(pool) => self.schedule_profile(&profile, &pool).await,
+                Err(e) => Err(e.into()),
+            };
+            if let Err(e) = result {
+                error!("Failed to schedule libraries of {}: {}", profile, e);
+            }
+        }
+    }
+
+    pub async fn get_upcoming_runs(&self, profile: &str) -> Vec<UpcomingRun> {
+        let mut upcoming = Vec::new();
+        for ((owner, library_id), jobs) in &self.jobs {
+            if owner != profile {
+                continue;
+            }
+            for scheduled in jobs {
+                let next_run = self
+                    .scheduler
+                    .clone()
+                    .next_tick_for_job(scheduled.job.guid())
+                    .await
+                    .ok()
+                    .flatten()
+                    .map(|tick| tick.to_rfc3339());
+                upcoming.push(UpcomingRun {
+                    library_id: *library_id,
+                    kind: scheduled.kind.clone(),
+                    schedule: scheduled.schedule.clone(),
+                    next_run,
+                    progress_token: (scheduled.kind == "rescan")
+                        .then(|| scan_progress_token(*library_id)),
+                });
+            }
+        }
+        upcoming.sort_by(|a, b| {
+            (a.next_run.is_none(), &a.next_run).cmp(&(b.next_run.is_none(), &b.next_run))
+        });
+        upcoming
+    }
+
+    pub fn get_recent_runs(&self, profile: &str) -> Vec<ScheduledRun> {
+        self.recent_runs
+            .lock()
+            .unwrap()
+            .iter()
+            .filter(|run| run.profile == profile)
+            .cloned()
+            .collect()
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use crate::services::scheduler_service::*;
+    use crate::{ApiTokens, AppGlobalVariables};
+    use sqlx::sqlite::SqlitePoolOptions;
+    use std::sync::Arc;
+    use tokio::sync::Mutex;
+    use tokio_cron_scheduler::JobScheduler;
+
+    async fn make_scheduler() -> LibraryScheduler {
+        LibraryScheduler::new(
+            JobScheduler::new().await.unwrap(),
+            "/tmp".to_string(),
+            Arc::new(Mutex::new(AppGlobalVariables::default())),
+            Arc::new(Mutex::new(ApiTokens {
+                marvel_public_key: String::new(),
+                marvel_private_key: String::new(),
+                google_books_api_key: String::new(),
+                open_library_api_key: String::new(),
+            })),
+        )
+    }
+
+    #[test]
+    fn test_is_valid_schedule() {
+        assert!(is_valid_schedule("0 0 3 * * *"));
+        assert!(is_valid_schedule("0 30 */6 * * Sun"));
+        assert!(!is_valid_schedule("every night"));
+    }
+
+    #[tokio::test]
+    async fn test_get_library_schedules_ignores_empty_schedules() {
+        let db = SqlitePoolOptions::new()
+            .max_connections(1)
+            .connect("sqlite::memory:")
+            .await
+            .unwrap();
+        sqlx::query(
+            "CREATE TABLE Libraries (ID_LIBRARY INTEGER, rescanSchedule TEXT, refreshSchedule TEXT, scheduleEnabled BOOLEAN DEFAULT false NOT NULL);",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query("INSERT INTO Libraries VALUES (1, '0 0 3 * * *', '', 1), (2, NULL, NULL, 0);")
+            .execute(&db)
+            .await
+            .unwrap();
+
+        let schedules = get_library_schedules(&db).await.unwrap();
+
+        assert_eq!(
+            schedules,
+            vec![
+                LibrarySchedule {
+                    library_id: 1,
+                    rescan: Some("0 0 3 * * *".to_string()),
+                    refresh: None,
+                    enabled: true,
+                },
+                LibrarySchedule {
+                    library_id: 2,
+                    rescan: None,
+                    refresh: None,
+                    enabled: false,
+                },
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_schedule_library_registers_and_replaces_jobs() {
+        let mut scheduler = make_scheduler().await;
+        let mut schedule = LibrarySchedule {
+            library_id: 1,
+            rescan: Some("0 0 3 * * *".to_string()),
+            refresh: Some("0 0 4 * * Sun".to_string()),
+            enabled: true,
+        };
+
+        scheduler
+            .schedule_library("alice", &schedule)
+            .await
+            .unwrap();
+        let upcoming = scheduler.get_upcoming_runs("alice").await;
+        assert_eq!(upcoming.len(), 2);
+        assert!(
+            upcoming
+                .iter()
+                .any(|run| run.kind == "rescan"
+                    && run.progress_token.as_deref() == Some("schedule-1"))
+        );
+        assert!(
+            upcoming
+                .iter()
+                .any(|run| run.kind == "refresh" && run.progress_token.is_none())
+        );
+        assert!(upcoming.iter().all(|run| run.next_run.is_some()));
+        assert!(scheduler.get_upcoming_runs("bob").await.is_empty());
+
+        schedule.refresh = None;
+        scheduler
+            .schedule_library("alice", &schedule)
+            .await
+            .unwrap();
+        assert_eq!(scheduler.get_upcoming_runs("alice").await.len(), 1);
+
+        schedule.enabled = false;
+        scheduler
+            .schedule_library("alice", &schedule)
+            .await
+            .unwrap();
+        assert!(scheduler.get_upcoming_runs("alice").await.is_empty());
+        assert!(scheduler.get_recent_runs("alice").is_empty());
+    }
+}
@@ -0,0 +1,128 @@
+use crate::repositories::database_repo::{SEARCH_INDEXES, SearchIndex};
+use serde::{Deserialize, Serialize};
+use sqlx::{Row, SqlitePool};
+use std::collections::BTreeMap;
+
+pub const DEFAULT_SEARCH_LIMIT: u32 = 20;
+pub const MAX_SEARCH_LIMIT: u32 = 100;
+const TITLE_WEIGHT: f64 = 10.0;
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SearchQuery {
+    #[serde(default)]
+    pub q: String,
+    pub types: Option<String>,
+    pub limit: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchHit {
+    pub id: String,
+    pub title: Option<String>,
+    pub snippet: Option<String>,
+    pub score: f64,
+    pub cover: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct SearchResults {
+    pub query: String,
+    pub total: usize,
+    pub results: BTreeMap<&'static str, Vec<SearchHit>>,
+}
+
+pub fn build_match_query(input: &str) -> Option<String> {
+    let terms: Vec<String> = input
+        .split_whitespace()
+        .map(|term| term.replace('"', ""))
+        .filter(|term| !term.is_empty())
+        .map(|term| format!("\"{}\"*", term))
+        .collect();
+    (!terms.is_empty()).then(|| terms.join(" "))
+}
+
+fn get_indexes(types: Option<&str>) -> Result<Vec<&'static SearchIndex>, String> {
+    let Some(types) = types else {
+        return Ok(SEARCH_INDEXES.iter().collect());
+    };
+    types
+        .split(',')
+        .map(str::trim)
+        .filter(|entity| !entity.is_empty())
+        .map(|entity| {
+            SEARCH_INDEXES
+                .iter()
+                .find(|index| index.entity == entity)
+                .ok_or_else(|| format!("Unknown search type: {}", entity))
+        })
+        .collect()
+}
+
+async fn search_index(
+    db_pool: &SqlitePool,
+    index: &SearchIndex,
+    match_query: &str,
+    limit: u32,
+) -> Result<Vec<SearchHit>, sqlx::Error> {
+    let weights: Vec<String> = std::iter::once("0.0".to_string())
+        .chain(index.column_names().iter().map(|column| {
+            if *column == "title" {
+                TITLE_WEIGHT.to_string()
+            } else {
+                "1.0".to_string()
+            }
+        }))
+        .collect();
+    let sql = format!(
+        "SELECT {fts}.{key} AS id, highlight({fts}, 1, '<mark>', '</mark>') AS title, \
+         snippet({fts}, -1, '<mark>', '</mark>', '…', 16) AS snippet, \
+         -bm25({fts}, {weights}) AS score, {table}.{image} AS cover \
+         FROM {fts} JOIN {table} ON {table}.rowid = {fts}.rowid AND {table}.{key} = {fts}.{key} \
+         WHERE {fts} MATCH ? ORDER BY score DESC LIMIT ?;",
+        fts = index.fts,
+        key = index.key,
+        table = index.table,
+        image = index.image,
+        weights = weights.join(", ")
+    );
+    sqlx::query(&sql)
+        .bind(match_query)
+        .bind(limit as i64)
+        .fetch_all(db_pool)
+        .await?
+        .iter()
+        .map(|row| {
+            Ok(SearchHit {
+                id: row.try_get("id")?,
+                title: row.try_get("title")?,
+                snippet: row.try_get("snippet")?,
+                score: row.try_get("score")?,
+                cover: row.try_get("cover")?,
+            })
+        })
+        .collect()
+}
+
+pub async fn search(
+    db_pool: &SqlitePool,
+    query: &SearchQuery,
+) -> Result<SearchResults, Box<dyn std::error::Error + Send + Sync>> {
+    let match_query = build_match_query(&query.q).ok_or("Search query cannot be empty")?;
+    let indexes = get_indexes(query.types.as_deref())?;
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_SEARCH_LIMIT)
+        .clamp(1, MAX_SEARCH_LIMIT);
+    let mut results = BTreeMap::new();
+    for index in indexes {
+        results.insert(
+            index.entity,
+            search_index(db_pool, index, &match_query, limit).await?,
+        );
+    }
+    Ok(SearchResults {
+        query: query.q.clone(),
+        total: results.values().map(Vec::len).sum(),
+        results,
+    })
+}
@@ -0,0 +1,120 @@
+#[cfg(test)]
+mod tests {
+    use crate::repositories::database_repo::{make_test_db, rebuild_search_index};
+    use crate::services::collection_service::ItemType;
+    use crate::services::search_service::*;
+    use crate::services::tag_service::set_item_tags;
+    use sqlx::SqlitePool;
+    use tempfile::TempDir;
+
+    async fn setup_db() -> (TempDir, SqlitePool) {
+        let (temp, pool) = make_test_db().await;
+        sqlx::query(
+            r#"INSERT INTO Series (ID_Series, title, description, favorite, PATH) VALUES
+               ('s1', '{"english":"Attack on Titan","romaji":"Shingeki no Kyojin","native":"進撃の巨人"}', 'Humanity behind walls', false, '/manga/aot'),
+               ('s2', 'Civil War', 'Heroes clash', false, '/comics/civil-war');"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            r#"INSERT INTO Books (ID_book, NOM, description, creators, read, reading, unread, favorite, last_page, folder, PATH) VALUES
+               ('b1', 'Civil War #1', 'The Superhuman Registration Act divides the heroes.',
+                '{"available":1,"items":[{"name":"Mark Millar","role":"writer"}]}', false, false, true, false, 0, false, '/comics/civil-war/01.cbz'),
+               ('b2', 'Amazing Spider-Man #532', 'Peter unmasks during the civil war.',
+                '[]', false, false, true, false, 0, false, '/comics/asm/532.cbz');"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO Creators (ID_CREATOR, name, description) VALUES ('c1', 'Mark Millar', 'Scottish writer');",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        (temp, pool)
+    }
+
+    fn query(q: &str) -> SearchQuery {
+        SearchQuery {
+            q: q.to_string(),
+            ..Default::default()
+        }
+    }
+
+    fn ids(results: &SearchResults, entity: &str) -> Vec<String> {
+        results.results[entity]
+            .iter()
+            .map(|hit| hit.id.clone())
+            .collect()
+    }
+
+    #[test]
+    fn test_build_match_query_quotes_terms() {
+        assert_eq!(
+            build_match_query(r#"spider "man" OR"#).as_deref(),
+            Some(r#""spider"* "man"* "OR"*"#)
+        );
+        assert_eq!(build_match_query(r#"  "" "#), None);
+    }
+
+    #[tokio::test]
+    async fn test_search_ranks_and_groups_hits() {
+        let (_temp, pool) = setup_db().await;
+
+        let results = search(&pool, &query("civil war")).await.unwrap();
+
+        assert_eq!(ids(&results, "books"), vec!["b1", "b2"]);
+        assert_eq!(ids(&results, "series"), vec!["s2"]);
+        assert!(results.results["creators"].is_empty());
+        assert_eq!(results.total, 3);
+        assert_eq!(
+            results.results["books"][0].title.as_deref(),
+            Some("<mark>Civil</mark> <mark>War</mark> #1")
+        );
+
+        let results = search(&pool, &query("millar")).await.unwrap();
+        assert_eq!(ids(&results, "books"), vec!["b1"]);
+        assert_eq!(ids(&results, "creators"), vec!["c1"]);
+
+        let results = search(&pool, &query("kyojin")).await.unwrap();
+        assert_eq!(ids(&results, "series"), vec!["s1"]);
+
+        let results = SearchQuery {
+            types: Some("books,comics".to_string()),
+            ..query("war")
+        };
+        assert!(search(&pool, &results).await.is_err());
+        assert!(search(&pool, &query("  ")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_index_follows_updates_and_tags() {
+        let (_temp, pool) = setup_db().await;
+        sqlx::query("UPDATE Books SET NOM = 'Frontline #1' WHERE ID_book = 'b1';")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query("DELETE FROM Books WHERE ID_book = 'b2';")
+            .execute(&pool)
+            .await
+            .unwrap();
+        set_item_tags(&pool, ItemType::Series, "s1", &["Favourites".to_string()])
+            .await
+            .unwrap();
+
+        let results = search(&pool, &query("frontline")).await.unwrap();
+        assert_eq!(ids(&results, "books"), vec!["b1"]);
+        let results = search(&pool, &query("spider")).await.unwrap();
+        assert_eq!(results.total, 0);
+        let results = search(&pool, &query("favourite")).await.unwrap();
+        assert_eq!(ids(&results, "series"), vec!["s1"]);
+
+        rebuild_search_index(&pool).await.unwrap();
+        let results = search(&pool, &query("favourites frontline")).await.unwrap();
+        assert_eq!(results.total, 0);
+        let results = search(&pool, &query("favourites")).await.unwrap();
+        assert_eq!(ids(&results, "series"), vec!["s1"]);
+    }
+}
@@ -0,0 +1,676 @@
+use crate::models::book_model::Book;
+use crate::models::series_model::Series;
+use crate::services::pagination_service::{DEFAULT_PAGE_SIZE, MAX_PAGE_SIZE, sort_order};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{FromRow, QueryBuilder, Sqlite, SqlitePool};
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SmartTarget {
+    Books,
+    Series,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MatchMode {
+    #[default]
+    All,
+    Any,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FilterOp {
+    Eq,
+    Ne,
+    Gt,
+    Gte,
+    Lt,
+    Lte,
+    Contains,
+    StartsWith,
+    In,
+    IsNull,
+    NotNull,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FilterCondition {
+    pub field: String,
+    pub op: FilterOp,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    pub value: Value,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SmartRules {
+    pub target: SmartTarget,
+    #[serde(default, rename = "match")]
+    pub match_mode: MatchMode,
+    #[serde(default)]
+    pub conditions: Vec<FilterCondition>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct SmartQuery {
+    pub page: Option<u32>,
+    pub limit: Option<u32>,
+    pub sort: Option<String>,
+    pub order: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(untagged)]
+pub enum SmartItem {
+    Book(Box<Book>),
+    Series(Box<Series>),
+}
+
+#[cfg(test)]
+impl SmartItem {
+    pub fn id(&self) -> &str {
+        match self {
+            SmartItem::Book(book) => &book.id,
+            SmartItem::Series(series) => &series.id,
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SmartPage {
+    pub total: i64,
+    pub page: u32,
+    pub limit: u32,
+    pub items: Vec<SmartItem>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum FieldKind {
+    Text,
+    Number,
+    Bool,
+}
+
+struct FieldDef {
+    name: &'static str,
+    expr: &'static str,
+    kind: FieldKind,
+}
+
+const BOOK_FIELDS: &[FieldDef] = &[
+    FieldDef {
+        name: "name",
+        expr: "Books.NOM",
+        kind: FieldKind::Text,
+    },
+    FieldDef {
+        name: "path",
+        expr: "Books.PATH",
+        kind: FieldKind::Text,
+    },
+    FieldDef {
+        name: "format",
+        expr: "Books.format",
+        kind: FieldKind::Text,
+    },
+    FieldDef {
+        name: "provider",
+        expr: "(SELECT API.NOM FROM API WHERE API.ID_API = Books.API_ID)",
+        kind: FieldKind::Text,
+    },
+    FieldDef {
+        name: "series",
+        expr: "(SELECT Series.title FROM Series WHERE Series.ID_Series = Books.ID_Series)",
+        kind: FieldKind::Text,
+    },
+    FieldDef {
+        name: "rating",
+        expr: "Books.note",
+        kind: FieldKind::Number,
+    },
+    FieldDef {
+        name: "issue",
+        expr: "Books.issueNumber",
+        kind: FieldKind::Number,
+    },
+    FieldDef {
+        name: "pages",
+        expr: "Books.pageCount",
+        kind: FieldKind::Number,
+    },
+    FieldDef {
+        name: "last_page",
+        expr: "Books.last_page",
+        kind: FieldKind::Number,
+    },
+    FieldDef {
+        name: "size",
+        expr: "Books.fileSize",
+        kind: FieldKind::Number,
+    },
+    FieldDef {
+        name: "year",
+        expr: "(SELECT CAST(CASE WHEN d GLOB '[0-9][0-9][0-9][0-9]*' THEN substr(d, 1, 4) ELSE substr(d, -4) END AS INTEGER) \
+               FROM (SELECT CASE WHEN json_valid(Books.dates) AND json_type(Books.dates) = 'array' \
+               THEN json_extract(Books.dates, '$[0].date') ELSE Books.dates END AS d) WHERE d IS NOT NULL AND d != '')",
+        kind: FieldKind::Number,
+    },
+    FieldDef {
+        name: "read",
+        expr: "Books.read",
+        kind: FieldKind::Bool,
+    },
+    FieldDef {
+        name: "reading",
+        expr: "Books.reading",
+        kind: FieldKind::Bool,
+    },
+    FieldDef {
+        name: "unread",
+        expr: "Books.unread",
+        kind: FieldKind::Bool,
+    },
+    FieldDef {
+        name: "favorite",
+        expr: "Books.favorite",
+        kind: FieldKind::Bool,
+    },
+    FieldDef {
+        name: "missing",
+        expr: "Books.missing",
+        kind: FieldKind::Bool,
+    },
+];
+
+const SERIES_FIELDS: &[FieldDef] = &[
+    FieldDef {
+        name: "name",
+        expr: "Series.title",
+        kind: FieldKind::Text,
+    },
+    FieldDef {
+        name: "path",
+        expr: "Series.PATH",
+        kind: FieldKind::Text,
+    },
+    FieldDef {
+        name: "status",
+        expr: "Series.statut",
+        kind: FieldKind::Text,
+    },
+    FieldDef {
+        name: "genres",
+        expr: "Series.genres",
+        kind: FieldKind::Text,
+    },
+    FieldDef {
+        name: "source",
+        expr: "Series.SOURCE",
+        kind: FieldKind::Text,
+    },
+    FieldDef {
+        name: "rating",
+        expr: "Series.note",
+        kind: FieldKind::Number,
+    },
+    FieldDef {
+        name: "score",
+        expr: "Series.Score",
+        kind: FieldKind::Number,
+    },
+    FieldDef {
+        name: "volumes",
+        expr: "Series.volumes",
+        kind: FieldKind::Number,
+    },
+    FieldDef {
+        name: "chapters",
+        expr: "Series.chapters",
+        kind: FieldKind::Number,
+    },
+    FieldDef {
+        name: "books",
+        expr: "(SELECT COUNT(*) FROM Books WHERE Books.ID_Series = Series.ID_Series)",
+        kind: FieldKind::Number,
+    },
+    FieldDef {
+        name: "unread_books",
+        expr: "(SELECT COUNT(*) FROM Books WHERE Books.ID_Series = Series.ID_Series \
+               AND Books.read NOT IN (1, 'true'))",
+        kind: FieldKind::Number,
+    },
+    FieldDef {
+        name: "missing_issues",
+        expr: "(SELECT COALESCE(MAX(Books.issueNumber) - COUNT(DISTINCT Books.issueNumber), 0) \
+               FROM Books WHERE Books.ID_Series = Series.ID_Series AND Books.issueNumber > 0)",
+        kind: FieldKind::Number,
+    },
+    FieldDef {
+        name: "year",
+        expr: "(CASE WHEN json_valid(Series.start_date) AND json_type(Series.start_date) = 'object' \
+               THEN CAST(json_extract(Series.start_date, '$.year') AS INTEGER) \
+               WHEN json_valid(Series.start_date) AND json_type(Series.start_date) IN ('integer', 'real') \
+               THEN CAST(Series.start_date AS INTEGER) END)",
+        kind: FieldKind::Number,
+    },
+    FieldDef {
+        name: "favorite",
+        expr: "Series.favorite",
+        kind: FieldKind::Bool,
+    },
+];
+
+const CUSTOM_FIELD_PREFIX: &str = "custom.";
+
+enum Field {
+    Column(&'static FieldDef),
+    Tags,
+    Custom(String),
+}
+
+impl SmartTarget {
+    pub(crate) fn table(&self) -> &'static str {
+        match self {
+            SmartTarget::Books => "Books",
+            SmartTarget::Series => "Series",
+        }
+    }
+
+    pub(crate) fn key(&self) -> &'static str {
+        match self {
+            SmartTarget::Books => "Books.ID_book",
+            SmartTarget::Series => "Series.ID_Series",
+        }
+    }
+
+    fn item_type(&self) -> &'static str {
+        match self {
+            SmartTarget::Books => "book",
+            SmartTarget::Series => "series",
+        }
+    }
+
+    fn fields(&self) -> &'static [FieldDef] {
+        match self {
+            SmartTarget::Books => BOOK_FIELDS,
+            SmartTarget::Series => SERIES_FIELDS,
+        }
+    }
+
+    fn field(&self, name: &str) -> Option<Field> {
+        if name == "tags" {
+            return Some(Field::Tags);
+        }
+        if let Some(custom) = name.strip_prefix(CUSTOM_FIELD_PREFIX) {
+            return (!custom.is_empty()).then(|| Field::Custom(custom.to_string()));
+        }
+        self.fields()
+            .iter()
+            .find(|field| field.name == name)
+            .map(Field::Column)
+    }
+}
+
+pub(crate) fn field_expr(target: SmartTarget, name: &str) -> Option<&'static str> {
+    target
+        .fields()
+        .iter()
+        .find(|field| field.name == name)
+        .map(|field| field.expr)
+}
+
+fn is_valid_value(kind: FieldKind, value: &Value) -> bool {
+    match kind {
+        FieldKind::Text => value.is_string(),
+        FieldKind::Number => value.is_number(),
+        FieldKind::Bool => value.is_boolean(),
+    }
+}
+
+fn value_kind(value: &Value) -> FieldKind {
+    let value = value
+        .as_array()
+        .and_then(|values| values.first())
+        .unwrap_or(value);
+    if value.is_number() {
+        FieldKind::Number
+    } else {
+        FieldKind::Text
+    }
+}
+
+fn field_kind(field: &Field, condition: &FilterCondition) -> FieldKind {
+    match field {
+        Field::Column(def) => def.kind,
+        Field::Tags => FieldKind::Text,
+        Field::Custom(_) => value_kind(&condition.value),
+    }
+}
+
+pub fn validate_rules(rules: &SmartRules) -> Result<(), String> {
+    for condition in &rules.conditions {
+        let field = rules
+            .target
+            .field(&condition.field)
+            .ok_or_else(|| format!("Unknown field: {}", condition.field))?;
+        let kind = field_kind(&field, condition);
+        let valid = match condition.op {
+            FilterOp::IsNull | FilterOp::NotNull => true,
+            _ if matches!(field, Field::Tags)
+                && !matches!(condition.op, FilterOp::Eq | FilterOp::Ne | FilterOp::In) =>
+            {
+                false
+            }
+            FilterOp::Contains | FilterOp::StartsWith => {
+                kind == FieldKind::Text && condition.value.is_string()
+            }
+            FilterOp::Gt | FilterOp::Gte | FilterOp::Lt | FilterOp::Lte => {
+                kind != FieldKind::Bool && is_valid_value(kind, &condition.value)
+            }
+            FilterOp::Eq | FilterOp::Ne => is_valid_value(kind, &condition.value),
+            FilterOp::In => condition.value.as_array().is_some_and(|values| {
+                !values.is_empty() && values.iter().all(|v| is_valid_value(kind, v))
+            }),
+        };
+        if !valid {
+            return Err(format!(
+                "Invalid value or operator for field: {}",
+                condition.field
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn get_sort(rules: &SmartRules, query: &SmartQuery) -> Result<(Field, &'static str), String> {
+    let field = match query.sort.as_deref() {
+        None => Field::Column(&rules.target.fields()[0]),
+        Some(sort) => match rules.target.field(sort) {
+            Some(Field::Tags) | None => return Err(format!("Unknown sort field: {}", sort)),
+            Some(field) => field,
+        },
+    };
+    Ok((field, sort_order(query.order.as_deref())?))
+}
+
+fn push_value(qb: &mut QueryBuilder<'_, Sqlite>, kind: FieldKind, value: &Value) {
+    match kind {
+        FieldKind::Text => qb.push_bind(value.as_str().unwrap_or_default().to_string()),
+        FieldKind::Number => qb.push_bind(value.as_f64().unwrap_or_default()),
+        FieldKind::Bool => qb.push_bind(value.as_bool().unwrap_or_default() as i64),
+    };
+}
+
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+fn push_expr(qb: &mut QueryBuilder<'_, Sqlite>, target: SmartTarget, field: &Field) {
+    match field {
+        Field::Column(def) if def.kind == FieldKind::Bool => {
+            qb.push(format!(
+                "(CASE WHEN {} IN (1, 'true') THEN 1 ELSE 0 END)",
+                def.expr
+            ));
+        }
+        Field::Column(def) => {
+            qb.push(def.expr);
+        }
+        Field::Custom(name) => {
+            qb.push(format!(
+                "(SELECT CustomFieldValues.value FROM CustomFieldValues \
+                 JOIN CustomFields ON CustomFields.ID_FIELD = CustomFieldValues.ID_FIELD \
+                 WHERE CustomFields.target = '{}' AND CustomFieldValues.ID_item = {} AND CustomFields.NAME = ",
+                target.item_type(),
+                target.key()
+            ))
+            .push_bind(name.clone())
+            .push(")");
+        }
+        Field::Tags => {
+            qb.push(format!(
+                "(SELECT Tags.NAME FROM TagLinks JOIN Tags ON Tags.ID_TAG = TagLinks.ID_TAG \
+                 WHERE TagLinks.itemType = '{}' AND TagLinks.ID_item = {}",
+                target.item_type(),
+                target.key()
+            ));
+        }
+    }
+}
+
+fn push_tag_condition(
+    qb: &mut QueryBuilder<'_, Sqlite>,
+    target: SmartTarget,
+    condition: &FilterCondition,
+) {
+    if matches!(condition.op, FilterOp::Ne | FilterOp::IsNull) {
+        qb.push("NOT ");
+    }
+    qb.push("EXISTS ");
+    push_expr(qb, target, &Field::Tags);
+    match condition.op {
+        FilterOp::Eq | FilterOp::Ne => {
+            qb.push(" AND Tags.NAME = ");
+            push_value(qb, FieldKind::Text, &condition.value);
+        }
+        FilterOp::In => {
+            qb.push(" AND Tags.NAME IN (");
+            let mut separated = qb.separated(", ");
+            for value in condition.value.as_array().into_iter().flatten() {
+                separated.push_bind(value.as_str().unwrap_or_default().to_string());
+            }
+            qb.push(")");
+        }
+        _ => {}
+    }
+    qb.push(")");
+}
+
+fn push_condition(
+    qb: &mut QueryBuilder<'_, Sqlite>,
+    target: SmartTarget,
+    field: &Field,
+    condition: &FilterCondition,
+) {
+    if matches!(field, Field::Tags) {
+        push_tag_condition(qb, target, condition);
+        return;
+    }
+    let kind = field_kind(field, condition);
+    let collate = if kind == FieldKind::Text {
+        " COLLATE NOCASE"
+    } else {
+        ""
+    };
+    push_expr(qb, target, field);
+    let comparison = match condition.op {
+        FilterOp::Eq => "=",
+        FilterOp::Ne => "!=",
+        FilterOp::Gt => ">",
+        FilterOp::Gte => ">=",
+        FilterOp::Lt => "<",
+        FilterOp::Lte => "<=",
+        FilterOp::IsNull => {
+            qb.push(" IS NULL");
+            return;
+        }
+        FilterOp::NotNull => {
+            qb.push(" IS NOT NULL");
+            return;
+        }
+        FilterOp::Contains | FilterOp::StartsWith => {
+            let value = escape_like(condition.value.as_str().unwrap_or_default());
+            let pattern = if condition.op == FilterOp::Contains {
+                format!("%{}%", value)
+            } else {
+                format!("{}%", value)
+            };
+            qb.push(" LIKE ").push_bind(pattern).push(" ESCAPE '\\'");
+            return;
+        }
+        FilterOp::In => {
+            qb.push(format!("{} IN (", collate));
+            let mut separated = qb.separated(", ");
+            for value in condition.value.as_array().into_iter().flatten() {
+                match kind {
+                    FieldKind::Text => {
+                        separated.push_bind(value.as_str().unwrap_or_default().to_string())
+                    }
+                    FieldKind::Number => separated.push_bind(value.as_f64().unwrap_or_default()),
+                    FieldKind::Bool => {
+                        separated.push_bind(value.as_bool().unwrap_or_default() as i64)
+                    }
+                };
+            }
+            qb.push(")");
+            return;
+        }
+    };
+    qb.push(format!("{} {} ", collate, comparison));
+    push_value(qb, kind, &condition.value);
+}
+
+pub(crate) fn push_rules_filter(qb: &mut QueryBuilder<'_, Sqlite>, rules: &SmartRules) {
+    if rules.conditions.is_empty() {
+        qb.push("1");
+        return;
+    }
+    let joiner = match rules.match_mode {
+        MatchMode::All => " AND ",
+        MatchMode::Any => " OR ",
+    };
+    qb.push("(");
+    for (i, condition) in rules.conditions.iter().enumerate() {
+        if i > 0 {
+            qb.push(joiner);
+        }
+        if let Some(field) = rules.target.field(&condition.field) {
+            qb.push("(");
+            push_condition(qb, rules.target, &field, condition);
+            qb.push(")");
+        }
+    }
+    qb.push(")");
+}
+
+fn push_where(qb: &mut QueryBuilder<'_, Sqlite>, rules: &SmartRules) {
+    if rules.conditions.is_empty() {
+        return;
+    }
+    qb.push(" WHERE ");
+    push_rules_filter(qb, rules);
+}
+
+pub(crate) fn row_to_smart_item(
+    target: SmartTarget,
+    row: &SqliteRow,
+) -> Result<SmartItem, sqlx::Error> {
+    Ok(match target {
+        SmartTarget::Books => SmartItem::Book(Box::new(Book::from_row(row)?)),
+        SmartTarget::Series => SmartItem::Series(Box::new(Series::from_row(row)?)),
+    })
+}
+
+pub(crate) fn push_page(
+    qb: &mut QueryBuilder<'_, Sqlite>,
+    rules: &SmartRules,
+    query: &SmartQuery,
+) -> Result<(u32, u32), String> {
+    let (sort, order) = get_sort(rules, query)?;
+    let page = query.page.unwrap_or(1).max(1);
+    let limit = query
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+    qb.push(" ORDER BY ");
+    push_expr(qb, rules.target, &sort);
+    qb.push(format!(" {}, {} LIMIT ", order, rules.target.key()))
+        .push_bind(limit as i64)
+        .push(" OFFSET ")
+        .push_bind(((page - 1) * limit) as i64);
+    Ok((page, limit))
+}
+
+pub async fn evaluate_smart_rules(
+    db_pool: &SqlitePool,
+    rules: &SmartRules,
+    query: &SmartQuery,
+) -> Result<SmartPage, Box<dyn std::error::Error + Send + Sync>> {
+    validate_rules(rules)?;
+    let table = rules.target.table();
+    let mut qb = QueryBuilder::<Sqlite>::new(format!("SELECT {}.* FROM {}", table, table));
+    push_where(&mut qb, rules);
+    let (page, limit) = push_page(&mut qb, rules, query)?;
+
+    let mut count_qb = QueryBuilder::<Sqlite>::new(format!("SELECT COUNT(*) FROM {}", table));
+    push_where(&mut count_qb, rules);
+    let (total,): (i64,) = count_qb.build_query_as().fetch_one(db_pool).await?;
+
+    let items = qb
+        .build()
+        .fetch_all(db_pool)
+        .await?
+        .iter()
+        .map(|row| row_to_smart_item(rules.target, row))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(SmartPage {
+        total,
+        page,
+        limit,
+        items,
+    })
+}
+
+pub async fn create_smart_collection(
+    db_pool: &SqlitePool,
+    name: &str,
+    description: Option<&str>,
+    rules: &SmartRules,
+) -> Result<i64, sqlx::Error> {
+    let result = sqlx::query(
+        "INSERT INTO Collections (NAME, description, position, rules) \
+         VALUES (?, ?, (SELECT COUNT(*) FROM Collections), ?);",
+    )
+    .bind(name)
+    .bind(description)
+    .bind(serde_json::to_string(rules).unwrap_or_default())
+    .execute(db_pool)
+    .await?;
+    Ok(result.last_insert_rowid())
+}
+
+pub async fn update_smart_rules(
+    db_pool: &SqlitePool,
+    collection_id: i64,
+    rules: &SmartRules,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query(
+        "UPDATE Collections SET rules = ? WHERE ID_COLLECTION = ? AND rules IS NOT NULL;",
+    )
+    .bind(serde_json::to_string(rules).unwrap_or_default())
+    .bind(collection_id)
+    .execute(db_pool)
+    .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_smart_rules(
+    db_pool: &SqlitePool,
+    collection_id: i64,
+) -> Result<Option<SmartRules>, sqlx::Error> {
+    let rules: Option<(Option<String>,)> =
+        sqlx::query_as("SELECT rules FROM Collections WHERE ID_COLLECTION = ?;")
+            .bind(collection_id)
+            .fetch_optional(db_pool)
+            .await?;
+    Ok(rules
+        .and_then(|(rules,)| rules)
+        .and_then(|rules| serde_json::from_str(&rules).ok()))
+}
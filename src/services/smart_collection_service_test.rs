@@ -0,0 +1,283 @@
+#[cfg(test)]
+mod tests {
+    use crate::repositories::database_repo::make_test_db;
+    use crate::services::collection_service::ItemType;
+    use crate::services::custom_field_service::{
+        FieldType, create_custom_field, set_item_field_values,
+    };
+    use crate::services::smart_collection_service::*;
+    use crate::services::tag_service::set_item_tags;
+    use serde_json::json;
+    use sqlx::SqlitePool;
+    use tempfile::TempDir;
+
+    async fn setup_db() -> (TempDir, SqlitePool) {
+        let (temp, pool) = make_test_db().await;
+        sqlx::query(
+            "INSERT INTO Series (ID_Series, title, favorite, PATH) VALUES \
+             ('s1', 'Saga', false, '/saga'), ('s2', 'Paper Girls', true, '/paper');",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        for (id, api, read, note, dates, series, issue) in [
+            (
+                "m1",
+                "1",
+                0,
+                5,
+                r#"[{"type":"onsaleDate","date":"2016-03-02T00:00:00-0500"}]"#,
+                "s1",
+                1,
+            ),
+            (
+                "m2",
+                "1",
+                1,
+                5,
+                r#"[{"type":"onsaleDate","date":"2018-01-10T00:00:00-0500"}]"#,
+                "s1",
+                2,
+            ),
+            (
+                "m3",
+                "1",
+                0,
+                3,
+                r#"[{"type":"onsaleDate","date":"2019-05-01T00:00:00-0500"}]"#,
+                "s1",
+                4,
+            ),
+            (
+                "m4",
+                "1",
+                0,
+                4,
+                r#"[{"type":"onsaleDate","date":"2012-05-01T00:00:00-0500"}]"#,
+                "s2",
+                1,
+            ),
+            ("g1", "4", 0, 5, "2020-04-01", "s2", 2),
+            ("o1", "3", 0, 4, "March 2, 2017", "s2", 3),
+        ] {
+            sqlx::query(
+                "INSERT INTO Books (ID_book, API_ID, NOM, note, read, reading, unread, favorite, last_page, folder, PATH, dates, ID_Series, issueNumber) \
+                 VALUES (?, ?, ?, ?, ?, false, true, false, 0, false, ?, ?, ?, ?);",
+            )
+            .bind(id)
+            .bind(api)
+            .bind(format!("Book {}", id))
+            .bind(note)
+            .bind(read)
+            .bind(format!("/books/{}.cbz", id))
+            .bind(dates)
+            .bind(series)
+            .bind(issue)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        (temp, pool)
+    }
+
+    fn rules(value: serde_json::Value) -> SmartRules {
+        serde_json::from_value(value).unwrap()
+    }
+
+    fn ids(page: &SmartPage) -> Vec<String> {
+        page.items
+            .iter()
+            .map(|item| item.id().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_validate_rules_rejects_unknown_fields_and_bad_values() {
+        assert!(
+            validate_rules(&rules(json!({
+                "target": "books",
+                "conditions": [{ "field": "rating", "op": "gte", "value": 4 }]
+            })))
+            .is_ok()
+        );
+        assert!(
+            validate_rules(&rules(json!({
+                "target": "books",
+                "conditions": [{ "field": "NOM; DROP TABLE Books", "op": "eq", "value": "x" }]
+            })))
+            .is_err()
+        );
+        assert!(
+            validate_rules(&rules(json!({
+                "target": "books",
+                "conditions": [{ "field": "read", "op": "gt", "value": true }]
+            })))
+            .is_err()
+        );
+        assert!(
+            validate_rules(&rules(json!({
+                "target": "series",
+                "conditions": [{ "field": "name", "op": "in", "value": [] }]
+            })))
+            .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_unread_marvel_books_after_2015_rated_4_plus() {
+        let (_temp, pool) = setup_db().await;
+        let rules = rules(json!({
+            "target": "books",
+            "conditions": [
+                { "field": "read", "op": "eq", "value": false },
+                { "field": "provider", "op": "eq", "value": "marvel" },
+                { "field": "year", "op": "gt", "value": 2015 },
+                { "field": "rating", "op": "gte", "value": 4 }
+            ]
+        }));
+
+        let page = evaluate_smart_rules(&pool, &rules, &SmartQuery::default())
+            .await
+            .unwrap();
+
+        assert_eq!(page.total, 1);
+        assert_eq!(ids(&page), vec!["m1"]);
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_paginates_and_sorts() {
+        let (_temp, pool) = setup_db().await;
+        let rules = rules(json!({
+            "target": "books",
+            "match": "any",
+            "conditions": [
+                { "field": "year", "op": "gte", "value": 2017 },
+                { "field": "name", "op": "contains", "value": "m1" }
+            ]
+        }));
+        let query = SmartQuery {
+            page: Some(2),
+            limit: Some(2),
+            sort: Some("year".to_string()),
+            order: Some("desc".to_string()),
+        };
+
+        let page = evaluate_smart_rules(&pool, &rules, &query).await.unwrap();
+
+        assert_eq!(page.total, 5);
+        assert_eq!(ids(&page), vec!["m2", "o1"]);
+
+        let query = SmartQuery {
+            sort: Some("Books.NOM; --".to_string()),
+            ..Default::default()
+        };
+        assert!(evaluate_smart_rules(&pool, &rules, &query).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_series_with_missing_issues() {
+        let (_temp, pool) = setup_db().await;
+        let rules = rules(json!({
+            "target": "series",
+            "conditions": [{ "field": "missing_issues", "op": "gt", "value": 0 }]
+        }));
+
+        let page = evaluate_smart_rules(&pool, &rules, &SmartQuery::default())
+            .await
+            .unwrap();
+
+        assert_eq!(ids(&page), vec!["s1"]);
+    }
+
+    #[tokio::test]
+    async fn test_smart_collection_rules_are_stored() {
+        let (_temp, pool) = setup_db().await;
+        let favorites = rules(json!({
+            "target": "series",
+            "conditions": [{ "field": "favorite", "op": "eq", "value": true }]
+        }));
+        let id = create_smart_collection(&pool, "Favourite series", None, &favorites)
+            .await
+            .unwrap();
+
+        assert_eq!(get_smart_rules(&pool, id).await.unwrap(), Some(favorites));
+
+        let unread = rules(json!({
+            "target": "series",
+            "conditions": [{ "field": "unread_books", "op": "gt", "value": 0 }]
+        }));
+        assert!(update_smart_rules(&pool, id, &unread).await.unwrap());
+        let stored = get_smart_rules(&pool, id).await.unwrap().unwrap();
+        let page = evaluate_smart_rules(&pool, &stored, &SmartQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(ids(&page), vec!["s2", "s1"]);
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_tags_and_custom_fields() {
+        let (_temp, pool) = setup_db().await;
+        create_custom_field(&pool, "Price", FieldType::Number, ItemType::Book, &[])
+            .await
+            .unwrap();
+        for (id, tags, price) in [
+            ("m1", vec!["Signed", "Variant"], 30.0),
+            ("m2", vec!["signed"], 4.5),
+            ("g1", vec!["Variant"], 12.0),
+        ] {
+            let tags: Vec<String> = tags.into_iter().map(String::from).collect();
+            set_item_tags(&pool, ItemType::Book, id, &tags)
+                .await
+                .unwrap();
+            let values = serde_json::from_value(json!({ "Price": price })).unwrap();
+            set_item_field_values(&pool, ItemType::Book, id, &values)
+                .await
+                .unwrap();
+        }
+
+        let signed = rules(json!({
+            "target": "books",
+            "conditions": [
+                { "field": "tags", "op": "eq", "value": "SIGNED" },
+                { "field": "custom.Price", "op": "gt", "value": 10 }
+            ]
+        }));
+        let page = evaluate_smart_rules(&pool, &signed, &SmartQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(ids(&page), vec!["m1"]);
+
+        let untagged = rules(json!({
+            "target": "books",
+            "conditions": [{ "field": "tags", "op": "is_null" }]
+        }));
+        let query = SmartQuery {
+            sort: Some("custom.Price".to_string()),
+            ..Default::default()
+        };
+        let page = evaluate_smart_rules(&pool, &untagged, &query)
+            .await
+            .unwrap();
+        assert_eq!(page.total, 3);
+
+        let query = SmartQuery {
+            sort: Some("custom.Price".to_string()),
+            order: Some("desc".to_string()),
+            ..Default::default()
+        };
+        let tagged = rules(json!({
+            "target": "books",
+            "conditions": [{ "field": "tags", "op": "in", "value": ["signed", "variant"] }]
+        }));
+        let page = evaluate_smart_rules(&pool, &tagged, &query).await.unwrap();
+        assert_eq!(ids(&page), vec!["m1", "g1", "m2"]);
+
+        assert!(
+            validate_rules(&rules(json!({
+                "target": "books",
+                "conditions": [{ "field": "tags", "op": "gt", "value": "a" }]
+            })))
+            .is_err()
+        );
+    }
+}
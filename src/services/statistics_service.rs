@@ -0,0 +1,302 @@
+use crate::services::facet_service::{BrowseRequest, FacetValue, count_facets};
+use crate::services::smart_collection_service::{
+    FilterCondition, FilterOp, MatchMode, SmartRules, SmartTarget,
+};
+use chrono::{Datelike, Duration, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::{Row, SqlitePool};
+use std::collections::{BTreeMap, HashMap};
+
+pub const TOP_LIMIT: usize = 10;
+pub const MAX_POINTS: usize = 1000;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsPeriod {
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StatsQuery {
+    pub period: Option<StatsPeriod>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    pub offset: Option<i64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct StatsPoint {
+    pub period: String,
+    pub pages: i64,
+    pub books: i64,
+    pub time_spent: i64,
+    pub sessions: i64,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct Streaks {
+    pub current: i64,
+    pub longest: i64,
+}
+
+#[derive(Debug, Default, PartialEq, Serialize)]
+pub struct ReadingSpeed {
+    pub pages_per_hour: Option<f64>,
+    pub seconds_per_page: Option<f64>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Completion {
+    pub value: String,
+    pub label: String,
+    pub total: i64,
+    pub read: i64,
+    pub rate: f64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ReadingStats {
+    pub period: StatsPeriod,
+    pub from: String,
+    pub to: String,
+    pub series: Vec<StatsPoint>,
+    pub totals: StatsPoint,
+    pub streaks: Streaks,
+    pub speed: ReadingSpeed,
+    pub top_creators: Vec<FacetValue>,
+    pub top_genres: Vec<FacetValue>,
+    pub top_publishers: Vec<FacetValue>,
+    pub libraries: Vec<Completion>,
+    pub formats: Vec<Completion>,
+}
+
+impl StatsPeriod {
+    fn start_of(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            StatsPeriod::Day => date,
+            StatsPeriod::Week => {
+                date - Duration::days(date.weekday().num_days_from_monday() as i64)
+            }
+            StatsPeriod::Month => date.with_day(1).unwrap_or(date),
+        }
+    }
+
+    fn next(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            StatsPeriod::Day => date + Duration::days(1),
+            StatsPeriod::Week => date + Duration::days(7),
+            StatsPeriod::Month => date + Months::new(1),
+        }
+    }
+
+    fn default_from(self, to: NaiveDate) -> NaiveDate {
+        match self {
+            StatsPeriod::Day => to - Duration::days(29),
+            StatsPeriod::Week => self.start_of(to) - Duration::days(7 * 11),
+            StatsPeriod::Month => self.start_of(to) - Months::new(11),
+        }
+    }
+
+    fn label(self, date: NaiveDate) -> String {
+        match self {
+            StatsPeriod::Month => date.format("%Y-%m").to_string(),
+            _ => date.format("%Y-%m-%d").to_string(),
+        }
+    }
+}
+
+fn parse_date(value: Option<&str>) -> Result<Option<NaiveDate>, String> {
+    value
+        .map(|date| {
+            NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .map_err(|_| format!("Invalid date: {}", date))
+        })
+        .transpose()
+}
+
+fn get_streaks(days: &[NaiveDate], today: NaiveDate) -> Streaks {
+    let mut streaks = Streaks::default();
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for day in days {
+        run = match previous {
+            Some(previous) if *day - previous == Duration::days(1) => run + 1,
+            _ => 1,
+        };
+        streaks.longest = streaks.longest.max(run);
+        previous = Some(*day);
+    }
+    if previous.is_some_and(|last| today - last <= Duration::days(1)) {
+        streaks.current = run;
+    }
+    streaks
+}
+
+fn books_request(read_only: bool) -> BrowseRequest {
+    let conditions = if read_only {
+        vec![FilterCondition {
+            field: "read".to_string(),
+            op: FilterOp::Eq,
+            value: Value::Bool(true),
+        }]
+    } else {
+        Vec::new()
+    };
+    BrowseRequest {
+        rules: SmartRules {
+            target: SmartTarget::Books,
+            match_mode: MatchMode::All,
+            conditions,
+        },
+        facets: HashMap::new(),
+    }
+}
+
+fn get_completion(total: &[FacetValue], read: &[FacetValue]) -> Vec<Completion> {
+    total
+        .iter()
+        .map(|facet| {
+            let read = read
+                .iter()
+                .find(|read| read.value == facet.value)
+                .map_or(0, |read| read.count);
+            Completion {
+                value: facet.value.clone(),
+                label: facet.label.clone(),
+                total: facet.count,
+                read,
+                rate: read as f64 / facet.count as f64,
+            }
+        })
+        .collect()
+}
+
+pub async fn get_reading_stats(
+    db_pool: &SqlitePool,
+    query: &StatsQuery,
+    now: i64,
+) -> Result<ReadingStats, Box<dyn std::error::Error + Send + Sync>> {
+    let period = query.period.unwrap_or_default();
+    let offset = query.offset.unwrap_or(0);
+    if !(-720..=840).contains(&offset) {
+        return Err(format!("Invalid offset: {}", offset).into());
+    }
+    let modifier = format!("{:+} minutes", offset);
+    let today = chrono::DateTime::from_timestamp(now + offset * 60, 0)
+        .ok_or("Invalid timestamp")?
+        .date_naive();
+    let to = parse_date(query.to.as_deref())?.unwrap_or(today);
+    let from = parse_date(query.from.as_deref())?.unwrap_or_else(|| period.default_from(to));
+    if from > to {
+        return Err("Invalid range: from is after to".into());
+    }
+
+    let mut series = BTreeMap::new();
+    let mut bucket = period.start_of(from);
+    while bucket <= to {
+        if series.len() == MAX_POINTS {
+            return Err(format!("Range is too large: more than {} points", MAX_POINTS).into());
+        }
+        series.insert(
+            bucket,
+            StatsPoint {
+                period: period.label(bucket),
+                ..Default::default()
+            },
+        );
+        bucket = period.next(bucket);
+    }
+
+    let rows = sqlx::query(
+        "SELECT day, SUM(pages) AS pages, SUM(books) AS books, SUM(time_spent) AS time_spent, \
+         SUM(sessions) AS sessions FROM ( \
+         SELECT date(viewed, 'unixepoch', ?1) AS day, 1 AS pages, 0 AS books, 0 AS time_spent, \
+         0 AS sessions FROM ReadingSessionPages \
+         UNION ALL SELECT date(started, 'unixepoch', ?1), 0, 0, ended - started, 1 FROM ReadingSessions \
+         UNION ALL SELECT date(ended, 'unixepoch', ?1), 0, 1, 0, 0 FROM ReadingSessions WHERE completed \
+         ) WHERE day BETWEEN ?2 AND ?3 GROUP BY day;",
+    )
+    .bind(&modifier)
+    .bind(from.to_string())
+    .bind(to.to_string())
+    .fetch_all(db_pool)
+    .await?;
+    let mut totals = StatsPoint {
+        period: "total".to_string(),
+        ..Default::default()
+    };
+    for row in rows {
+        let day: String = row.try_get("day")?;
+        let day = NaiveDate::parse_from_str(&day, "%Y-%m-%d")?;
+        let (pages, books, time_spent, sessions): (i64, i64, i64, i64) = (
+            row.try_get("pages")?,
+            row.try_get("books")?,
+            row.try_get("time_spent")?,
+            row.try_get("sessions")?,
+        );
+        for point in [series.get_mut(&period.start_of(day)), Some(&mut totals)]
+            .into_iter()
+            .flatten()
+        {
+            point.pages += pages;
+            point.books += books;
+            point.time_spent += time_spent;
+            point.sessions += sessions;
+        }
+    }
+
+    let days: Vec<String> = sqlx::query_scalar(
+        "SELECT DISTINCT date(viewed, 'unixepoch', ?) AS day FROM ReadingSessionPages ORDER BY day;",
+    )
+    .bind(&modifier)
+    .fetch_all(db_pool)
+    .await?;
+    let days = days
+        .iter()
+        .map(|day| NaiveDate::parse_from_str(day, "%Y-%m-%d"))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let (pages, seconds): (i64, i64) = sqlx::query_as(
+        "SELECT COALESCE(SUM(pages_read), 0), COALESCE(SUM(ended - started), 0) FROM ReadingSessions \
+         WHERE ended > started AND date(started, 'unixepoch', ?1) BETWEEN ?2 AND ?3;",
+    )
+    .bind(&modifier)
+    .bind(from.to_string())
+    .bind(to.to_string())
+    .fetch_one(db_pool)
+    .await?;
+    let speed = ReadingSpeed {
+        pages_per_hour: (seconds > 0).then(|| pages as f64 * 3600.0 / seconds as f64),
+        seconds_per_page: (pages > 0 && seconds > 0).then(|| seconds as f64 / pages as f64),
+    };
+
+    let names: &[&str] = &["creators", "genres", "publishers", "libraries", "formats"];
+    let mut read = count_facets(db_pool, &books_request(true), Some(names)).await?;
+    let all = count_facets(db_pool, &books_request(false), Some(&names[3..])).await?;
+    let mut top = |name: &str| {
+        let mut values = read.remove(name).unwrap_or_default();
+        values.truncate(TOP_LIMIT);
+        values
+    };
+    let (top_creators, top_genres, top_publishers) =
+        (top("creators"), top("genres"), top("publishers"));
+
+    Ok(ReadingStats {
+        period,
+        from: from.to_string(),
+        to: to.to_string(),
+        series: series.into_values().collect(),
+        totals,
+        streaks: get_streaks(&days, today),
+        speed,
+        top_creators,
+        top_genres,
+        top_publishers,
+        libraries: get_completion(&all["libraries"], &read["libraries"]),
+        formats: get_completion(&all["formats"], &read["formats"]),
+    })
+}
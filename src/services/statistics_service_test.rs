@@ -0,0 +1,260 @@
+#[cfg(test)]
+mod tests {
+    use crate::repositories::database_repo::make_test_db;
+    use crate::services::reading_history_service::{PageView, record_page_view};
+    use crate::services::statistics_service::*;
+    use chrono::NaiveDate;
+    use sqlx::SqlitePool;
+    use tempfile::TempDir;
+
+    fn at(day: u32, hour: u32, minute: u32) -> i64 {
+        NaiveDate::from_ymd_opt(2026, 10, day)
+            .unwrap()
+            .and_hms_opt(hour, minute, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp()
+    }
+
+    async fn setup_db() -> (TempDir, SqlitePool) {
+        let (temp, pool) = make_test_db().await;
+        sqlx::query(
+            r#"INSERT INTO Libraries (NAME, PATH, API_ID) VALUES ('Comics', '/comics', '1'), ('Manga', '/manga', '2');
+               INSERT INTO Series (ID_Series, title, genres, favorite, PATH) VALUES
+               ('10_1', 'Civil War', '["Action"]', false, '/comics/civil-war'),
+               ('20_2', 'Berserk', '["Action","Horror"]', false, '/manga/berserk');"#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        for (id, series, read, creators, format, publisher, path) in [
+            (
+                "b1",
+                "10_1",
+                1,
+                r#"{"items":[{"name":"Mark Millar","role":"writer"}]}"#,
+                "Comic",
+                "Marvel",
+                "/comics/civil-war/01.cbz",
+            ),
+            (
+                "b2",
+                "10_1",
+                0,
+                r#"{"items":[{"name":"Mark Millar","role":"writer"}]}"#,
+                "Comic",
+                "Marvel",
+                "/comics/civil-war/02.cbz",
+            ),
+            (
+                "b3",
+                "20_2",
+                1,
+                r#"["Kentaro Miura"]"#,
+                "BOOK",
+                "Dark Horse",
+                "/manga/berserk/01.cbz",
+            ),
+        ] {
+            sqlx::query(
+                "INSERT INTO Books (ID_book, API_ID, NOM, read, reading, unread, favorite, last_page, folder, PATH, ID_Series, creators, format, publisher) \
+                 VALUES (?, '1', ?, ?, false, false, false, 0, false, ?, ?, ?, ?, ?);",
+            )
+            .bind(id)
+            .bind(format!("Book {}", id))
+            .bind(read)
+            .bind(path)
+            .bind(series)
+            .bind(creators)
+            .bind(format)
+            .bind(publisher)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+        for (book_id, page, timestamp) in [
+            ("b1", 0, at(10, 10, 0)),
+            ("b1", 1, at(10, 10, 10)),
+            ("b1", 2, at(10, 10, 20)),
+            ("b3", 0, at(11, 10, 0)),
+            ("b3", 1, at(12, 10, 0)),
+            ("b3", 2, at(12, 10, 30)),
+        ] {
+            let view = PageView {
+                book_id: book_id.to_string(),
+                page,
+                page_count: Some(3),
+                user_agent: None,
+            };
+            record_page_view(&pool, &view, timestamp).await.unwrap();
+        }
+        (temp, pool)
+    }
+
+    fn query(period: StatsPeriod, from: &str, to: &str) -> StatsQuery {
+        StatsQuery {
+            period: Some(period),
+            from: Some(from.to_string()),
+            to: Some(to.to_string()),
+            offset: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reading_stats_time_series() {
+        let (_temp, pool) = setup_db().await;
+        let now = at(12, 12, 0);
+        let stats = get_reading_stats(
+            &pool,
+            &query(StatsPeriod::Day, "2026-10-09", "2026-10-12"),
+            now,
+        )
+        .await
+        .unwrap();
+        let series: Vec<(&str, i64, i64, i64)> = stats
+            .series
+            .iter()
+            .map(|point| {
+                (
+                    point.period.as_str(),
+                    point.pages,
+                    point.books,
+                    point.time_spent,
+                )
+            })
+            .collect();
+        assert_eq!(
+            series,
+            vec![
+                ("2026-10-09", 0, 0, 0),
+                ("2026-10-10", 3, 1, 1200),
+                ("2026-10-11", 1, 0, 0),
+                ("2026-10-12", 2, 1, 1800),
+            ]
+        );
+        assert_eq!(
+            (
+                stats.totals.pages,
+                stats.totals.books,
+                stats.totals.sessions
+            ),
+            (6, 2, 3)
+        );
+        assert_eq!(
+            stats.streaks,
+            Streaks {
+                current: 3,
+                longest: 3
+            }
+        );
+        assert_eq!(stats.speed.pages_per_hour, Some(6.0));
+        assert_eq!(stats.speed.seconds_per_page, Some(600.0));
+
+        let weeks = get_reading_stats(
+            &pool,
+            &query(StatsPeriod::Week, "2026-10-01", "2026-10-12"),
+            now,
+        )
+        .await
+        .unwrap();
+        let series: Vec<(&str, i64)> = weeks
+            .series
+            .iter()
+            .map(|point| (point.period.as_str(), point.pages))
+            .collect();
+        assert_eq!(
+            series,
+            vec![("2026-09-28", 0), ("2026-10-05", 4), ("2026-10-12", 2)]
+        );
+        let months = get_reading_stats(
+            &pool,
+            &StatsQuery {
+                period: Some(StatsPeriod::Month),
+                ..Default::default()
+            },
+            now,
+        )
+        .await
+        .unwrap();
+        assert_eq!(months.series.len(), 12);
+        assert_eq!(months.series[11].period, "2026-10");
+        assert_eq!(months.series[11].pages, 6);
+
+        let later = get_reading_stats(&pool, &StatsQuery::default(), at(20, 12, 0))
+            .await
+            .unwrap();
+        assert_eq!(later.series.len(), 30);
+        assert_eq!(later.streaks.current, 0);
+        assert_eq!(later.streaks.longest, 3);
+
+        for (query, message) in [
+            (
+                query(StatsPeriod::Day, "2026-10-12", "2026-10-01"),
+                "Invalid range: from is after to",
+            ),
+            (
+                query(StatsPeriod::Day, "yesterday", "2026-10-01"),
+                "Invalid date: yesterday",
+            ),
+            (
+                StatsQuery {
+                    offset: Some(1000),
+                    ..Default::default()
+                },
+                "Invalid offset: 1000",
+            ),
+        ] {
+            let error = get_reading_stats(&pool, &query, now).await.unwrap_err();
+            assert_eq!(error.to_string(), message);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_reading_stats_breakdowns() {
+        let (_temp, pool) = setup_db().await;
+        let stats = get_reading_stats(&pool, &StatsQuery::default(), at(12, 12, 0))
+            .await
+            .unwrap();
+        let labels = |values: &[crate::services::facet_service::FacetValue]| {
+            values
+                .iter()
+                .map(|value| (value.label.clone(), value.count))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            labels(&stats.top_genres),
+            vec![("Action".to_string(), 2), ("Horror".to_string(), 1)]
+        );
+        assert_eq!(
+            labels(&stats.top_creators),
+            vec![
+                ("Kentaro Miura".to_string(), 1),
+                ("Mark Millar".to_string(), 1)
+            ]
+        );
+        assert_eq!(
+            labels(&stats.top_publishers),
+            vec![("Dark Horse".to_string(), 1), ("Marvel".to_string(), 1)]
+        );
+        let completion = |values: &[Completion]| {
+            values
+                .iter()
+                .map(|value| (value.label.clone(), value.total, value.read, value.rate))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            completion(&stats.libraries),
+            vec![
+                ("Comics".to_string(), 2, 1, 0.5),
+                ("Manga".to_string(), 1, 1, 1.0)
+            ]
+        );
+        assert_eq!(
+            completion(&stats.formats),
+            vec![
+                ("Comic".to_string(), 2, 1, 0.5),
+                ("BOOK".to_string(), 1, 1, 1.0)
+            ]
+        );
+    }
+}
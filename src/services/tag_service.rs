@@ -0,0 +1,189 @@
+use crate::services::collection_service::ItemType;
+use serde::Serialize;
+use sqlx::sqlite::SqliteRow;
+use sqlx::{Row, Sqlite, SqlitePool, Transaction};
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Tag {
+    pub id: i64,
+    pub name: String,
+    pub books: i64,
+    pub series: i64,
+}
+
+fn row_to_tag(row: &SqliteRow) -> Result<Tag, sqlx::Error> {
+    Ok(Tag {
+        id: row.try_get("ID_TAG")?,
+        name: row.try_get("NAME")?,
+        books: row.try_get("book_count")?,
+        series: row.try_get("series_count")?,
+    })
+}
+
+pub fn normalize_tag_names(names: &[String]) -> Vec<String> {
+    let mut normalized: Vec<String> = Vec::new();
+    for name in names {
+        let name = name.trim();
+        if !name.is_empty()
+            && !normalized
+                .iter()
+                .any(|existing| existing.eq_ignore_ascii_case(name))
+        {
+            normalized.push(name.to_string());
+        }
+    }
+    normalized
+}
+
+pub async fn list_tags(db_pool: &SqlitePool) -> Result<Vec<Tag>, sqlx::Error> {
+    sqlx::query(
+        "SELECT Tags.ID_TAG, Tags.NAME, \
+         COUNT(CASE WHEN TagLinks.itemType = 'book' THEN 1 END) AS book_count, \
+         COUNT(CASE WHEN TagLinks.itemType = 'series' THEN 1 END) AS series_count \
+         FROM Tags LEFT JOIN TagLinks ON TagLinks.ID_TAG = Tags.ID_TAG \
+         GROUP BY Tags.ID_TAG ORDER BY Tags.NAME COLLATE NOCASE;",
+    )
+    .fetch_all(db_pool)
+    .await?
+    .iter()
+    .map(row_to_tag)
+    .collect()
+}
+
+async fn get_or_create_tag(
+    tx: &mut Transaction<'_, Sqlite>,
+    name: &str,
+) -> Result<i64, sqlx::Error> {
+    sqlx::query("INSERT OR IGNORE INTO Tags (NAME) VALUES (?);")
+        .bind(name)
+        .execute(&mut **tx)
+        .await?;
+    sqlx::query_scalar("SELECT ID_TAG FROM Tags WHERE NAME = ?;")
+        .bind(name)
+        .fetch_one(&mut **tx)
+        .await
+}
+
+pub async fn create_tag(db_pool: &SqlitePool, name: &str) -> Result<i64, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    let id = get_or_create_tag(&mut tx, name).await?;
+    tx.commit().await?;
+    Ok(id)
+}
+
+pub async fn rename_tag(
+    db_pool: &SqlitePool,
+    tag_id: i64,
+    name: &str,
+) -> Result<bool, sqlx::Error> {
+    let result = sqlx::query("UPDATE Tags SET NAME = ? WHERE ID_TAG = ?;")
+        .bind(name)
+        .bind(tag_id)
+        .execute(db_pool)
+        .await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn delete_tag(db_pool: &SqlitePool, tag_id: i64) -> Result<bool, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    sqlx::query("DELETE FROM TagLinks WHERE ID_TAG = ?;")
+        .bind(tag_id)
+        .execute(&mut *tx)
+        .await?;
+    let result = sqlx::query("DELETE FROM Tags WHERE ID_TAG = ?;")
+        .bind(tag_id)
+        .execute(&mut *tx)
+        .await?;
+    tx.commit().await?;
+    Ok(result.rows_affected() > 0)
+}
+
+pub async fn get_item_tags(
+    db_pool: &SqlitePool,
+    item_type: ItemType,
+    item_id: &str,
+) -> Result<Vec<String>, sqlx::Error> {
+    sqlx::query_scalar(
+        "SELECT Tags.NAME FROM TagLinks JOIN Tags ON Tags.ID_TAG = TagLinks.ID_TAG \
+         WHERE TagLinks.itemType = ? AND TagLinks.ID_item = ? ORDER BY Tags.NAME COLLATE NOCASE;",
+    )
+    .bind(item_type.as_str())
+    .bind(item_id)
+    .fetch_all(db_pool)
+    .await
+}
+
+pub async fn set_item_tags(
+    db_pool: &SqlitePool,
+    item_type: ItemType,
+    item_id: &str,
+    names: &[String],
+) -> Result<(), sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    sqlx::query("DELETE FROM TagLinks WHERE itemType = ? AND ID_item = ?;")
+        .bind(item_type.as_str())
+        .bind(item_id)
+        .execute(&mut *tx)
+        .await?;
+    for name in normalize_tag_names(names) {
+        let tag_id = get_or_create_tag(&mut tx, &name).await?;
+        sqlx::query("INSERT OR IGNORE INTO TagLinks (ID_TAG, itemType, ID_item) VALUES (?, ?, ?);")
+            .bind(tag_id)
+            .bind(item_type.as_str())
+            .bind(item_id)
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+    Ok(())
+}
+
+pub async fn tag_items(
+    db_pool: &SqlitePool,
+    names: &[String],
+    items: &[(ItemType, String)],
+) -> Result<u64, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    let mut added = 0;
+    for name in normalize_tag_names(names) {
+        let tag_id = get_or_create_tag(&mut tx, &name).await?;
+        for (item_type, item_id) in items {
+            let result = sqlx::query(
+                "INSERT OR IGNORE INTO TagLinks (ID_TAG, itemType, ID_item) VALUES (?, ?, ?);",
+            )
+            .bind(tag_id)
+            .bind(item_type.as_str())
+            .bind(item_id)
+            .execute(&mut *tx)
+            .await?;
+            added += result.rows_affected();
+        }
+    }
+    tx.commit().await?;
+    Ok(added)
+}
+
+pub async fn untag_items(
+    db_pool: &SqlitePool,
+    names: &[String],
+    items: &[(ItemType, String)],
+) -> Result<u64, sqlx::Error> {
+    let mut tx = db_pool.begin().await?;
+    let mut removed = 0;
+    for name in normalize_tag_names(names) {
+        for (item_type, item_id) in items {
+            let result = sqlx::query(
+                "DELETE FROM TagLinks WHERE ID_TAG = (SELECT ID_TAG FROM Tags WHERE NAME = ?) \
+                 AND itemType = ? AND ID_item = ?;",
+            )
+            .bind(&name)
+            .bind(item_type.as_str())
+            .bind(item_id)
+            .execute(&mut *tx)
+            .await?;
+            removed += result.rows_affected();
+        }
+    }
+    tx.commit().await?;
+    Ok(removed)
+}
@@ -0,0 +1,94 @@
+#[cfg(test)]
+mod tests {
+    use crate::repositories::database_repo::make_test_db;
+    use crate::services::collection_service::ItemType;
+    use crate::services::tag_service::*;
+
+    fn names(tags: &[&str]) -> Vec<String> {
+        tags.iter().map(|tag| tag.to_string()).collect()
+    }
+
+    #[test]
+    fn test_normalize_tag_names_trims_and_dedups() {
+        assert_eq!(
+            normalize_tag_names(&names(&[" Horror ", "horror", "", "Sci-Fi"])),
+            vec!["Horror", "Sci-Fi"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_set_item_tags_reuses_tags_case_insensitively() {
+        let (_temp, pool) = make_test_db().await;
+        let horror = create_tag(&pool, "Horror").await.unwrap();
+
+        set_item_tags(&pool, ItemType::Book, "b1", &names(&["horror", "Signed"]))
+            .await
+            .unwrap();
+        set_item_tags(&pool, ItemType::Series, "s1", &names(&["HORROR"]))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            get_item_tags(&pool, ItemType::Book, "b1").await.unwrap(),
+            vec!["Horror", "Signed"]
+        );
+        let tags = list_tags(&pool).await.unwrap();
+        assert_eq!(tags.len(), 2);
+        assert_eq!((tags[0].id, tags[0].books, tags[0].series), (horror, 1, 1));
+
+        set_item_tags(&pool, ItemType::Book, "b1", &names(&["Signed"]))
+            .await
+            .unwrap();
+        assert_eq!(
+            get_item_tags(&pool, ItemType::Book, "b1").await.unwrap(),
+            vec!["Signed"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bulk_tagging_rename_and_delete() {
+        let (_temp, pool) = make_test_db().await;
+        let items = vec![
+            (ItemType::Book, "b1".to_string()),
+            (ItemType::Book, "b2".to_string()),
+            (ItemType::Series, "s1".to_string()),
+        ];
+
+        assert_eq!(
+            tag_items(&pool, &names(&["To lend"]), &items)
+                .await
+                .unwrap(),
+            3
+        );
+        assert_eq!(
+            tag_items(&pool, &names(&["to lend"]), &items)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            untag_items(&pool, &names(&["To lend"]), &items[..1])
+                .await
+                .unwrap(),
+            1
+        );
+
+        let tag = list_tags(&pool).await.unwrap().remove(0);
+        assert!(rename_tag(&pool, tag.id, "Lent out").await.unwrap());
+        assert_eq!(
+            get_item_tags(&pool, ItemType::Series, "s1").await.unwrap(),
+            vec!["Lent out"]
+        );
+        let other = create_tag(&pool, "Keep").await.unwrap();
+        assert!(rename_tag(&pool, other, "lent OUT").await.is_err());
+
+        assert!(delete_tag(&pool, tag.id).await.unwrap());
+        assert!(!delete_tag(&pool, tag.id).await.unwrap());
+        assert!(
+            get_item_tags(&pool, ItemType::Book, "b2")
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
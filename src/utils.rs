@@ -1,4 +1,6 @@
 use rand::Rng;
+use regex::Regex;
+use std::cmp::Ordering;
 use tracing::error;
 
 pub const VALID_BOOK_EXTENSION: &[&str] = &[
@@ -51,6 +53,26 @@ pub(crate) fn generate_random_id() -> u32 {
     id
 }
 
+pub fn generate_stable_id(key: &str) -> u64 {
+    let digest = md5::compute(key.as_bytes());
+    let mut bytes = [0u8; 8];
+    bytes.copy_from_slice(&digest.0[..8]);
+    u64::from_be_bytes(bytes)
+}
+
+pub fn get_xml_attribute(tag: &str, attribute: &str) -> Option<String> {
+    let attribute_regex = Regex::new(&format!(
+        r#"\s{}\s*=\s*(?:"([^"]*)"|'([^']*)')"#,
+        regex::escape(attribute)
+    ))
+    .unwrap();
+    attribute_regex.captures(tag).and_then(|caps| {
+        caps.get(1)
+            .or_else(|| caps.get(2))
+            .map(|value| value.as_str().to_string())
+    })
+}
+
 pub fn strip_outer_quotes(s: &str) -> &str {
     if s.starts_with('"') && s.ends_with('"') && s.len() >= 2 {
         &s[1..s.len() - 1]
@@ -58,3 +80,44 @@ pub fn strip_outer_quotes(s: &str) -> &str {
         s
     }
 }
+
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chars = a.chars().peekable();
+    let mut b_chars = b.chars().peekable();
+    loop {
+        match (a_chars.peek().copied(), b_chars.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(ca), Some(cb)) if ca.is_ascii_digit() && cb.is_ascii_digit() => {
+                let mut a_number = String::new();
+                while let Some(c) = a_chars.peek().copied().filter(|c| c.is_ascii_digit()) {
+                    a_number.push(c);
+                    a_chars.next();
+                }
+                let mut b_number = String::new();
+                while let Some(c) = b_chars.peek().copied().filter(|c| c.is_ascii_digit()) {
+                    b_number.push(c);
+                    b_chars.next();
+                }
+                let a_trimmed = a_number.trim_start_matches('0');
+                let b_trimmed = b_number.trim_start_matches('0');
+                let ordering = a_trimmed
+                    .len()
+                    .cmp(&b_trimmed.len())
+                    .then_with(|| a_trimmed.cmp(b_trimmed));
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+            }
+            (Some(ca), Some(cb)) => {
+                let ordering = ca.to_lowercase().cmp(cb.to_lowercase());
+                if ordering != Ordering::Equal {
+                    return ordering;
+                }
+                a_chars.next();
+                b_chars.next();
+            }
+        }
+    }
+}